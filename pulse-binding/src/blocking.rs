@@ -0,0 +1,269 @@
+// Copyright 2026 Lyndon Brown
+//
+// This file is part of the PulseAudio Rust language binding.
+//
+// Licensed under the MIT license or the Apache license (version 2.0), at your option. You may not
+// copy, modify, or distribute this file except in compliance with said license. You can find copies
+// of these licenses either in the LICENSE-MIT and LICENSE-APACHE files, or alternatively at
+// <http://opensource.org/licenses/MIT> and <http://www.apache.org/licenses/LICENSE-2.0>
+// respectively.
+
+//! A blocking client, built upon the threaded main loop.
+//!
+//! # Overview
+//!
+//! Many simple tools just want to query or adjust a few things on the server, one after the other.
+//! Doing so with the asynchronous API, even with the [threaded main loop], involves waiting for the
+//! context to become ready, and then waiting for each operation to complete, with the lock held.
+//!
+//! The [`BlockingClient`] takes care of all of this, owning both a [threaded main loop] and a
+//! [`Context`], and offering synchronous methods returning owned results.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! extern crate libpulse_binding as pulse;
+//!
+//! use pulse::blocking::BlockingClient;
+//! use pulse::time::MicroSeconds;
+//!
+//! let timeout = MicroSeconds::from_secs(5).unwrap();
+//! let mut client = BlockingClient::connect("FooApp", None, Some(timeout))
+//!     .expect("Failed to connect");
+//!
+//! let server = client.server_info().unwrap();
+//! println!("Connected to {:?}", server.server_name);
+//!
+//! for sink in client.sinks().unwrap() {
+//!     println!("{}: {:?}", sink.index, sink.description);
+//! }
+//! ```
+//!
+//! [threaded main loop]: mod@crate::mainloop::threaded
+
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use crate::callbacks::ListResult;
+use crate::context::{Context, FlagSet as ContextFlagSet, State as ContextState};
+use crate::context::introspect::{ServerInfo, SinkInfo};
+use crate::def;
+use crate::error::{Code, PAErr};
+use crate::mainloop::api::Mainloop as MainloopTrait;
+use crate::mainloop::threaded::{LockGuard, Mainloop};
+use crate::operation::{Operation, State as OperationState};
+use crate::time::{MicroSeconds, MonotonicTs};
+use crate::volume::ChannelVolumes;
+
+/// A blocking client, owning a threaded main loop and a connected context.
+///
+/// See the [module level documentation](mod@self) for details.
+pub struct BlockingClient {
+    // Note, the context must be dropped before the main loop.
+    /// The connected context.
+    context: Context,
+    /// The threaded main loop the context is attached to.
+    mainloop: Mainloop,
+}
+
+impl BlockingClient {
+    /// Creates a new client, connecting it to the server, and waiting for it to become ready.
+    ///
+    /// `name` is used as the application name. `server` is the server to connect to, with `None`
+    /// meaning the default.
+    ///
+    /// If `timeout` is given and the context has not become ready by the time it has elapsed, the
+    /// [`PAErr`] form of the [`Code::Timeout`] error is returned. Otherwise should the connection
+    /// fail, the error of the context is returned.
+    pub fn connect(name: &str, server: Option<&str>, timeout: Option<MicroSeconds>)
+        -> Result<Self, PAErr>
+    {
+        let mut mainloop = Mainloop::new().ok_or(PAErr::from(Code::Internal))?;
+        let mut context = Context::new(&mainloop, name).ok_or(PAErr::from(Code::Internal))?;
+
+        // Wake up whoever is waiting upon any state change; if the context fails while an
+        // operation is in progress, the operation will be cancelled.
+        let handle = mainloop.handle();
+        context.set_state_callback(Some(Box::new(move || { let _ = handle.signal(false); })));
+
+        let mut guard = mainloop.lock_guard()?;
+        context.connect(server, ContextFlagSet::NOFLAGS, None)?;
+        mainloop.start()?;
+
+        if let Err(e) = Self::wait_until_ready(&mut mainloop, &context, &mut guard, timeout) {
+            // Stop the event loop thread before the context gets destroyed
+            context.set_state_callback(None);
+            context.disconnect();
+            drop(guard);
+            let _ = mainloop.try_stop();
+            return Err(e);
+        }
+        drop(guard);
+
+        Ok(Self { context, mainloop })
+    }
+
+    /// Waits, with the lock held, for the context to become ready.
+    fn wait_until_ready(mainloop: &mut Mainloop, context: &Context, guard: &mut LockGuard,
+        timeout: Option<MicroSeconds>) -> Result<(), PAErr>
+    {
+        let timed_out = Rc::new(Cell::new(false));
+        let _timer = match timeout {
+            Some(t) => {
+                let timed_out_ref = Rc::clone(&timed_out);
+                let handle = mainloop.handle();
+                let timer = mainloop.new_timer_event_rt(MonotonicTs::now() + t,
+                    Box::new(move |_| {
+                        timed_out_ref.set(true);
+                        let _ = handle.signal(false);
+                    }));
+                Some(timer.ok_or(PAErr::from(Code::Internal))?)
+            },
+            None => None,
+        };
+
+        loop {
+            match context.get_state() {
+                ContextState::Ready => return Ok(()),
+                ContextState::Failed | ContextState::Terminated => return Err(context.errno()),
+                _ if timed_out.get() => return Err(PAErr::from(Code::Timeout)),
+                _ => guard.wait(),
+            }
+        }
+    }
+
+    /// Gets information about the server.
+    pub fn server_info(&mut self) -> Result<ServerInfo<'static>, PAErr> {
+        let result = Rc::new(RefCell::new(None));
+        let result_ref = Rc::clone(&result);
+        let handle = self.mainloop.handle();
+
+        let mut guard = self.mainloop.lock_guard()?;
+        let op = self.context.introspect().get_server_info(move |info| {
+            *result_ref.borrow_mut() = Some(info.to_owned());
+            let _ = handle.signal(false);
+        });
+        self.wait_for(&mut guard, &op)?;
+
+        let info = result.borrow_mut().take();
+        info.ok_or_else(|| self.context.errno())
+    }
+
+    /// Gets information about all sinks.
+    pub fn sinks(&mut self) -> Result<Vec<SinkInfo<'static>>, PAErr> {
+        let result = Rc::new(RefCell::new(Vec::new()));
+        let result_ref = Rc::clone(&result);
+        let failed = Rc::new(Cell::new(false));
+        let failed_ref = Rc::clone(&failed);
+        let handle = self.mainloop.handle();
+
+        let mut guard = self.mainloop.lock_guard()?;
+        let op = self.context.introspect().get_sink_info_list(move |r| {
+            match r {
+                ListResult::Item(info) => {
+                    result_ref.borrow_mut().push(info.to_owned());
+                    return;
+                },
+                ListResult::End => {},
+                ListResult::Error => failed_ref.set(true),
+            }
+            let _ = handle.signal(false);
+        });
+        self.wait_for(&mut guard, &op)?;
+
+        match failed.get() {
+            true => Err(self.context.errno()),
+            false => Ok(result.replace(Vec::new())),
+        }
+    }
+
+    /// Sets the volume of the sink with the specified index.
+    pub fn set_sink_volume(&mut self, index: u32, volume: &ChannelVolumes) -> Result<(), PAErr> {
+        self.wait_for_success(|context, cb| {
+            context.introspect().set_sink_volume_by_index(index, volume, Some(cb))
+        })
+    }
+
+    /// Sets the volume of the sink with the specified name.
+    pub fn set_sink_volume_by_name(&mut self, name: &str, volume: &ChannelVolumes)
+        -> Result<(), PAErr>
+    {
+        self.wait_for_success(|context, cb| {
+            context.introspect().set_sink_volume_by_name(name, volume, Some(cb))
+        })
+    }
+
+    /// Loads a module, returning its index.
+    pub fn load_module(&mut self, name: &str, argument: &str) -> Result<u32, PAErr> {
+        let result = Rc::new(Cell::new(def::INVALID_INDEX));
+        let result_ref = Rc::clone(&result);
+        let handle = self.mainloop.handle();
+
+        let mut guard = self.mainloop.lock_guard()?;
+        let op = self.context.introspect().load_module(name, argument, move |index| {
+            result_ref.set(index);
+            let _ = handle.signal(false);
+        });
+        self.wait_for(&mut guard, &op)?;
+
+        match result.get() {
+            def::INVALID_INDEX => Err(self.context.errno()),
+            index => Ok(index),
+        }
+    }
+
+    /// Starts an operation taking a success callback, and waits for it to complete.
+    ///
+    /// A reported failure is turned into the error of the context.
+    fn wait_for_success<F>(&mut self, start: F) -> Result<(), PAErr>
+        where F: FnOnce(&mut Context, Box<dyn FnMut(bool) + 'static>) -> Operation<dyn FnMut(bool)>
+    {
+        let result = Rc::new(Cell::new(false));
+        let result_ref = Rc::clone(&result);
+        let handle = self.mainloop.handle();
+
+        let mut guard = self.mainloop.lock_guard()?;
+        let op = start(&mut self.context, Box::new(move |success| {
+            result_ref.set(success);
+            let _ = handle.signal(false);
+        }));
+        self.wait_for(&mut guard, &op)?;
+
+        match result.get() {
+            true => Ok(()),
+            false => Err(self.context.errno()),
+        }
+    }
+
+    /// Waits, with the lock held, for an operation to complete.
+    ///
+    /// Returns the error of the context if it failed in the meantime, or the [`PAErr`] form of the
+    /// [`Code::Killed`] error if the operation was otherwise cancelled.
+    fn wait_for<C: ?Sized>(&self, guard: &mut LockGuard, op: &Operation<C>) -> Result<(), PAErr> {
+        loop {
+            match op.get_state() {
+                OperationState::Done => return Ok(()),
+                OperationState::Cancelled => break,
+                OperationState::Running => {},
+            }
+            if !self.context.get_state().is_good() {
+                break;
+            }
+            guard.wait();
+        }
+        match self.context.get_state().is_good() {
+            true => Err(PAErr::from(Code::Killed)),
+            false => Err(self.context.errno()),
+        }
+    }
+}
+
+impl Drop for BlockingClient {
+    fn drop(&mut self) {
+        if let Ok(guard) = self.mainloop.lock_guard() {
+            self.context.set_state_callback(None);
+            self.context.disconnect();
+            drop(guard);
+        }
+        let _ = self.mainloop.try_stop();
+    }
+}
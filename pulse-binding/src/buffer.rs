@@ -0,0 +1,503 @@
+// Copyright 2026 Lyndon Brown
+//
+// This file is part of the PulseAudio Rust language binding.
+//
+// Licensed under the MIT license or the Apache license (version 2.0), at your option. You may not
+// copy, modify, or distribute this file except in compliance with said license. You can find copies
+// of these licenses either in the LICENSE-MIT and LICENSE-APACHE files, or alternatively at
+// <http://opensource.org/licenses/MIT> and <http://www.apache.org/licenses/LICENSE-2.0>
+// respectively.
+
+//! Typed views of sample data.
+//!
+//! # Overview
+//!
+//! Stream data is exchanged with the server as raw bytes. This module allows such data to instead
+//! be worked with as slices of samples of the type corresponding to the sample format in use, such
+//! as `&[i16]` for [`Format::S16NE`], split into frames (one sample per channel).
+//!
+//! The [`Sample`] trait is implemented for each type that can represent samples of one or more
+//! formats:
+//!
+//! * `u8`: [`Format::U8`], [`Format::ALaw`] and [`Format::ULaw`] (the latter two being encoded).
+//! * `i16`: [`Format::S16NE`].
+//! * `i32`: [`Format::S32NE`] and [`Format::S24_32NE`].
+//! * `f32`: [`Format::FLOAT32NE`].
+//! * [`S24le`] and [`S24be`]: [`Format::S24le`] and [`Format::S24be`] respectively, these being
+//!   packed three byte samples, with accessors converting to and from `i32`.
+//!
+//! Note that only the native endian forms of the multi-byte formats can be viewed as slices of the
+//! native integer and floating point types; reverse endian data must be converted first, which
+//! [`to_native_endian()`] does in place, with [`swap_endian()`] doing the reverse for data to be
+//! written in a reverse endian format.
+//!
+//! A [`SampleBuf`] or [`SampleBufMut`] wraps a slice of samples along with the number of channels,
+//! guaranteeing that it holds a whole number of frames, which can be iterated over.
+//!
+//! Typed counterparts of the byte based stream data methods are available, such as
+//! [`Stream::write_samples()`], [`Stream::begin_write_samples()`] and [`Stream::peek_samples()`].
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use libpulse_binding::buffer::SampleBufMut;
+//!
+//! // Fill the buffer offered by the server with silence, then write it
+//! if let Some(mut buf) = stream.begin_write_samples::<f32>(None).unwrap() {
+//!     for frame in buf.frames_mut() {
+//!         frame.fill(0.0);
+//!     }
+//!     stream.write_samples(buf.as_slice(), 0, SeekMode::Relative).unwrap();
+//! }
+//! ```
+//!
+//! [`Stream::write_samples()`]: crate::stream::Stream::write_samples
+//! [`Stream::begin_write_samples()`]: crate::stream::Stream::begin_write_samples
+//! [`Stream::peek_samples()`]: crate::stream::Stream::peek_samples
+
+use std::mem::{align_of, size_of};
+use std::slice::{ChunksExact, ChunksExactMut};
+use crate::error::{Code, PAErr};
+use crate::sample::{Format, Spec};
+
+/// A type representing a single sample of one or more sample formats.
+///
+/// # Safety
+///
+/// Implementing types must be plain data, valid for any bit pattern, without padding, and with a
+/// size matching that of the formats they declare compatibility with.
+pub unsafe trait Sample: Copy + Default + 'static {
+    /// Whether or not samples of the specified format can be represented by this type.
+    fn is_compatible(format: Format) -> bool;
+
+    /// Gets the value representing silence in the specified format.
+    #[inline]
+    fn silence(_format: Format) -> Self {
+        Self::default()
+    }
+}
+
+unsafe impl Sample for u8 {
+    #[inline]
+    fn is_compatible(format: Format) -> bool {
+        matches!(format, Format::U8 | Format::ALaw | Format::ULaw)
+    }
+
+    #[inline]
+    fn silence(format: Format) -> Self {
+        match format {
+            Format::ALaw => 0xd5,
+            Format::ULaw => 0xff,
+            _ => 0x80,
+        }
+    }
+}
+
+unsafe impl Sample for i16 {
+    #[inline]
+    fn is_compatible(format: Format) -> bool {
+        format == Format::S16NE
+    }
+}
+
+unsafe impl Sample for i32 {
+    #[inline]
+    fn is_compatible(format: Format) -> bool {
+        format == Format::S32NE || format == Format::S24_32NE
+    }
+}
+
+unsafe impl Sample for f32 {
+    #[inline]
+    fn is_compatible(format: Format) -> bool {
+        format == Format::FLOAT32NE
+    }
+}
+
+/// A signed 24-bit sample, packed in three bytes, little endian.
+#[repr(transparent)]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct S24le(pub [u8; 3]);
+
+impl S24le {
+    /// Gets the value of the sample, sign extended.
+    #[inline]
+    pub fn to_i32(self) -> i32 {
+        let [a, b, c] = self.0;
+        // Place in the upper bytes, then shift down to sign extend
+        i32::from_le_bytes([0, a, b, c]) >> 8
+    }
+
+    /// Creates a sample from a value, which is truncated to the lower 24 bits.
+    #[inline]
+    pub fn from_i32(value: i32) -> Self {
+        let [a, b, c, _] = value.to_le_bytes();
+        Self([a, b, c])
+    }
+}
+
+unsafe impl Sample for S24le {
+    #[inline]
+    fn is_compatible(format: Format) -> bool {
+        format == Format::S24le
+    }
+}
+
+/// A signed 24-bit sample, packed in three bytes, big endian.
+#[repr(transparent)]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct S24be(pub [u8; 3]);
+
+impl S24be {
+    /// Gets the value of the sample, sign extended.
+    #[inline]
+    pub fn to_i32(self) -> i32 {
+        let [a, b, c] = self.0;
+        // Place in the upper bytes, then shift down to sign extend
+        i32::from_be_bytes([a, b, c, 0]) >> 8
+    }
+
+    /// Creates a sample from a value, which is truncated to the lower 24 bits.
+    #[inline]
+    pub fn from_i32(value: i32) -> Self {
+        let [_, a, b, c] = value.to_be_bytes();
+        Self([a, b, c])
+    }
+}
+
+unsafe impl Sample for S24be {
+    #[inline]
+    fn is_compatible(format: Format) -> bool {
+        format == Format::S24be
+    }
+}
+
+/// A signed 24-bit sample, packed in three bytes, native endian.
+#[cfg(target_endian = "little")]
+pub type S24ne = S24le;
+/// A signed 24-bit sample, packed in three bytes, native endian.
+#[cfg(target_endian = "big")]
+pub type S24ne = S24be;
+
+/// Views raw sample data of the specified format as a slice of samples.
+///
+/// Returns the [`PAErr`] form of the [`Code::NotSupported`] error if the format cannot be
+/// represented by the type, or of the [`Code::Invalid`] error if the data is not suitably aligned
+/// for the type, or its length is not a multiple of the sample size.
+pub fn cast_slice<T: Sample>(bytes: &[u8], format: Format) -> Result<&[T], PAErr> {
+    check_cast::<T>(bytes.as_ptr(), bytes.len(), format)?;
+    let len = bytes.len() / size_of::<T>();
+    Ok(unsafe { std::slice::from_raw_parts(bytes.as_ptr() as *const T, len) })
+}
+
+/// Views raw sample data of the specified format as a mutable slice of samples.
+///
+/// See [`cast_slice()`] for the errors possible.
+pub fn cast_slice_mut<T: Sample>(bytes: &mut [u8], format: Format) -> Result<&mut [T], PAErr> {
+    check_cast::<T>(bytes.as_ptr(), bytes.len(), format)?;
+    let len = bytes.len() / size_of::<T>();
+    Ok(unsafe { std::slice::from_raw_parts_mut(bytes.as_mut_ptr() as *mut T, len) })
+}
+
+/// Checks that raw data can be viewed as a slice of samples of the specified format.
+fn check_cast<T: Sample>(ptr: *const u8, len: usize, format: Format) -> Result<(), PAErr> {
+    if !T::is_compatible(format) {
+        return Err(PAErr::from(Code::NotSupported));
+    }
+    match ptr as usize % align_of::<T>() == 0 && len % size_of::<T>() == 0 {
+        true => Ok(()),
+        false => Err(PAErr::from(Code::Invalid)),
+    }
+}
+
+/// Views a slice of samples as raw bytes.
+#[inline]
+pub fn as_bytes<T: Sample>(samples: &[T]) -> &[u8] {
+    let len = std::mem::size_of_val(samples);
+    unsafe { std::slice::from_raw_parts(samples.as_ptr() as *const u8, len) }
+}
+
+/// Views a mutable slice of samples as raw bytes.
+#[inline]
+pub fn as_bytes_mut<T: Sample>(samples: &mut [T]) -> &mut [u8] {
+    let len = std::mem::size_of_val(samples);
+    unsafe { std::slice::from_raw_parts_mut(samples.as_mut_ptr() as *mut u8, len) }
+}
+
+/// Converts raw sample data of the specified format to native endian form, in place, returning
+/// the native endian format.
+///
+/// Data of formats which are already native endian (according to [`Format::is_ne()`]), or for
+/// which endianness does not apply, is left untouched. Returns the [`PAErr`] form of the
+/// [`Code::Invalid`] error if the format is invalid, or the length of the data is not a multiple
+/// of the sample size.
+pub fn to_native_endian(bytes: &mut [u8], format: Format) -> Result<Format, PAErr> {
+    match format.is_ne() {
+        Some(false) => swap_endian(bytes, format),
+        _ => check_samples(bytes.len(), format).map(|_| format),
+    }
+}
+
+/// Reverses the byte order of each sample of raw sample data of the specified format, in place,
+/// returning the format of the opposite endianness.
+///
+/// Data of formats for which endianness does not apply is left untouched. See
+/// [`to_native_endian()`] for the errors possible.
+pub fn swap_endian(bytes: &mut [u8], format: Format) -> Result<Format, PAErr> {
+    let swapped = match format {
+        Format::S16le => Format::S16be,
+        Format::S16be => Format::S16le,
+        Format::F32le => Format::F32be,
+        Format::F32be => Format::F32le,
+        Format::S32le => Format::S32be,
+        Format::S32be => Format::S32le,
+        Format::S24le => Format::S24be,
+        Format::S24be => Format::S24le,
+        Format::S24_32le => Format::S24_32be,
+        Format::S24_32be => Format::S24_32le,
+        Format::U8 | Format::ALaw | Format::ULaw => format,
+        Format::Invalid => return Err(PAErr::from(Code::Invalid)),
+    };
+    let size = check_samples(bytes.len(), format)?;
+    if size > 1 {
+        bytes.chunks_exact_mut(size).for_each(<[u8]>::reverse);
+    }
+    Ok(swapped)
+}
+
+/// Checks that raw data of the specified length forms a whole number of samples of the specified
+/// format, returning the sample size.
+fn check_samples(len: usize, format: Format) -> Result<usize, PAErr> {
+    match crate::dsp::sample_size(format) {
+        Some(size) if len % size == 0 => Ok(size),
+        _ => Err(PAErr::from(Code::Invalid)),
+    }
+}
+
+/// Checks a number of samples and channels, as forming a whole number of frames.
+fn check_frames(len: usize, channels: u8) -> Result<usize, PAErr> {
+    match channels {
+        0 => Err(PAErr::from(Code::Invalid)),
+        c if len % c as usize != 0 => Err(PAErr::from(Code::Invalid)),
+        c => Ok(c as usize),
+    }
+}
+
+/// A slice of samples, holding a whole number of frames.
+#[derive(Debug, Copy, Clone)]
+pub struct SampleBuf<'a, T: Sample> {
+    /// The samples, interleaved.
+    samples: &'a [T],
+    /// The number of channels, thus samples per frame.
+    channels: usize,
+}
+
+impl<'a, T: Sample> SampleBuf<'a, T> {
+    /// Wraps a slice of interleaved samples.
+    ///
+    /// Returns the [`PAErr`] form of the [`Code::Invalid`] error if `channels` is zero, or the
+    /// length of the slice is not a multiple of it.
+    pub fn new(samples: &'a [T], channels: u8) -> Result<Self, PAErr> {
+        let channels = check_frames(samples.len(), channels)?;
+        Ok(Self { samples, channels })
+    }
+
+    /// Views raw data of the specified sample spec.
+    ///
+    /// See [`cast_slice()`] and [`new()`](Self::new) for the errors possible.
+    pub fn from_bytes(bytes: &'a [u8], spec: &Spec) -> Result<Self, PAErr> {
+        Self::new(cast_slice(bytes, spec.format)?, spec.channels)
+    }
+
+    /// Gets the number of channels, thus samples per frame.
+    #[inline]
+    pub fn channels(&self) -> usize {
+        self.channels
+    }
+
+    /// Gets the number of frames.
+    #[inline]
+    pub fn frame_count(&self) -> usize {
+        self.samples.len() / self.channels
+    }
+
+    /// Gets the samples, interleaved.
+    #[inline]
+    pub fn as_slice(&self) -> &'a [T] {
+        self.samples
+    }
+
+    /// Gets the samples as raw bytes.
+    #[inline]
+    pub fn as_bytes(&self) -> &'a [u8] {
+        as_bytes(self.samples)
+    }
+
+    /// Gets the frame at the specified position.
+    #[inline]
+    pub fn frame(&self, index: usize) -> Option<&'a [T]> {
+        self.samples.chunks_exact(self.channels).nth(index)
+    }
+
+    /// Gets an iterator over the frames.
+    #[inline]
+    pub fn frames(&self) -> ChunksExact<'a, T> {
+        self.samples.chunks_exact(self.channels)
+    }
+}
+
+/// A mutable slice of samples, holding a whole number of frames.
+#[derive(Debug)]
+pub struct SampleBufMut<'a, T: Sample> {
+    /// The samples, interleaved.
+    samples: &'a mut [T],
+    /// The number of channels, thus samples per frame.
+    channels: usize,
+}
+
+impl<'a, T: Sample> SampleBufMut<'a, T> {
+    /// Wraps a mutable slice of interleaved samples.
+    ///
+    /// See [`SampleBuf::new()`] for the errors possible.
+    pub fn new(samples: &'a mut [T], channels: u8) -> Result<Self, PAErr> {
+        let channels = check_frames(samples.len(), channels)?;
+        Ok(Self { samples, channels })
+    }
+
+    /// Views raw data of the specified sample spec.
+    ///
+    /// See [`SampleBuf::from_bytes()`] for the errors possible.
+    pub fn from_bytes(bytes: &'a mut [u8], spec: &Spec) -> Result<Self, PAErr> {
+        Self::new(cast_slice_mut(bytes, spec.format)?, spec.channels)
+    }
+
+    /// Gets the number of channels, thus samples per frame.
+    #[inline]
+    pub fn channels(&self) -> usize {
+        self.channels
+    }
+
+    /// Gets the number of frames.
+    #[inline]
+    pub fn frame_count(&self) -> usize {
+        self.samples.len() / self.channels
+    }
+
+    /// Gets the samples, interleaved.
+    #[inline]
+    pub fn as_slice(&self) -> &[T] {
+        self.samples
+    }
+
+    /// Gets the samples, interleaved, for modification.
+    #[inline]
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        self.samples
+    }
+
+    /// Gets the samples as raw bytes.
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        as_bytes(self.samples)
+    }
+
+    /// Gets the frame at the specified position, for modification.
+    #[inline]
+    pub fn frame_mut(&mut self, index: usize) -> Option<&mut [T]> {
+        self.samples.chunks_exact_mut(self.channels).nth(index)
+    }
+
+    /// Gets an iterator over the frames.
+    #[inline]
+    pub fn frames(&self) -> ChunksExact<'_, T> {
+        self.samples.chunks_exact(self.channels)
+    }
+
+    /// Gets an iterator over the frames, for modification.
+    #[inline]
+    pub fn frames_mut(&mut self) -> ChunksExactMut<'_, T> {
+        self.samples.chunks_exact_mut(self.channels)
+    }
+
+    /// Shortens the buffer to the specified number of frames, having no effect if it holds fewer.
+    pub fn truncate(&mut self, frames: usize) {
+        let len = std::cmp::min(frames * self.channels, self.samples.len());
+        let samples = std::mem::take(&mut self.samples);
+        self.samples = &mut samples[..len];
+    }
+
+    /// Converts into the underlying slice of samples.
+    #[inline]
+    pub fn into_slice(self) -> &'a mut [T] {
+        self.samples
+    }
+}
+
+impl<'a, T: Sample> From<SampleBufMut<'a, T>> for SampleBuf<'a, T> {
+    #[inline]
+    fn from(buf: SampleBufMut<'a, T>) -> Self {
+        Self { samples: buf.samples, channels: buf.channels }
+    }
+}
+
+/// Result type for the [`Stream::peek_samples()`] method.
+///
+/// [`Stream::peek_samples()`]: crate::stream::Stream::peek_samples
+#[derive(Debug)]
+pub enum PeekSamples<'a, T: Sample> {
+    /// No data.
+    Empty,
+    /// Data hole with given size, in bytes.
+    Hole(usize),
+    /// Data available.
+    Data(SampleBuf<'a, T>),
+}
+
+/// Check conversion of packed 24-bit samples
+#[test]
+fn packed_24() {
+    assert_eq!(S24le([0x56, 0x34, 0x12]).to_i32(), 0x123456);
+    assert_eq!(S24be([0x12, 0x34, 0x56]).to_i32(), 0x123456);
+    assert_eq!(S24le([0xff, 0xff, 0xff]).to_i32(), -1);
+    assert_eq!(S24be([0x80, 0x00, 0x00]).to_i32(), -0x800000);
+    assert_eq!(S24le::from_i32(-2), S24le([0xfe, 0xff, 0xff]));
+    assert_eq!(S24be::from_i32(0x123456), S24be([0x12, 0x34, 0x56]));
+}
+
+/// Check casting and frame chunking of raw data
+#[test]
+fn casting() {
+    let spec = Spec { format: Format::S16NE, channels: 2, rate: 44100 };
+    let samples: [i16; 6] = [1, -1, 2, -2, 3, -3];
+    let bytes = as_bytes(&samples);
+
+    let buf = SampleBuf::<i16>::from_bytes(bytes, &spec).unwrap();
+    assert_eq!(buf.frame_count(), 3);
+    assert_eq!(buf.frame(1), Some(&[2, -2][..]));
+    assert_eq!(buf.frames().last(), Some(&[3, -3][..]));
+
+    // Incompatible format, partial frame, misaligned
+    assert_eq!(cast_slice::<f32>(bytes, Format::S16NE).unwrap_err(), Code::NotSupported.into());
+    assert!(SampleBuf::<i16>::from_bytes(&bytes[..10], &spec).is_err());
+    assert!(cast_slice::<i16>(&bytes[1..5], Format::S16NE).is_err());
+    assert!(cast_slice::<i16>(bytes, Format::S16RE).is_err());
+}
+
+/// Check byte swapping of reverse endian data
+#[test]
+fn endian_swapping() {
+    let mut bytes = [0x12, 0x34, 0x56, 0x78, 0x9a, 0xbc];
+    assert_eq!(swap_endian(&mut bytes, Format::S16be), Ok(Format::S16le));
+    assert_eq!(bytes, [0x34, 0x12, 0x78, 0x56, 0xbc, 0x9a]);
+    assert_eq!(swap_endian(&mut bytes, Format::S24le), Ok(Format::S24be));
+    assert_eq!(bytes, [0x78, 0x12, 0x34, 0x9a, 0xbc, 0x56]);
+    assert_eq!(swap_endian(&mut bytes, Format::U8), Ok(Format::U8));
+    assert_eq!(bytes, [0x78, 0x12, 0x34, 0x9a, 0xbc, 0x56]);
+
+    let mut bytes = 1.5f32.to_be_bytes();
+    assert_eq!(swap_endian(&mut bytes, Format::F32be), Ok(Format::F32le));
+    assert_eq!(f32::from_le_bytes(bytes), 1.5);
+
+    assert!(swap_endian(&mut [0; 6], Format::S32le).is_err());
+    assert!(swap_endian(&mut [0; 4], Format::Invalid).is_err());
+}
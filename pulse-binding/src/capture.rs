@@ -0,0 +1,236 @@
+// Copyright 2026 Lyndon Brown
+//
+// This file is part of the PulseAudio Rust language binding.
+//
+// Licensed under the MIT license or the Apache license (version 2.0), at your option. You may not
+// copy, modify, or distribute this file except in compliance with said license. You can find copies
+// of these licenses either in the LICENSE-MIT and LICENSE-APACHE files, or alternatively at
+// <http://opensource.org/licenses/MIT> and <http://www.apache.org/licenses/LICENSE-2.0>
+// respectively.
+
+//! Push-model capture.
+//!
+//! # Overview
+//!
+//! Consuming data from a recording stream involves responding to the read callback of the stream
+//! by repeatedly obtaining the next fragment with [`Stream::peek()`] and then removing it with
+//! [`Stream::discard()`], which must be done for holes as well as for data, otherwise recording
+//! stalls.
+//!
+//! Implementing the [`CaptureSink`] trait instead allows a consumer of data to simply be given
+//! chunks of frames. A [`Capture`] attaches a sink to a stream, taking care of the above. Holes in
+//! the data can either be filled with silence, or reported to the sink as gaps, as chosen with
+//! [`Holes`].
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use libpulse_binding::buffer::SampleBuf;
+//! use libpulse_binding::capture::{Capture, CaptureSink, Holes};
+//!
+//! /// Tracks the peak level.
+//! struct Peak(f32);
+//!
+//! impl CaptureSink for Peak {
+//!     type Sample = f32;
+//!
+//!     fn data(&mut self, buf: SampleBuf<'_, f32>) {
+//!         for sample in buf.as_slice() {
+//!             self.0 = self.0.max(sample.abs());
+//!         }
+//!     }
+//! }
+//!
+//! // With the stream connected for recording, in the `f32` native endian format
+//! let capture = Capture::new(&mut stream, Peak(0.0), Holes::Silence).unwrap();
+//! ```
+
+use std::cell::{Cell, RefCell, RefMut};
+use std::rc::Rc;
+use crate::buffer::{Sample, SampleBuf};
+use crate::error::{Code, PAErr};
+use crate::sample::Format;
+use crate::stream::{PeekResult, Stream};
+
+/// How holes in the data are to be handled.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Holes {
+    /// Holes are filled with silence, given to [`CaptureSink::data()`].
+    Silence,
+    /// Holes are reported to [`CaptureSink::gap()`].
+    Gap,
+}
+
+/// A consumer of data from a recording stream.
+///
+/// See the [module level documentation](mod@self) for details.
+pub trait CaptureSink: 'static {
+    /// The type of the samples, which must be able to represent the sample format of the stream.
+    type Sample: Sample;
+
+    /// Consumes a chunk of frames.
+    fn data(&mut self, buf: SampleBuf<'_, Self::Sample>);
+
+    /// Called upon a hole in the data, giving its length in frames, if holes are to be reported as
+    /// gaps.
+    fn gap(&mut self, _frames: usize) {}
+
+    /// Called upon the server reporting a buffer overflow.
+    fn overflow(&mut self) {}
+}
+
+/// A consumer of data attached to a recording stream.
+///
+/// See the [module level documentation](mod@self) for details.
+///
+/// Dropping this stops data being given to the sink, and thus removed from the stream, though the
+/// read and overflow callbacks of the stream remain set until replaced.
+pub struct Capture<S: CaptureSink> {
+    /// State shared with the stream callbacks.
+    shared: Rc<CaptureShared<S>>,
+}
+
+/// State shared between a [`Capture`] and the stream callbacks.
+struct CaptureShared<S: CaptureSink> {
+    /// The stream, of which a reference is held.
+    stream: *mut capi::pa_stream,
+    /// The consumer of data.
+    sink: RefCell<S>,
+    /// How holes are to be handled.
+    holes: Holes,
+    /// Silence, for filling holes, kept for reuse.
+    silence: RefCell<Vec<S::Sample>>,
+    /// The number of overflows that have occurred.
+    overflows: Cell<u64>,
+    /// The last error encountered in reading data from within the read callback.
+    last_error: Cell<Option<PAErr>>,
+}
+
+impl<S: CaptureSink> Capture<S> {
+    /// Attaches a sink to a recording stream, replacing its read and overflow callbacks.
+    ///
+    /// Note that PulseAudio documents overflow notification as applying to playback streams, thus
+    /// the server may never report overflows of recording streams.
+    ///
+    /// Returns the [`PAErr`] form of the [`Code::NotSupported`] error if the sample format of the
+    /// stream cannot be represented by the sample type of the sink, or of the [`Code::BadState`]
+    /// error if the sample spec of the stream is not available.
+    pub fn new(stream: &mut Stream, sink: S, holes: Holes) -> Result<Self, PAErr> {
+        let spec = stream.get_sample_spec().ok_or(PAErr::from(Code::BadState))?;
+        if !S::Sample::is_compatible(spec.format) {
+            return Err(PAErr::from(Code::NotSupported));
+        }
+        let shared = Rc::new(CaptureShared {
+            stream: unsafe { capi::pa_stream_ref(stream.ptr) },
+            sink: RefCell::new(sink),
+            holes,
+            silence: RefCell::new(Vec::new()),
+            overflows: Cell::new(0),
+            last_error: Cell::new(None),
+        });
+
+        let weak = Rc::downgrade(&shared);
+        stream.set_read_callback(Some(Box::new(move |_| {
+            if let Some(shared) = weak.upgrade() {
+                if let Err(e) = shared.read() {
+                    shared.last_error.set(Some(e));
+                }
+            }
+        })));
+        let weak = Rc::downgrade(&shared);
+        stream.set_overflow_callback(Some(Box::new(move || {
+            if let Some(shared) = weak.upgrade() {
+                shared.overflows.set(shared.overflows.get() + 1);
+                shared.sink.borrow_mut().overflow();
+            }
+        })));
+
+        Ok(Self { shared })
+    }
+
+    /// Gives the sink all of the data currently available.
+    ///
+    /// This is done automatically upon data becoming available, but can be used to catch up with
+    /// data which was available before the sink was attached.
+    #[inline]
+    pub fn pump(&self) -> Result<(), PAErr> {
+        self.shared.read()
+    }
+
+    /// Gets the number of overflows that have occurred.
+    #[inline]
+    pub fn overflow_count(&self) -> u64 {
+        self.shared.overflows.get()
+    }
+
+    /// Gets the last error encountered in reading data from within the read callback, if any.
+    ///
+    /// Note that a fragment which could not be given to the sink will have been discarded
+    /// regardless, lest recording stall.
+    #[inline]
+    pub fn last_error(&self) -> Option<PAErr> {
+        self.shared.last_error.get()
+    }
+
+    /// Gets the sink.
+    ///
+    /// Panics if called from within a method of the sink.
+    #[inline]
+    pub fn sink(&self) -> RefMut<'_, S> {
+        self.shared.sink.borrow_mut()
+    }
+}
+
+impl<S: CaptureSink> CaptureShared<S> {
+    /// Gives the sink all of the data currently available, removing it from the stream.
+    ///
+    /// Any trailing partial frame of a fragment is discarded.
+    fn read(&self) -> Result<(), PAErr> {
+        let mut stream = Stream::from_raw_weak(self.stream);
+        let spec = *stream.get_sample_spec().ok_or(PAErr::from(Code::BadState))?;
+        let frame_size = spec.frame_size();
+
+        loop {
+            match stream.peek()? {
+                PeekResult::Empty => return Ok(()),
+                PeekResult::Hole(size) => {
+                    stream.discard()?;
+                    self.hole(size / frame_size, spec.channels as usize, spec.format);
+                },
+                PeekResult::Data(data) => {
+                    let len = data.len() - data.len() % frame_size;
+                    // The fragment must be discarded regardless, lest recording stall
+                    let result = SampleBuf::from_bytes(&data[..len], &spec)
+                        .map(|buf| self.sink.borrow_mut().data(buf));
+                    stream.discard()?;
+                    result?;
+                },
+            }
+        }
+    }
+
+    /// Handles a hole of the specified number of frames.
+    fn hole(&self, frames: usize, channels: usize, format: Format) {
+        if frames == 0 {
+            return;
+        }
+        match self.holes {
+            Holes::Gap => self.sink.borrow_mut().gap(frames),
+            Holes::Silence => {
+                let mut silence = self.silence.borrow_mut();
+                silence.clear();
+                silence.resize(frames * channels, S::Sample::silence(format));
+                // Cannot fail, the length being a whole number of frames
+                if let Ok(buf) = SampleBuf::new(&silence, channels as u8) {
+                    self.sink.borrow_mut().data(buf);
+                }
+            },
+        }
+    }
+}
+
+impl<S: CaptureSink> Drop for CaptureShared<S> {
+    fn drop(&mut self) {
+        unsafe { capi::pa_stream_unref(self.stream) };
+    }
+}
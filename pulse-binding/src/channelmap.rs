@@ -54,6 +54,7 @@ pub const POSITION_MASK_ALL: PositionMask = 0xffffffffffffffffu64;
 #[repr(C)]
 #[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
 #[derive(FromPrimitive, ToPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Position {
     /* NOTE: This enum’s variants and variant values **must** remain identical to the `sys` crate
        (C API) equivalent */
@@ -327,6 +328,51 @@ impl PartialEq for Map {
     }
 }
 
+/// Serialized as the sequence of positions of the mapped channels.
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl serde::Serialize for Map {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.get())
+    }
+}
+
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<'de> serde::Deserialize<'de> for Map {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let positions = Vec::<Position>::deserialize(deserializer)?;
+        if positions.len() > Self::CHANNELS_MAX as usize {
+            return Err(serde::de::Error::invalid_length(positions.len(),
+                &"no more than `CHANNELS_MAX` positions"));
+        }
+        let mut map = Self::default();
+        map.set_len(positions.len() as u8);
+        map.get_mut().copy_from_slice(&positions);
+        Ok(map)
+    }
+}
+
+/// Check serialization round-trips, and that more than `CHANNELS_MAX` positions are rejected
+#[cfg(feature = "serde")]
+#[test]
+fn map_serde() {
+    let mut map = Map::default();
+    map.set_len(2);
+    map.get_mut().copy_from_slice(&[Position::FrontLeft, Position::Lfe]);
+    let json = serde_json::to_string(&map).unwrap();
+    assert_eq!(json, r#"["FrontLeft","Lfe"]"#);
+    assert_eq!(serde_json::from_str::<Map>(&json).unwrap().get(), map.get());
+    assert_eq!(serde_json::from_str::<Map>("[]").unwrap().len(), 0);
+
+    let full = vec![Position::Mono; Map::CHANNELS_MAX as usize];
+    let map = serde_json::from_value::<Map>(serde_json::to_value(&full).unwrap()).unwrap();
+    assert_eq!(map.get(), &full[..]);
+    let too_many = vec![Position::Mono; Map::CHANNELS_MAX as usize + 1];
+    assert!(serde_json::from_value::<Map>(serde_json::to_value(too_many).unwrap()).is_err());
+    assert!(serde_json::from_str::<Map>(r#"["Nowhere"]"#).is_err());
+}
+
 impl Position {
     /// Makes a bit mask from a channel position.
     pub const fn to_mask(self) -> PositionMask {
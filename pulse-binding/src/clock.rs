@@ -0,0 +1,347 @@
+// Copyright 2026 Lyndon Brown
+//
+// This file is part of the PulseAudio Rust language binding.
+//
+// Licensed under the MIT license or the Apache license (version 2.0), at your option. You may not
+// copy, modify, or distribute this file except in compliance with said license. You can find copies
+// of these licenses either in the LICENSE-MIT and LICENSE-APACHE files, or alternatively at
+// <http://opensource.org/licenses/MIT> and <http://www.apache.org/licenses/LICENSE-2.0>
+// respectively.
+
+//! A stream clock, for audio/video synchronisation.
+//!
+//! # Overview
+//!
+//! The time of a stream, as given by [`Stream::get_time()`], is in the sound card clock domain,
+//! which usually runs at a slightly different rate than the system clock, and is only as current
+//! as the latest timing update received from the server, unless the stream was connected with
+//! [`FlagSet::INTERPOLATE_TIMING`].
+//!
+//! A [`Clock`] samples the time of a stream upon each timing update, and interpolates between
+//! updates using the monotonic system clock, giving a presentation time which never goes
+//! backwards. It also estimates the drift of the sound card clock against the monotonic system
+//! clock, taking it into account when interpolating, and allows the presentation time to be
+//! determined for any [`MonotonicTs`], such as the time at which a video frame is to be shown.
+//!
+//! For best results, connect the stream with the flags of [`Clock::STREAM_FLAGS`], such that
+//! timing updates are received regularly without needing to be requested with
+//! [`Clock::update()`].
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use libpulse_binding::clock::Clock;
+//!
+//! let clock = Clock::new(&mut stream);
+//!
+//! // Upon each video frame
+//! if let Some(audio_time) = clock.presentation_time() {
+//!     let ahead = frame_pts.as_micros() as i64 - audio_time.inner() as i64;
+//!     // Delay, show or drop the frame, according to how far ahead of the audio it is
+//! }
+//! ```
+//!
+//! [`Stream::get_time()`]: crate::stream::Stream::get_time
+//! [`FlagSet::INTERPOLATE_TIMING`]: crate::stream::FlagSet::INTERPOLATE_TIMING
+
+use std::cell::Cell;
+use std::rc::Rc;
+use crate::error::PAErr;
+use crate::stream::{FlagSet, Stream};
+use crate::time::{MicroSeconds, MonotonicTs};
+
+/// The minimum span of continuous playback over which drift is estimated.
+const MIN_DRIFT_SPAN: MicroSeconds = MicroSeconds::SECOND;
+
+/// How far a sample may be from the time interpolated for it before the time of the stream is
+/// taken to have jumped, such as due to a seek or flush, rather than to have drifted.
+const JUMP_TOLERANCE: MicroSeconds = MicroSeconds(50 * MicroSeconds::MILLISECOND.0);
+
+/// The largest plausible drift, in parts per million, to which the estimate is clamped.
+const MAX_DRIFT_PPM: f64 = 1000.0;
+
+/// A sample of the time of a stream.
+#[derive(Debug, Copy, Clone)]
+struct Anchor {
+    /// The time of the stream.
+    time: MicroSeconds,
+    /// When it was sampled.
+    at: MonotonicTs,
+    /// Whether or not the stream was playing, thus its time advancing.
+    playing: bool,
+}
+
+/// Estimation of the time of a stream from samples of it, independent of the stream itself.
+#[derive(Debug, Copy, Clone, Default)]
+struct Estimator {
+    /// The latest sample.
+    anchor: Option<Anchor>,
+    /// The sample taken at the start of the current span of continuous playback.
+    reference: Option<Anchor>,
+    /// The estimated drift, in parts per million.
+    drift: Option<f64>,
+    /// The latest presentation time given.
+    last: MicroSeconds,
+}
+
+impl Estimator {
+    /// Takes a sample of the time of the stream, updating the drift estimate.
+    ///
+    /// If the time of the stream has jumped since the previous sample, the estimate is restarted.
+    fn sample(&mut self, anchor: Anchor) {
+        let expected = self.time_at(anchor.at);
+        self.anchor = Some(anchor);
+        match (anchor.playing, self.reference) {
+            (false, _) => self.reference = None,
+            (true, None) => self.reference = Some(anchor),
+            (true, Some(_)) if expected.map_or(false, |t| t.diff(anchor.time) > JUMP_TOLERANCE) => {
+                self.reference = Some(anchor);
+                self.drift = None;
+            },
+            (true, Some(reference)) => {
+                let span = anchor.at.0.diff(reference.at.0);
+                if span >= MIN_DRIFT_SPAN && anchor.time >= reference.time {
+                    let progress = (anchor.time - reference.time).0 as f64;
+                    let drift = (progress / span.0 as f64 - 1.0) * 1_000_000.0;
+                    self.drift = Some(drift.clamp(-MAX_DRIFT_PPM, MAX_DRIFT_PPM));
+                }
+            },
+        }
+    }
+
+    /// Gets the time of the stream at the specified monotonic system time, which may go
+    /// backwards between calls.
+    fn time_at(&self, ts: MonotonicTs) -> Option<MicroSeconds> {
+        let anchor = self.anchor?;
+        if !anchor.playing {
+            return Some(anchor.time);
+        }
+        let elapsed = ts.0.0 as f64 - anchor.at.0.0 as f64;
+        let rate = 1.0 + self.drift.unwrap_or(0.0) / 1_000_000.0;
+        let time = anchor.time.0 as f64 + elapsed * rate;
+        Some(MicroSeconds(time.max(0.0) as u64))
+    }
+
+    /// Gets the presentation time at the specified monotonic system time, which never goes
+    /// backwards between calls.
+    fn presentation_time(&mut self, ts: MonotonicTs) -> Option<MicroSeconds> {
+        let time = std::cmp::max(self.time_at(ts)?, self.last);
+        self.last = time;
+        Some(time)
+    }
+}
+
+/// A clock following the time of a stream.
+///
+/// See the [module level documentation](mod@self) for details.
+pub struct Clock {
+    /// State shared with the stream callbacks.
+    shared: Rc<ClockShared>,
+}
+
+/// State shared between a [`Clock`] and the stream callbacks.
+struct ClockShared {
+    /// The stream, of which a reference is held.
+    stream: *mut capi::pa_stream,
+    /// Estimation of the time of the stream.
+    estimator: Cell<Estimator>,
+}
+
+impl Clock {
+    /// The stream flags with which the stream should be connected, for best results.
+    pub const STREAM_FLAGS: FlagSet = FlagSet::INTERPOLATE_TIMING
+        .union(FlagSet::AUTO_TIMING_UPDATE);
+
+    /// Creates a clock following the time of a stream, replacing its latency update callback.
+    pub fn new(stream: &mut Stream) -> Self {
+        let shared = Rc::new(ClockShared {
+            stream: unsafe { capi::pa_stream_ref(stream.ptr) },
+            estimator: Cell::new(Estimator::default()),
+        });
+
+        let weak = Rc::downgrade(&shared);
+        stream.set_latency_update_callback(Some(Box::new(move || {
+            if let Some(shared) = weak.upgrade() {
+                shared.sample();
+            }
+        })));
+        shared.sample();
+
+        Self { shared }
+    }
+
+    /// Requests a timing update from the server, needed if the stream was not connected with
+    /// [`FlagSet::AUTO_TIMING_UPDATE`].
+    pub fn update(&self) {
+        let weak = Rc::downgrade(&self.shared);
+        Stream::from_raw_weak(self.shared.stream).update_timing_info(Some(Box::new(move |_| {
+            if let Some(shared) = weak.upgrade() {
+                shared.sample();
+            }
+        })));
+    }
+
+    /// Gets the current presentation time, i.e. the time of the stream, which never goes
+    /// backwards, or `None` if no timing information has been received yet.
+    ///
+    /// Should the time of the stream jump backwards, such as upon a flush or seek, the same time
+    /// is given until it catches up again, unless [`reset()`](Self::reset) is used.
+    pub fn presentation_time(&self) -> Option<MicroSeconds> {
+        let mut estimator = self.shared.estimator.get();
+        let time = estimator.presentation_time(MonotonicTs::now());
+        self.shared.estimator.set(estimator);
+        time
+    }
+
+    /// Gets the time of the stream at the specified monotonic system time, or `None` if no timing
+    /// information has been received yet.
+    ///
+    /// This is interpolated, or extrapolated, from the latest timing information, taking the
+    /// estimated drift into account, and may go backwards between calls.
+    pub fn time_at(&self, ts: MonotonicTs) -> Option<MicroSeconds> {
+        self.shared.estimator.get().time_at(ts)
+    }
+
+    /// Gets the estimated drift of the sound card clock against the monotonic system clock, in
+    /// parts per million, positive meaning that the sound card clock runs faster.
+    ///
+    /// Returns `None` until timing information spanning enough continuous playback has been
+    /// received. The estimate is restarted whenever playback stops, or the time of the stream
+    /// jumps, such as due to a seek or flush, and is limited to ±1000 ppm.
+    #[inline]
+    pub fn drift_ppm(&self) -> Option<f64> {
+        self.shared.estimator.get().drift
+    }
+
+    /// Gets the total latency of the stream, as a signed number of microseconds.
+    ///
+    /// This is simply the result of [`Stream::get_latency()`], with `Ok(None)` returned if no
+    /// timing information has been received yet.
+    ///
+    /// [`Stream::get_latency()`]: crate::stream::Stream::get_latency
+    pub fn latency(&self) -> Result<Option<i64>, PAErr> {
+        let latency = Stream::from_raw_weak(self.shared.stream).get_latency()?;
+        Ok(latency.as_micros_signed())
+    }
+
+    /// Discards all timing information, such as after a flush or seek, such that the presentation
+    /// time may go backwards, and then samples the time of the stream afresh.
+    pub fn reset(&self) {
+        self.shared.estimator.set(Estimator::default());
+        self.shared.sample();
+    }
+}
+
+impl ClockShared {
+    /// Samples the time of the stream, updating the drift estimate.
+    fn sample(&self) {
+        let mut stream = Stream::from_raw_weak(self.stream);
+        let time = match stream.get_time() {
+            Ok(Some(time)) => time,
+            _ => return,
+        };
+        let playing = stream.get_timing_info().map_or(false, |i| i.playing != 0)
+            && stream.is_corked() == Ok(false);
+        let mut estimator = self.estimator.get();
+        estimator.sample(Anchor { time, at: MonotonicTs::now(), playing });
+        self.estimator.set(estimator);
+    }
+}
+
+impl Drop for ClockShared {
+    fn drop(&mut self) {
+        unsafe { capi::pa_stream_unref(self.stream) };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Makes a sample of the stream time (`time`) at a monotonic time (`at`), in milliseconds.
+    fn anchor(time: u64, at: u64, playing: bool) -> Anchor {
+        let ms = |v: u64| MicroSeconds(v * 1000);
+        Anchor { time: ms(time), at: MonotonicTs(ms(at)), playing }
+    }
+
+    /// Check the sign and magnitude of the drift estimate, and its use in interpolation
+    #[test]
+    fn drift() {
+        let mut estimator = Estimator::default();
+        assert_eq!(estimator.time_at(MonotonicTs(MicroSeconds(0))), None);
+
+        // Too short a span for an estimate
+        estimator.sample(anchor(0, 1000, true));
+        estimator.sample(anchor(500, 1500, true));
+        assert_eq!(estimator.drift, None);
+
+        // The sound card running 100 ppm fast
+        estimator.sample(anchor(10_001, 11_000, true));
+        assert!((estimator.drift.unwrap() - 100.0).abs() < 1e-6);
+        let time = estimator.time_at(MonotonicTs(MicroSeconds(12_000_000))).unwrap();
+        assert!(time.diff(MicroSeconds(11_001_100)) <= MicroSeconds(1));
+
+        // Seeking forwards restarts the estimate, rather than counting the jump as drift
+        estimator.sample(anchor(60_000, 12_000, true));
+        assert_eq!(estimator.drift, None);
+        estimator.sample(anchor(61_000, 13_000, true));
+        assert_eq!(estimator.drift, Some(0.0));
+
+        // As does flushing, jumping backwards
+        estimator.sample(anchor(5_000, 14_000, true));
+        assert_eq!(estimator.drift, None);
+        assert_eq!(estimator.time_at(MonotonicTs(MicroSeconds(14_500_000))),
+            Some(MicroSeconds(5_500_000)));
+        estimator.sample(anchor(6_000, 15_000, true));
+        assert_eq!(estimator.drift, Some(0.0));
+
+        // And slow
+        let mut estimator = Estimator::default();
+        estimator.sample(anchor(0, 0, true));
+        estimator.sample(anchor(9_999, 10_000, true));
+        assert!((estimator.drift.unwrap() + 100.0).abs() < 1e-6);
+
+        // An implausible estimate is clamped
+        let mut estimator = Estimator::default();
+        estimator.sample(anchor(0, 0, true));
+        estimator.sample(anchor(1_030, 1_000, true));
+        assert_eq!(estimator.drift, Some(MAX_DRIFT_PPM));
+    }
+
+    /// Check that the time holds while paused, and the estimate restarts upon resuming
+    #[test]
+    fn pause_resume() {
+        let mut estimator = Estimator::default();
+        estimator.sample(anchor(0, 0, true));
+        estimator.sample(anchor(2000, 2000, true));
+        assert_eq!(estimator.drift, Some(0.0));
+
+        estimator.sample(anchor(3000, 3000, false));
+        assert_eq!(estimator.time_at(MonotonicTs(MicroSeconds(9_000_000))),
+            Some(MicroSeconds(3_000_000)));
+
+        // The drift estimate is kept, but measured afresh from the resumption
+        estimator.sample(anchor(3000, 10_000, true));
+        assert_eq!(estimator.reference.map(|r| r.at), Some(MonotonicTs(MicroSeconds(10_000_000))));
+        assert_eq!(estimator.time_at(MonotonicTs(MicroSeconds(10_500_000))),
+            Some(MicroSeconds(3_500_000)));
+    }
+
+    /// Check that the presentation time never goes backwards, other than upon a reset
+    #[test]
+    fn monotonic() {
+        let mut estimator = Estimator::default();
+        estimator.sample(anchor(5000, 0, true));
+        let ts = MonotonicTs(MicroSeconds(1_000_000));
+        assert_eq!(estimator.presentation_time(ts), Some(MicroSeconds(6_000_000)));
+
+        // Flushed, jumping back
+        estimator.sample(anchor(1000, 1000, true));
+        assert_eq!(estimator.time_at(ts), Some(MicroSeconds(1_000_000)));
+        assert_eq!(estimator.presentation_time(ts), Some(MicroSeconds(6_000_000)));
+
+        estimator = Estimator::default();
+        estimator.sample(anchor(1000, 1000, true));
+        assert_eq!(estimator.presentation_time(ts), Some(MicroSeconds(1_000_000)));
+    }
+}
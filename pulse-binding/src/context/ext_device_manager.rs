@@ -22,7 +22,7 @@ use capi::pa_ext_device_manager_role_priority_info as RolePriorityInfoInternal;
 use super::{ContextInternal, Context};
 use crate::def;
 use crate::callbacks::{ListResult, box_closure_get_capi_ptr, callback_for_list_instance};
-use crate::operation::Operation;
+use crate::operation::{Operation, OperationStream};
 
 /// Role priority information.
 #[derive(Debug)]
@@ -183,6 +183,14 @@ impl DeviceManager {
         Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(ListResult<&Info>)>)
     }
 
+    /// Like [`read()`](Self::read), but yields owned entries through an [`OperationStream`].
+    pub fn read_stream(&mut self) -> OperationStream<Info<'static>> {
+        let context = self.context;
+        OperationStream::new(|sender| self.read(move |r| {
+            super::list_result_to_stream(context, &sender, r, Info::to_owned)
+        }))
+    }
+
     /// Sets the description for a device.
     ///
     /// The callback must accept a `bool`, which indicates success.
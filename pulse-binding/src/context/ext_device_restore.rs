@@ -22,7 +22,7 @@ use crate::{def, format};
 use crate::callbacks::{
     ListResult, box_closure_get_capi_ptr, callback_for_list_instance, MultiUseCallback
 };
-use crate::operation::Operation;
+use crate::operation::{Operation, OperationFuture, OperationStream};
 
 /// Stores information about one device in the device database that is maintained by
 /// module-device-manager.
@@ -157,6 +157,15 @@ impl DeviceRestore {
         Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(ListResult<&Info>)>)
     }
 
+    /// Like [`read_formats_all()`](Self::read_formats_all), but yields owned entries through an
+    /// [`OperationStream`].
+    pub fn read_formats_all_stream(&mut self) -> OperationStream<Info> {
+        let context = self.context;
+        OperationStream::new(|sender| self.read_formats_all(move |r| {
+            super::list_result_to_stream(context, &sender, r, Info::to_owned)
+        }))
+    }
+
     /// Reads an entry from the device database.
     ///
     /// Panics if the underlying C function returns a null pointer.
@@ -170,6 +179,20 @@ impl DeviceRestore {
         Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(ListResult<&Info>)>)
     }
 
+    /// Like [`read_formats()`](Self::read_formats), but resolves to the owned entry through an
+    /// [`OperationFuture`].
+    pub fn read_formats_future(&mut self, type_: def::Device, index: u32)
+        -> OperationFuture<Info>
+    {
+        let context = self.context;
+        OperationFuture::new(|completer| {
+            let mut item = None;
+            self.read_formats(type_, index, move |r| {
+                super::list_result_to_future(context, &completer, &mut item, r, Info::to_owned)
+            })
+        })
+    }
+
     /// Reads an entry from the device database.
     ///
     /// The callback must accept a `bool`, which indicates success.
@@ -22,7 +22,7 @@ use capi::pa_ext_stream_restore_info as InfoInternal;
 use super::{ContextInternal, Context};
 use crate::{channelmap, proplist};
 use crate::callbacks::{ListResult, box_closure_get_capi_ptr, callback_for_list_instance};
-use crate::{operation::Operation, volume::ChannelVolumes};
+use crate::{operation::{Operation, OperationStream}, volume::ChannelVolumes};
 
 /// Stores information about one entry in the stream database that is maintained by
 /// module-stream-restore.
@@ -135,6 +135,14 @@ impl StreamRestore {
         Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(ListResult<&Info>)>)
     }
 
+    /// Like [`read()`](Self::read), but yields owned entries through an [`OperationStream`].
+    pub fn read_stream(&mut self) -> OperationStream<Info<'static>> {
+        let context = self.context;
+        OperationStream::new(|sender| self.read(move |r| {
+            super::list_result_to_stream(context, &sender, r, Info::to_owned)
+        }))
+    }
+
     /// Stores entries in the stream database.
     ///
     /// The callback must accept a `bool`, which indicates success.
@@ -40,6 +40,12 @@
 //! Note that even if a single object is requested, and not the entire list, the terminating call
 //! will still be made.
 //!
+//! Alternatively, each such query has an asynchronous counterpart, delivering owned copies of the
+//! information structures. Those fetching lists (suffixed `_stream`) give an [`OperationStream`],
+//! from which the items can be taken one at a time, or collected into a `Vec` with
+//! [`OperationStream::collect_vec()`]. Those fetching a single object (suffixed `_future`) give an
+//! [`OperationFuture`], resolving to the object, or to an error if no such object exists.
+//!
 //! Data members in the information structures are only valid during the duration of the callback.
 //! If they are required after the callback is finished, a deep copy of the information structure
 //! must be performed.
@@ -209,7 +215,8 @@ use crate::callbacks::{
     get_su_callback
 };
 use crate::volume::{ChannelVolumes, Volume};
-use crate::{operation::Operation, proplist::Proplist};
+use crate::operation::{Operation, OperationFuture, OperationStream};
+use crate::proplist::Proplist;
 #[cfg(any(doc, feature = "pa_v14"))]
 use crate::def::DevicePortType;
 
@@ -259,6 +266,7 @@ impl Drop for Introspector {
 /// Please note that this structure can be extended as part of evolutionary API updates at any time
 /// in any new release.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SinkPortInfo<'a> {
     /// Name of this port.
     pub name: Option<Cow<'a, str>>,
@@ -267,6 +275,7 @@ pub struct SinkPortInfo<'a> {
     /// The higher this value is, the more useful this port is as a default.
     pub priority: u32,
     /// A flag indicating availability status of this port.
+    #[cfg_attr(feature = "serde", serde(with = "def::PortAvailableDef"))]
     pub available: def::PortAvailable,
     /// An indentifier for the group of ports that share their availability status with each other.
     ///
@@ -295,6 +304,7 @@ pub struct SinkPortInfo<'a> {
     /// Port device type.
     #[cfg(any(doc, feature = "pa_v14"))]
     #[cfg_attr(docsrs, doc(cfg(feature = "pa_v14")))]
+    #[cfg_attr(feature = "serde", serde(with = "def::DevicePortTypeDef"))]
     pub r#type: DevicePortType,
 }
 
@@ -342,6 +352,7 @@ impl SinkPortInfo<'_> {
 /// Please note that this structure can be extended as part of evolutionary API updates at any time
 /// in any new release.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SinkInfo<'a> {
     /// Name of the sink.
     pub name: Option<Cow<'a, str>>,
@@ -498,6 +509,21 @@ impl Introspector {
         Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(ListResult<&SinkInfo>)>)
     }
 
+    /// Gets information about a sink by its name, as a future resolving to an owned item.
+    ///
+    /// This is an alternative to [`get_sink_info_by_name()`](Self::get_sink_info_by_name).
+    ///
+    /// Panics on error, i.e. invalid arguments or state.
+    pub fn get_sink_info_by_name_future(&self, name: &str) -> OperationFuture<SinkInfo<'static>> {
+        let context = self.context;
+        OperationFuture::new(|completer| {
+            let mut item = None;
+            self.get_sink_info_by_name(name, move |r| {
+                super::list_result_to_future(context, &completer, &mut item, r, SinkInfo::to_owned)
+            })
+        })
+    }
+
     /// Gets information about a sink by its index.
     ///
     /// Panics on error, i.e. invalid arguments or state.
@@ -511,6 +537,21 @@ impl Introspector {
         Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(ListResult<&SinkInfo>)>)
     }
 
+    /// Gets information about a sink by its index, as a future resolving to an owned item.
+    ///
+    /// This is an alternative to [`get_sink_info_by_index()`](Self::get_sink_info_by_index).
+    ///
+    /// Panics on error, i.e. invalid arguments or state.
+    pub fn get_sink_info_by_index_future(&self, index: u32) -> OperationFuture<SinkInfo<'static>> {
+        let context = self.context;
+        OperationFuture::new(|completer| {
+            let mut item = None;
+            self.get_sink_info_by_index(index, move |r| {
+                super::list_result_to_future(context, &completer, &mut item, r, SinkInfo::to_owned)
+            })
+        })
+    }
+
     /// Gets the complete sink list.
     ///
     /// Panics on error, i.e. invalid arguments or state.
@@ -523,6 +564,18 @@ impl Introspector {
         Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(ListResult<&SinkInfo>)>)
     }
 
+    /// Gets the complete sink list, as a stream of owned items.
+    ///
+    /// This is an alternative to [`get_sink_info_list()`](Self::get_sink_info_list).
+    ///
+    /// Panics on error, i.e. invalid arguments or state.
+    pub fn get_sink_info_list_stream(&self) -> OperationStream<SinkInfo<'static>> {
+        let context = self.context;
+        OperationStream::new(|sender| self.get_sink_info_list(move |r| {
+            super::list_result_to_stream(context, &sender, r, SinkInfo::to_owned)
+        }))
+    }
+
     /// Sets the volume of a sink device specified by its index.
     ///
     /// Panics on error, i.e. invalid arguments or state.
@@ -686,6 +739,7 @@ fn get_sink_info_list_cb_proxy(_: *mut ContextInternal, i: *const SinkInfoIntern
 /// Please note that this structure can be extended as part of evolutionary API updates at any time
 /// in any new release.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SourcePortInfo<'a> {
     /// Name of this port.
     pub name: Option<Cow<'a, str>>,
@@ -694,6 +748,7 @@ pub struct SourcePortInfo<'a> {
     /// The higher this value is, the more useful this port is as a default.
     pub priority: u32,
     /// A flag indicating availability status of this port.
+    #[cfg_attr(feature = "serde", serde(with = "def::PortAvailableDef"))]
     pub available: def::PortAvailable,
     /// An indentifier for the group of ports that share their availability status with each other.
     ///
@@ -722,6 +777,7 @@ pub struct SourcePortInfo<'a> {
     /// Port device type.
     #[cfg(any(doc, feature = "pa_v14"))]
     #[cfg_attr(docsrs, doc(cfg(feature = "pa_v14")))]
+    #[cfg_attr(feature = "serde", serde(with = "def::DevicePortTypeDef"))]
     pub r#type: DevicePortType,
 }
 
@@ -769,6 +825,7 @@ impl SourcePortInfo<'_> {
 /// Please note that this structure can be extended as part of evolutionary API updates at any time
 /// in any new release.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SourceInfo<'a> {
     /// Name of the source.
     pub name: Option<Cow<'a, str>>,
@@ -928,6 +985,24 @@ impl Introspector {
         Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(ListResult<&SourceInfo>)>)
     }
 
+    /// Gets information about a source by its name, as a future resolving to an owned item.
+    ///
+    /// This is an alternative to [`get_source_info_by_name()`](Self::get_source_info_by_name).
+    ///
+    /// Panics on error, i.e. invalid arguments or state.
+    pub fn get_source_info_by_name_future(&self, name: &str)
+        -> OperationFuture<SourceInfo<'static>>
+    {
+        let context = self.context;
+        OperationFuture::new(|completer| {
+            let mut item = None;
+            self.get_source_info_by_name(name, move |r| {
+                super::list_result_to_future(context, &completer, &mut item, r,
+                    SourceInfo::to_owned)
+            })
+        })
+    }
+
     /// Gets information about a source by its index.
     ///
     /// Panics on error, i.e. invalid arguments or state.
@@ -941,6 +1016,24 @@ impl Introspector {
         Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(ListResult<&SourceInfo>)>)
     }
 
+    /// Gets information about a source by its index, as a future resolving to an owned item.
+    ///
+    /// This is an alternative to [`get_source_info_by_index()`](Self::get_source_info_by_index).
+    ///
+    /// Panics on error, i.e. invalid arguments or state.
+    pub fn get_source_info_by_index_future(&self, index: u32)
+        -> OperationFuture<SourceInfo<'static>>
+    {
+        let context = self.context;
+        OperationFuture::new(|completer| {
+            let mut item = None;
+            self.get_source_info_by_index(index, move |r| {
+                super::list_result_to_future(context, &completer, &mut item, r,
+                    SourceInfo::to_owned)
+            })
+        })
+    }
+
     /// Gets the complete source list.
     ///
     /// Panics on error, i.e. invalid arguments or state.
@@ -954,6 +1047,18 @@ impl Introspector {
         Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(ListResult<&SourceInfo>)>)
     }
 
+    /// Gets the complete source list, as a stream of owned items.
+    ///
+    /// This is an alternative to [`get_source_info_list()`](Self::get_source_info_list).
+    ///
+    /// Panics on error, i.e. invalid arguments or state.
+    pub fn get_source_info_list_stream(&self) -> OperationStream<SourceInfo<'static>> {
+        let context = self.context;
+        OperationStream::new(|sender| self.get_source_info_list(move |r| {
+            super::list_result_to_stream(context, &sender, r, SourceInfo::to_owned)
+        }))
+    }
+
     /// Sets the volume of a source device specified by its index.
     ///
     /// Panics on error, i.e. invalid arguments or state.
@@ -1118,6 +1223,7 @@ fn get_source_info_list_cb_proxy(_: *mut ContextInternal, i: *const SourceInfoIn
 /// Please note that this structure can be extended as part of evolutionary API updates at any time
 /// in any new release.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ServerInfo<'a> {
     /// User name of the daemon process.
     pub user_name: Option<Cow<'a, str>>,
@@ -1229,6 +1335,7 @@ fn get_server_info_cb_proxy(_: *mut ContextInternal, i: *const ServerInfoInterna
 /// Please note that this structure can be extended as part of evolutionary API updates at any time
 /// in any new release.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ModuleInfo<'a> {
     /// Index of the module.
     pub index: u32,
@@ -1291,6 +1398,22 @@ impl Introspector {
         Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(ListResult<&ModuleInfo>)>)
     }
 
+    /// Gets some information about a module by its index, as a future resolving to an owned item.
+    ///
+    /// This is an alternative to [`get_module_info()`](Self::get_module_info).
+    ///
+    /// Panics on error, i.e. invalid arguments or state.
+    pub fn get_module_info_future(&self, index: u32) -> OperationFuture<ModuleInfo<'static>> {
+        let context = self.context;
+        OperationFuture::new(|completer| {
+            let mut item = None;
+            self.get_module_info(index, move |r| {
+                super::list_result_to_future(context, &completer, &mut item, r,
+                    ModuleInfo::to_owned)
+            })
+        })
+    }
+
     /// Gets the complete list of currently loaded modules.
     ///
     /// Panics on error, i.e. invalid arguments or state.
@@ -1304,6 +1427,18 @@ impl Introspector {
         Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(ListResult<&ModuleInfo>)>)
     }
 
+    /// Gets the complete list of currently loaded modules, as a stream of owned items.
+    ///
+    /// This is an alternative to [`get_module_info_list()`](Self::get_module_info_list).
+    ///
+    /// Panics on error, i.e. invalid arguments or state.
+    pub fn get_module_info_list_stream(&self) -> OperationStream<ModuleInfo<'static>> {
+        let context = self.context;
+        OperationStream::new(|sender| self.get_module_info_list(move |r| {
+            super::list_result_to_stream(context, &sender, r, ModuleInfo::to_owned)
+        }))
+    }
+
     /// Loads a module.
     ///
     /// Panics on error, i.e. invalid arguments or state. The callback is provided with the
@@ -1427,6 +1562,7 @@ fn send_message_to_object_cb_proxy(_: *mut ContextInternal, success: i32, respon
 /// Please note that this structure can be extended as part of evolutionary API updates at any time
 /// in any new release.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ClientInfo<'a> {
     /// Index of this client.
     pub index: u32,
@@ -1489,6 +1625,22 @@ impl Introspector {
         Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(ListResult<&ClientInfo>)>)
     }
 
+    /// Gets information about a client by its index, as a future resolving to an owned item.
+    ///
+    /// This is an alternative to [`get_client_info()`](Self::get_client_info).
+    ///
+    /// Panics on error, i.e. invalid arguments or state.
+    pub fn get_client_info_future(&self, index: u32) -> OperationFuture<ClientInfo<'static>> {
+        let context = self.context;
+        OperationFuture::new(|completer| {
+            let mut item = None;
+            self.get_client_info(index, move |r| {
+                super::list_result_to_future(context, &completer, &mut item, r,
+                    ClientInfo::to_owned)
+            })
+        })
+    }
+
     /// Gets the complete client list.
     ///
     /// Panics on error, i.e. invalid arguments or state.
@@ -1502,6 +1654,18 @@ impl Introspector {
         Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(ListResult<&ClientInfo>)>)
     }
 
+    /// Gets the complete client list, as a stream of owned items.
+    ///
+    /// This is an alternative to [`get_client_info_list()`](Self::get_client_info_list).
+    ///
+    /// Panics on error, i.e. invalid arguments or state.
+    pub fn get_client_info_list_stream(&self) -> OperationStream<ClientInfo<'static>> {
+        let context = self.context;
+        OperationStream::new(|sender| self.get_client_info_list(move |r| {
+            super::list_result_to_stream(context, &sender, r, ClientInfo::to_owned)
+        }))
+    }
+
     /// Kills a client.
     ///
     /// Panics on error, i.e. invalid arguments or state.
@@ -1538,6 +1702,7 @@ fn get_client_info_list_cb_proxy(_: *mut ContextInternal, i: *const ClientInfoIn
 /// Please note that this structure can be extended as part of evolutionary API updates at any time
 /// in any new release.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CardProfileInfo<'a> {
     /// Name of this profile.
     pub name: Option<Cow<'a, str>>,
@@ -1596,6 +1761,7 @@ impl CardProfileInfo<'_> {
 /// Please note that this structure can be extended as part of evolutionary API updates at any time
 /// in any new release.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CardPortInfo<'a> {
     /// Name of this port.
     pub name: Option<Cow<'a, str>>,
@@ -1604,6 +1770,7 @@ pub struct CardPortInfo<'a> {
     /// The higher this value is, the more useful this port is as a default.
     pub priority: u32,
     /// Availability status of this port.
+    #[cfg_attr(feature = "serde", serde(with = "def::PortAvailableDef"))]
     pub available: def::PortAvailable,
     /// The direction of this port.
     pub direction: direction::FlagSet,
@@ -1637,6 +1804,7 @@ pub struct CardPortInfo<'a> {
     /// Port device type.
     #[cfg(any(doc, feature = "pa_v14"))]
     #[cfg_attr(docsrs, doc(cfg(feature = "pa_v14")))]
+    #[cfg_attr(feature = "serde", serde(with = "def::DevicePortTypeDef"))]
     pub r#type: DevicePortType,
 }
 
@@ -1702,6 +1870,7 @@ impl CardPortInfo<'_> {
 /// Please note that this structure can be extended as part of evolutionary API updates at any time
 /// in any new release.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CardInfo<'a> {
     /// Index of this card.
     pub index: u32,
@@ -1799,6 +1968,21 @@ impl Introspector {
         Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(ListResult<&CardInfo>)>)
     }
 
+    /// Gets information about a card by its index, as a future resolving to an owned item.
+    ///
+    /// This is an alternative to [`get_card_info_by_index()`](Self::get_card_info_by_index).
+    ///
+    /// Panics on error, i.e. invalid arguments or state.
+    pub fn get_card_info_by_index_future(&self, index: u32) -> OperationFuture<CardInfo<'static>> {
+        let context = self.context;
+        OperationFuture::new(|completer| {
+            let mut item = None;
+            self.get_card_info_by_index(index, move |r| {
+                super::list_result_to_future(context, &completer, &mut item, r, CardInfo::to_owned)
+            })
+        })
+    }
+
     /// Gets information about a card by its name.
     ///
     /// Panics on error, i.e. invalid arguments or state.
@@ -1816,6 +2000,21 @@ impl Introspector {
         Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(ListResult<&CardInfo>)>)
     }
 
+    /// Gets information about a card by its name, as a future resolving to an owned item.
+    ///
+    /// This is an alternative to [`get_card_info_by_name()`](Self::get_card_info_by_name).
+    ///
+    /// Panics on error, i.e. invalid arguments or state.
+    pub fn get_card_info_by_name_future(&self, name: &str) -> OperationFuture<CardInfo<'static>> {
+        let context = self.context;
+        OperationFuture::new(|completer| {
+            let mut item = None;
+            self.get_card_info_by_name(name, move |r| {
+                super::list_result_to_future(context, &completer, &mut item, r, CardInfo::to_owned)
+            })
+        })
+    }
+
     /// Gets the complete card list.
     ///
     /// Panics on error, i.e. invalid arguments or state.
@@ -1828,6 +2027,18 @@ impl Introspector {
         Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(ListResult<&CardInfo>)>)
     }
 
+    /// Gets the complete card list, as a stream of owned items.
+    ///
+    /// This is an alternative to [`get_card_info_list()`](Self::get_card_info_list).
+    ///
+    /// Panics on error, i.e. invalid arguments or state.
+    pub fn get_card_info_list_stream(&self) -> OperationStream<CardInfo<'static>> {
+        let context = self.context;
+        OperationStream::new(|sender| self.get_card_info_list(move |r| {
+            super::list_result_to_stream(context, &sender, r, CardInfo::to_owned)
+        }))
+    }
+
     /// Changes the profile of a card.
     ///
     /// Panics on error, i.e. invalid arguments or state.
@@ -1909,6 +2120,7 @@ fn get_card_info_list_cb_proxy(_: *mut ContextInternal, i: *const CardInfoIntern
 /// Please note that this structure can be extended as part of evolutionary API updates at any time
 /// in any new release.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SinkInputInfo<'a> {
     /// Index of the sink input.
     pub index: u32,
@@ -2036,6 +2248,25 @@ impl Introspector {
         Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(ListResult<&SinkInputInfo>)>)
     }
 
+    /// Gets some information about a sink input by its index, as a future resolving to an owned
+    /// item.
+    ///
+    /// This is an alternative to [`get_sink_input_info()`](Self::get_sink_input_info).
+    ///
+    /// Panics on error, i.e. invalid arguments or state.
+    pub fn get_sink_input_info_future(&self, index: u32)
+        -> OperationFuture<SinkInputInfo<'static>>
+    {
+        let context = self.context;
+        OperationFuture::new(|completer| {
+            let mut item = None;
+            self.get_sink_input_info(index, move |r| {
+                super::list_result_to_future(context, &completer, &mut item, r,
+                    SinkInputInfo::to_owned)
+            })
+        })
+    }
+
     /// Gets the complete sink input list.
     ///
     /// Panics on error, i.e. invalid arguments or state.
@@ -2050,6 +2281,18 @@ impl Introspector {
         Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(ListResult<&SinkInputInfo>)>)
     }
 
+    /// Gets the complete sink input list, as a stream of owned items.
+    ///
+    /// This is an alternative to [`get_sink_input_info_list()`](Self::get_sink_input_info_list).
+    ///
+    /// Panics on error, i.e. invalid arguments or state.
+    pub fn get_sink_input_info_list_stream(&self) -> OperationStream<SinkInputInfo<'static>> {
+        let context = self.context;
+        OperationStream::new(|sender| self.get_sink_input_info_list(move |r| {
+            super::list_result_to_stream(context, &sender, r, SinkInputInfo::to_owned)
+        }))
+    }
+
     /// Moves the specified sink input to a different sink.
     ///
     /// Panics on error, i.e. invalid arguments or state.
@@ -2150,6 +2393,7 @@ fn get_sink_input_info_list_cb_proxy(_: *mut ContextInternal, i: *const SinkInpu
 /// Please note that this structure can be extended as part of evolutionary API updates at any time
 /// in any new release.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SourceOutputInfo<'a> {
     /// Index of the source output.
     pub index: u32,
@@ -2277,6 +2521,24 @@ impl Introspector {
         Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(ListResult<&SourceOutputInfo>)>)
     }
 
+    /// Gets information about a source output by its index, as a future resolving to an owned item.
+    ///
+    /// This is an alternative to [`get_source_output_info()`](Self::get_source_output_info).
+    ///
+    /// Panics on error, i.e. invalid arguments or state.
+    pub fn get_source_output_info_future(&self, index: u32)
+        -> OperationFuture<SourceOutputInfo<'static>>
+    {
+        let context = self.context;
+        OperationFuture::new(|completer| {
+            let mut item = None;
+            self.get_source_output_info(index, move |r| {
+                super::list_result_to_future(context, &completer, &mut item, r,
+                    SourceOutputInfo::to_owned)
+            })
+        })
+    }
+
     /// Gets the complete list of source outputs.
     ///
     /// Panics on error, i.e. invalid arguments or state.
@@ -2291,6 +2553,19 @@ impl Introspector {
         Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(ListResult<&SourceOutputInfo>)>)
     }
 
+    /// Gets the complete list of source outputs, as a stream of owned items.
+    ///
+    /// This is an alternative to
+    /// [`get_source_output_info_list()`](Self::get_source_output_info_list).
+    ///
+    /// Panics on error, i.e. invalid arguments or state.
+    pub fn get_source_output_info_list_stream(&self) -> OperationStream<SourceOutputInfo<'static>> {
+        let context = self.context;
+        OperationStream::new(|sender| self.get_source_output_info_list(move |r| {
+            super::list_result_to_stream(context, &sender, r, SourceOutputInfo::to_owned)
+        }))
+    }
+
     /// Moves the specified source output to a different source.
     ///
     /// Panics on error, i.e. invalid arguments or state.
@@ -2422,6 +2697,7 @@ fn get_stat_info_cb_proxy(_: *mut ContextInternal, i: *const StatInfo, userdata:
 /// Please note that this structure can be extended as part of evolutionary API updates at any time
 /// in any new release.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SampleInfo<'a> {
     /// Index of this entry.
     pub index: u32,
@@ -2503,6 +2779,24 @@ impl Introspector {
         Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(ListResult<&SampleInfo>)>)
     }
 
+    /// Gets information about a sample by its name, as a future resolving to an owned item.
+    ///
+    /// This is an alternative to [`get_sample_info_by_name()`](Self::get_sample_info_by_name).
+    ///
+    /// Panics on error, i.e. invalid arguments or state.
+    pub fn get_sample_info_by_name_future(&self, name: &str)
+        -> OperationFuture<SampleInfo<'static>>
+    {
+        let context = self.context;
+        OperationFuture::new(|completer| {
+            let mut item = None;
+            self.get_sample_info_by_name(name, move |r| {
+                super::list_result_to_future(context, &completer, &mut item, r,
+                    SampleInfo::to_owned)
+            })
+        })
+    }
+
     /// Gets information about a sample by its index.
     ///
     /// Panics on error, i.e. invalid arguments or state.
@@ -2516,6 +2810,24 @@ impl Introspector {
         Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(ListResult<&SampleInfo>)>)
     }
 
+    /// Gets information about a sample by its index, as a future resolving to an owned item.
+    ///
+    /// This is an alternative to [`get_sample_info_by_index()`](Self::get_sample_info_by_index).
+    ///
+    /// Panics on error, i.e. invalid arguments or state.
+    pub fn get_sample_info_by_index_future(&self, index: u32)
+        -> OperationFuture<SampleInfo<'static>>
+    {
+        let context = self.context;
+        OperationFuture::new(|completer| {
+            let mut item = None;
+            self.get_sample_info_by_index(index, move |r| {
+                super::list_result_to_future(context, &completer, &mut item, r,
+                    SampleInfo::to_owned)
+            })
+        })
+    }
+
     /// Gets the complete list of samples stored in the daemon.
     ///
     /// Panics on error, i.e. invalid arguments or state.
@@ -2528,6 +2840,18 @@ impl Introspector {
             Some(get_sample_info_list_cb_proxy), cb_data) };
         Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(ListResult<&SampleInfo>)>)
     }
+
+    /// Gets the complete list of samples stored in the daemon, as a stream of owned items.
+    ///
+    /// This is an alternative to [`get_sample_info_list()`](Self::get_sample_info_list).
+    ///
+    /// Panics on error, i.e. invalid arguments or state.
+    pub fn get_sample_info_list_stream(&self) -> OperationStream<SampleInfo<'static>> {
+        let context = self.context;
+        OperationStream::new(|sender| self.get_sample_info_list(move |r| {
+            super::list_result_to_stream(context, &sender, r, SampleInfo::to_owned)
+        }))
+    }
 }
 
 /// Proxy for get sample info list callbacks.
@@ -61,6 +61,7 @@
 //! * [`context::scache`](mod@crate::context::scache)
 //! * [`context::introspect`](mod@crate::context::introspect)
 //! * [`context::subscribe`](mod@crate::context::subscribe)
+//! * [`context::topology`](mod@crate::context::topology)
 
 pub mod ext_device_manager;
 pub mod ext_device_restore;
@@ -68,6 +69,7 @@ pub mod ext_stream_restore;
 pub mod introspect;
 pub mod scache;
 pub mod subscribe;
+pub mod topology;
 
 use std::os::raw::{c_char, c_void};
 use std::ffi::{CStr, CString};
@@ -79,11 +81,11 @@ use crate::{def, sample};
 use crate::mainloop::api::{Mainloop, MainloopInnerType};
 use crate::mainloop::events;
 use crate::mainloop::events::timer::{TimeEvent, TimeEventRef};
-use crate::operation::Operation;
-use crate::error::PAErr;
+use crate::operation::{Operation, FutureCompleter, StreamSender};
+use crate::error::{self, PAErr};
 use crate::time::MonotonicTs;
 use crate::proplist::{self, Proplist, ProplistInternal};
-use crate::callbacks::{box_closure_get_capi_ptr, get_su_callback, MultiUseCallback};
+use crate::callbacks::{box_closure_get_capi_ptr, get_su_callback, ListResult, MultiUseCallback};
 use crate::capi::pa_context as ContextInternal;
 
 /// An opaque connection context to a daemon.
@@ -255,6 +257,15 @@ impl Context {
         Self { ptr: ptr, weak: false, cb_ptrs: Default::default() }
     }
 
+    /// Creates a new `Context` from an existing [`ContextInternal`] pointer.
+    ///
+    /// This is the ‘weak’ version, which avoids destroying the internal object when dropped.
+    #[inline]
+    pub(crate) fn from_raw_weak(ptr: *mut ContextInternal) -> Self {
+        assert!(!ptr.is_null());
+        Self { ptr, weak: true, cb_ptrs: Default::default() }
+    }
+
     /// Sets a callback function that is called whenever the context status changes.
     pub fn set_state_callback(&mut self, callback: Option<Box<dyn FnMut() + 'static>>) {
         let saved = &mut self.cb_ptrs.set_state;
@@ -618,6 +629,43 @@ impl Drop for Context {
     }
 }
 
+/// Feeds a list callback result into an operation stream, converting items with `conv`.
+///
+/// On failure, the error number of the context is delivered as a final error.
+pub(crate) fn list_result_to_stream<I, T, C>(context: *mut ContextInternal,
+    sender: &StreamSender<T>, result: ListResult<I>, conv: C)
+    where C: FnOnce(I) -> T
+{
+    match result {
+        ListResult::Item(i) => sender.send(Ok(conv(i))),
+        ListResult::End => sender.finish(None),
+        ListResult::Error => {
+            sender.finish(Some(PAErr(unsafe { capi::pa_context_errno(context) })));
+        },
+    }
+}
+
+/// Feeds a list callback result, for a request for a single object, into an operation future,
+/// converting the item with `conv`.
+///
+/// The item is held in `item` until the end of the list is signalled. If no item was given, the
+/// future resolves to a [`Code::NoEntity`](crate::error::Code::NoEntity) error. On failure, the
+/// error number of the context is given.
+pub(crate) fn list_result_to_future<I, T, C>(context: *mut ContextInternal,
+    completer: &FutureCompleter<T>, item: &mut Option<T>, result: ListResult<I>, conv: C)
+    where C: FnOnce(I) -> T
+{
+    match result {
+        ListResult::Item(i) => *item = Some(conv(i)),
+        ListResult::End => {
+            completer.complete(item.take().ok_or_else(|| PAErr::from(error::Code::NoEntity)));
+        },
+        ListResult::Error => {
+            completer.complete(Err(PAErr(unsafe { capi::pa_context_errno(context) })));
+        },
+    }
+}
+
 /// Proxy for completion success callbacks.
 ///
 /// Warning: This is for single-use cases only! It destroys the actual closure callback.
@@ -27,6 +27,12 @@
 //! To complete the upload, [`Stream::finish_upload()`] is called and the sample will receive the
 //! same name as the stream. If the upload should be aborted, simply call [`Stream::disconnect()`].
 //!
+//! Alternatively, [`Context::upload_sample()`] takes care of the whole upload, from data held in
+//! memory, reporting completion through a callback. A [`SampleUpload`] allows further options to
+//! be given, such as a property list, or data read from a file, and with
+//! [`Context::sync_samples()`], a set of samples can be uploaded, skipping any already held in the
+//! cache with the same sample spec and length.
+//!
 //! # Playing samples
 //!
 //! To play back a sample, simply call [`Context::play_sample()`]:
@@ -56,12 +62,18 @@
 //! [`Stream::finish_upload()`]: crate::stream::Stream::finish_upload
 //! [`Stream::disconnect()`]: crate::stream::Stream::disconnect
 
+use std::cell::{Cell, RefCell};
+use std::io::{Cursor, Read};
 use std::os::raw::{c_char, c_void};
 use std::ffi::CString;
 use std::ptr::null;
+use std::rc::Rc;
 use super::{ContextInternal, Context};
-use crate::def;
-use crate::callbacks::{box_closure_get_capi_ptr, get_su_capi_params, get_su_callback};
+use super::introspect::SampleInfo;
+use crate::{channelmap, def, sample};
+use crate::callbacks::{box_closure_get_capi_ptr, get_su_capi_params, get_su_callback, ListResult};
+use crate::error::{Code, PAErr};
+use crate::stream::{SeekMode, State, Stream};
 use crate::{operation::Operation, volume::Volume, proplist::Proplist};
 
 impl Context {
@@ -164,6 +176,482 @@ impl Context {
         };
         Operation::from_raw(ptr, cb_data as *mut Box<dyn FnMut(Result<u32, ()>)>)
     }
+
+    /// Uploads a sample to the sample cache, from data held in memory.
+    ///
+    /// This creates an upload stream, writes the data to it as the server requests it, and then
+    /// finishes the upload. See [`SampleUpload`] for further options.
+    ///
+    /// # Params
+    ///
+    /// * `name`: Name to give the sample, replacing any existing sample of the same name.
+    /// * `spec`: Sample spec of the data.
+    /// * `map`: Channel map of the data, or `None` for the default.
+    /// * `data`: The data, a whole number of frames.
+    /// * `proplist`: Optional property list for the sample, such as for event sound metadata.
+    /// * `callback`: Optional completion callback. It must accept a `bool`, which indicates
+    ///   success.
+    ///
+    /// The upload is cancelled if the returned object is dropped before completion. Returns the
+    /// [`PAErr`] form of the [`Code::Invalid`] error if the data is empty or not a whole number of
+    /// frames, or the spec or map is invalid.
+    pub fn upload_sample(&mut self, name: &str, spec: &sample::Spec,
+        map: Option<&channelmap::Map>, data: Vec<u8>, proplist: Option<&Proplist>,
+        callback: Option<Box<dyn FnMut(bool) + 'static>>) -> Result<Upload, PAErr>
+    {
+        let mut upload = SampleUpload::new(name, spec, data);
+        if let Some(map) = map {
+            upload = upload.channel_map(map);
+        }
+        if let Some(proplist) = proplist {
+            upload = upload.proplist(proplist.clone());
+        }
+        upload.start(self, callback)
+    }
+
+    /// Uploads those of a set of samples not already held in the sample cache.
+    ///
+    /// The list of cached samples is obtained with the `get_sample_info_list()` method of the
+    /// [`Introspector`], with any sample already cached under the same name, with the same sample
+    /// spec and length, and channel map if one was given, being skipped. All others are uploaded,
+    /// simultaneously. Should obtaining the list fail, all are uploaded.
+    ///
+    /// The callback, if given, is called once all uploads have completed, with a report of the
+    /// outcome. The uploads are cancelled if the returned object is dropped before then.
+    ///
+    /// [`Introspector`]: super::introspect::Introspector
+    pub fn sync_samples(&mut self, uploads: Vec<SampleUpload>,
+        callback: Option<Box<dyn FnMut(SyncReport) + 'static>>) -> SampleSync
+    {
+        let shared = Rc::new(SyncShared {
+            context: unsafe { capi::pa_context_ref(self.ptr) },
+            pending: RefCell::new(uploads),
+            active: RefCell::new(Vec::new()),
+            progress: RefCell::new(SyncProgress::default()),
+            callback: RefCell::new(callback),
+        });
+
+        let weak = Rc::downgrade(&shared);
+        let mut cached = Vec::new();
+        self.introspect().get_sample_info_list(move |result| {
+            match result {
+                ListResult::Item(info) => cached.push(info.to_owned()),
+                ListResult::End | ListResult::Error => {
+                    if let Some(shared) = weak.upgrade() {
+                        SyncShared::start(&shared, &cached);
+                    }
+                },
+            }
+        });
+
+        SampleSync { shared }
+    }
+}
+
+/// An upload completion callback.
+type CompletionCb = Box<dyn FnMut(bool) + 'static>;
+
+/// A sync completion callback.
+type SyncCb = Box<dyn FnMut(SyncReport) + 'static>;
+
+/// A sample to be uploaded to the sample cache.
+///
+/// See the [module level documentation](mod@self) for details.
+pub struct SampleUpload {
+    /// Name to give the sample.
+    name: String,
+    /// Sample spec of the data.
+    spec: sample::Spec,
+    /// Channel map of the data, if not the default.
+    map: Option<channelmap::Map>,
+    /// Property list for the sample.
+    proplist: Option<Proplist>,
+    /// Source of the data.
+    reader: Box<dyn Read>,
+    /// Length of the data, in bytes.
+    length: usize,
+}
+
+impl SampleUpload {
+    /// Creates a sample to be uploaded, from data held in memory, a whole number of frames.
+    pub fn new(name: &str, spec: &sample::Spec, data: Vec<u8>) -> Self {
+        let length = data.len();
+        Self::from_reader(name, spec, Cursor::new(data), length)
+    }
+
+    /// Creates a sample to be uploaded, with the data read from the specified reader, such as a
+    /// file of raw samples, as the server requests it.
+    ///
+    /// The length is that of the data to be read, in bytes, which must be a whole number of frames.
+    /// Should reading fail, or end early, the upload fails.
+    pub fn from_reader<R>(name: &str, spec: &sample::Spec, reader: R, length: usize) -> Self
+        where R: Read + 'static
+    {
+        Self {
+            name: name.to_owned(),
+            spec: *spec,
+            map: None,
+            proplist: None,
+            reader: Box::new(reader),
+            length,
+        }
+    }
+
+    /// Sets the channel map of the data, rather than using the default.
+    pub fn channel_map(mut self, map: &channelmap::Map) -> Self {
+        self.map = Some(*map);
+        self
+    }
+
+    /// Sets the property list of the sample, such as for event sound metadata.
+    pub fn proplist(mut self, proplist: Proplist) -> Self {
+        self.proplist = Some(proplist);
+        self
+    }
+
+    /// Gets the name to be given to the sample.
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Starts the upload.
+    ///
+    /// The callback, if given, is called upon completion. It must accept a `bool`, which indicates
+    /// success. The upload is cancelled if the returned object is dropped before completion.
+    ///
+    /// Returns the [`PAErr`] form of the [`Code::Invalid`] error if the length is zero or not a
+    /// whole number of frames, or the spec or map is invalid.
+    pub fn start(mut self, ctx: &mut Context, callback: Option<Box<dyn FnMut(bool) + 'static>>)
+        -> Result<Upload, PAErr>
+    {
+        let frame_size = match self.spec.is_valid() {
+            true => self.spec.frame_size(),
+            false => return Err(PAErr::from(Code::Invalid)),
+        };
+        if self.length == 0 || self.length % frame_size != 0 {
+            return Err(PAErr::from(Code::Invalid));
+        }
+        if let Some(map) = self.map.as_ref() {
+            if !map.is_compatible_with_sample_spec(&self.spec) {
+                return Err(PAErr::from(Code::Invalid));
+            }
+        }
+
+        let stream = match self.proplist.as_mut() {
+            Some(proplist) => {
+                Stream::new_with_proplist(ctx, &self.name, &self.spec, self.map.as_ref(), proplist)
+            },
+            None => Stream::new(ctx, &self.name, &self.spec, self.map.as_ref()),
+        };
+        let mut stream = stream.ok_or_else(|| ctx.errno())?;
+
+        let shared = Rc::new(UploadShared {
+            stream: unsafe { capi::pa_stream_ref(stream.ptr) },
+            reader: RefCell::new(self.reader),
+            remaining: Cell::new(self.length),
+            frame_size,
+            chunk: RefCell::new(Vec::new()),
+            finishing: Cell::new(false),
+            failed: Cell::new(false),
+            result: Cell::new(None),
+            callback: RefCell::new(callback),
+        });
+
+        let weak = Rc::downgrade(&shared);
+        stream.set_write_callback(Some(Box::new(move |nbytes| {
+            if let Some(shared) = weak.upgrade() {
+                shared.write(nbytes);
+            }
+        })));
+        let weak = Rc::downgrade(&shared);
+        stream.set_state_callback(Some(Box::new(move || {
+            if let Some(shared) = weak.upgrade() {
+                shared.state_changed();
+            }
+        })));
+
+        stream.connect_upload(self.length)?;
+        Ok(Upload { shared, stream })
+    }
+
+    /// Checks whether or not the sample is already held in the cache, as described by the info.
+    fn is_cached(&self, info: &SampleInfo) -> bool {
+        self.matches(info.name.as_deref(), &info.sample_spec, info.bytes, &info.channel_map)
+    }
+
+    /// Checks whether or not the sample matches that of the specified name, sample spec, length
+    /// and channel map.
+    fn matches(&self, name: Option<&str>, spec: &sample::Spec, bytes: u32,
+        map: &channelmap::Map) -> bool
+    {
+        name == Some(self.name.as_str())
+            && *spec == self.spec
+            && bytes as usize == self.length
+            && self.map.map_or(true, |own| *map == own)
+    }
+}
+
+/// A sample upload in progress.
+///
+/// Dropping this before completion cancels the upload.
+pub struct Upload {
+    /// State shared with the stream callbacks.
+    ///
+    /// Declared first, such that no completion is reported upon cancellation through dropping.
+    shared: Rc<UploadShared>,
+    /// The upload stream.
+    stream: Stream,
+}
+
+/// State shared between an [`Upload`] and the stream callbacks.
+struct UploadShared {
+    /// The stream, of which a reference is held.
+    stream: *mut capi::pa_stream,
+    /// Source of the data.
+    reader: RefCell<Box<dyn Read>>,
+    /// Length of the data yet to be written, in bytes.
+    remaining: Cell<usize>,
+    /// The frame size of the data.
+    frame_size: usize,
+    /// Buffer for reading chunks of the data, kept for reuse.
+    chunk: RefCell<Vec<u8>>,
+    /// Whether or not the upload is being finished, all data having been written.
+    finishing: Cell<bool>,
+    /// Whether or not reading or writing the data failed.
+    failed: Cell<bool>,
+    /// The outcome, once complete.
+    result: Cell<Option<bool>>,
+    /// The completion callback.
+    callback: RefCell<Option<CompletionCb>>,
+}
+
+impl Upload {
+    /// Gets the outcome of the upload, indicating success, or `None` if not yet complete.
+    #[inline]
+    pub fn result(&self) -> Option<bool> {
+        self.shared.result.get()
+    }
+
+    /// Gets the length of the data yet to be written, in bytes.
+    #[inline]
+    pub fn bytes_remaining(&self) -> usize {
+        self.shared.remaining.get()
+    }
+
+    /// Gets the upload stream.
+    #[inline]
+    pub fn stream(&self) -> &Stream {
+        &self.stream
+    }
+}
+
+impl UploadShared {
+    /// Writes up to the specified number of bytes of data, finishing the upload once all has been
+    /// written.
+    fn write(&self, nbytes: usize) {
+        if self.finishing.get() || self.failed.get() {
+            return;
+        }
+        let mut stream = Stream::from_raw_weak(self.stream);
+        let len = std::cmp::min(nbytes, self.remaining.get());
+        let len = len - len % self.frame_size;
+        if len == 0 {
+            return;
+        }
+
+        let mut chunk = self.chunk.borrow_mut();
+        chunk.resize(len, 0);
+        let written = self.reader.borrow_mut().read_exact(&mut chunk[..]).is_ok()
+            && stream.write(&chunk[..], None, 0, SeekMode::Relative).is_ok();
+        if !written {
+            self.fail(&mut stream);
+            return;
+        }
+
+        self.remaining.set(self.remaining.get() - len);
+        if self.remaining.get() == 0 {
+            self.finishing.set(true);
+            if stream.finish_upload().is_err() {
+                self.fail(&mut stream);
+            }
+        }
+    }
+
+    /// Abandons the upload.
+    fn fail(&self, stream: &mut Stream) {
+        self.failed.set(true);
+        // The resulting state change reports the failure
+        let _ = stream.disconnect();
+    }
+
+    /// Reports completion, once the stream has been terminated, or has failed.
+    fn state_changed(&self) {
+        let success = match Stream::from_raw_weak(self.stream).get_state() {
+            State::Terminated => self.finishing.get() && !self.failed.get(),
+            State::Failed => false,
+            _ => return,
+        };
+        if self.result.get().is_some() {
+            return;
+        }
+        self.result.set(Some(success));
+        let callback = self.callback.borrow_mut().take();
+        if let Some(mut callback) = callback {
+            (callback)(success);
+        }
+    }
+}
+
+impl Drop for UploadShared {
+    fn drop(&mut self) {
+        unsafe { capi::pa_stream_unref(self.stream) };
+    }
+}
+
+/// The outcome of synchronising samples with the sample cache.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SyncReport {
+    /// Names of samples skipped, being already cached.
+    pub skipped: Vec<String>,
+    /// Names of samples successfully uploaded.
+    pub uploaded: Vec<String>,
+    /// Names of samples which failed to upload.
+    pub failed: Vec<String>,
+}
+
+/// Synchronisation of samples with the sample cache, in progress.
+///
+/// See [`Context::sync_samples()`]. Dropping this before completion cancels any uploads in
+/// progress.
+pub struct SampleSync {
+    /// State shared with the callbacks.
+    shared: Rc<SyncShared>,
+}
+
+/// State shared between a [`SampleSync`] and the callbacks.
+struct SyncShared {
+    /// The context, of which a reference is held.
+    context: *mut ContextInternal,
+    /// Samples yet to be checked against the cache.
+    pending: RefCell<Vec<SampleUpload>>,
+    /// Uploads in progress, or completed.
+    active: RefCell<Vec<Upload>>,
+    /// Progress of the synchronisation.
+    progress: RefCell<SyncProgress>,
+    /// The completion callback.
+    callback: RefCell<Option<SyncCb>>,
+}
+
+/// Progress of synchronising samples, as uploads are started and complete.
+#[derive(Debug, Default)]
+struct SyncProgress {
+    /// Whether or not every upload needed has been started.
+    started: bool,
+    /// The number of uploads yet to complete.
+    remaining: usize,
+    /// The outcome so far.
+    report: SyncReport,
+    /// Whether or not synchronisation has completed.
+    done: bool,
+}
+
+impl SampleSync {
+    /// Checks whether or not synchronisation has completed.
+    #[inline]
+    pub fn is_done(&self) -> bool {
+        self.shared.progress.borrow().done
+    }
+
+    /// Gets the outcome so far.
+    #[inline]
+    pub fn report(&self) -> SyncReport {
+        self.shared.progress.borrow().report.clone()
+    }
+}
+
+impl SyncShared {
+    /// Starts uploading those samples not already cached.
+    fn start(self: &Rc<Self>, cached: &[SampleInfo<'static>]) {
+        let mut ctx = Context::from_raw_weak(self.context);
+        let pending = std::mem::take(&mut *self.pending.borrow_mut());
+        for upload in pending {
+            let name = upload.name.clone();
+            if cached.iter().any(|info| upload.is_cached(info)) {
+                self.progress.borrow_mut().skipped(name);
+                continue;
+            }
+
+            self.progress.borrow_mut().upload_started();
+            let weak = Rc::downgrade(self);
+            let callback_name = name.clone();
+            let callback = Box::new(move |success| {
+                if let Some(shared) = weak.upgrade() {
+                    shared.completed(callback_name.clone(), success);
+                }
+            });
+            match upload.start(&mut ctx, Some(callback)) {
+                Ok(upload) => self.active.borrow_mut().push(upload),
+                Err(_) => self.progress.borrow_mut().upload_completed(name, false),
+            }
+        }
+        self.progress.borrow_mut().started = true;
+        self.check_done();
+    }
+
+    /// Records completion of an upload.
+    fn completed(&self, name: String, success: bool) {
+        self.progress.borrow_mut().upload_completed(name, success);
+        self.check_done();
+    }
+
+    /// Reports completion, once all uploads have completed.
+    fn check_done(&self) {
+        let report = self.progress.borrow_mut().completion();
+        if let Some(report) = report {
+            let callback = self.callback.borrow_mut().take();
+            if let Some(mut callback) = callback {
+                (callback)(report);
+            }
+        }
+    }
+}
+
+impl SyncProgress {
+    /// Records a sample skipped, being already cached.
+    fn skipped(&mut self, name: String) {
+        self.report.skipped.push(name);
+    }
+
+    /// Records an upload being started.
+    fn upload_started(&mut self) {
+        self.remaining += 1;
+    }
+
+    /// Records completion of an upload started, or its failure to start.
+    fn upload_completed(&mut self, name: String, success: bool) {
+        match success {
+            true => self.report.uploaded.push(name),
+            false => self.report.failed.push(name),
+        }
+        self.remaining -= 1;
+    }
+
+    /// Gets the report to give upon completion, once every upload started has completed, and
+    /// only the once.
+    fn completion(&mut self) -> Option<SyncReport> {
+        if !self.started || self.remaining != 0 || self.done {
+            return None;
+        }
+        self.done = true;
+        Some(self.report.clone())
+    }
+}
+
+impl Drop for SyncShared {
+    fn drop(&mut self) {
+        unsafe { capi::pa_context_unref(self.context) };
+    }
 }
 
 /// Proxy for completion success callbacks.
@@ -178,3 +666,60 @@ fn play_sample_success_cb_proxy(_: *mut ContextInternal, index: u32, userdata: *
         (callback)(index_actual);
     });
 }
+
+/// Check that completion is reported once, only once every upload started has completed
+#[test]
+fn sync_progress_completion() {
+    let mut progress = SyncProgress::default();
+    assert_eq!(progress.completion(), None);
+
+    progress.skipped("a".to_owned());
+    progress.upload_started();
+    progress.upload_started();
+    progress.upload_started();
+    progress.upload_completed("b".to_owned(), true);
+    progress.started = true;
+    assert_eq!(progress.completion(), None);
+
+    progress.upload_completed("c".to_owned(), false);
+    assert_eq!(progress.completion(), None);
+    progress.upload_completed("d".to_owned(), true);
+    let report = SyncReport {
+        skipped: vec!["a".to_owned()],
+        uploaded: vec!["b".to_owned(), "d".to_owned()],
+        failed: vec!["c".to_owned()],
+    };
+    assert_eq!(progress.completion(), Some(report));
+    assert!(progress.done);
+    assert_eq!(progress.completion(), None);
+
+    // Nothing needing uploading
+    let mut progress = SyncProgress { started: true, ..SyncProgress::default() };
+    assert_eq!(progress.completion(), Some(SyncReport::default()));
+}
+
+/// Check matching of samples against those already cached
+#[test]
+fn upload_matches_cached() {
+    use crate::channelmap::Position;
+
+    let spec = sample::Spec { format: sample::Format::S16le, rate: 44100, channels: 2 };
+    let mut map = channelmap::Map::default();
+    map.set_len(2);
+    map.get_mut().copy_from_slice(&[Position::FrontLeft, Position::FrontRight]);
+    let mut other_map = map;
+    other_map.get_mut().swap(0, 1);
+
+    let upload = SampleUpload::new("bell", &spec, vec![0; 400]);
+    assert!(upload.matches(Some("bell"), &spec, 400, &map));
+    assert!(upload.matches(Some("bell"), &spec, 400, &other_map));
+    assert!(!upload.matches(None, &spec, 400, &map));
+    assert!(!upload.matches(Some("bells"), &spec, 400, &map));
+    assert!(!upload.matches(Some("bell"), &spec, 404, &map));
+    let other_spec = sample::Spec { rate: 48000, ..spec };
+    assert!(!upload.matches(Some("bell"), &other_spec, 400, &map));
+
+    let upload = upload.channel_map(&map);
+    assert!(upload.matches(Some("bell"), &spec, 400, &map));
+    assert!(!upload.matches(Some("bell"), &spec, 400, &other_map));
+}
@@ -31,6 +31,9 @@
 //! callback, detailing *facility* and *operation*, where for instance [`Facility::Source`] with
 //! [`Operation::New`] indicates that a new source was added.
 //!
+//! Alternatively, [`Context::subscribe_events()`] delivers each [`Event`] with the object concerned
+//! already fetched, and [`Context::subscribe_event_stream()`] delivers them through a stream.
+//!
 //! # Example
 //!
 //! Subscribe (declare interest):
@@ -48,11 +51,19 @@
 //! ```
 
 use std::os::raw::c_void;
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, VecDeque};
+use std::pin::Pin;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+use std::task::{Poll, Waker};
 use bitflags::bitflags;
 use num_derive::{FromPrimitive, ToPrimitive};
 use super::{ContextInternal, Context};
+use super::introspect::{CardInfo, ClientInfo, ModuleInfo, SampleInfo, ServerInfo, SinkInfo,
+    SinkInputInfo, SourceInfo, SourceOutputInfo};
 use crate::operation;
-use crate::callbacks::{box_closure_get_capi_ptr, MultiUseCallback};
+use crate::callbacks::{box_closure_get_capi_ptr, ListResult, MultiUseCallback};
 
 pub use capi::context::subscribe::pa_subscription_event_type_t as EventType;
 
@@ -230,3 +241,508 @@ fn cb_proxy(_: *mut ContextInternal, et: EventType, index: u32, userdata: *mut c
         (callback)(facility, operation, index);
     });
 }
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// Resolved events
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// An event, resolved to the object concerned.
+///
+/// See [`Context::subscribe_events()`].
+#[derive(Debug)]
+pub enum Event {
+    /// A sink was added.
+    SinkAdded(SinkInfo<'static>),
+    /// A sink changed.
+    SinkChanged(SinkInfo<'static>),
+    /// A sink was removed. Holds the index and the last-known object, where available.
+    SinkRemoved(u32, Option<SinkInfo<'static>>),
+    /// A source was added.
+    SourceAdded(SourceInfo<'static>),
+    /// A source changed.
+    SourceChanged(SourceInfo<'static>),
+    /// A source was removed. Holds the index and the last-known object, where available.
+    SourceRemoved(u32, Option<SourceInfo<'static>>),
+    /// A sink input was added.
+    SinkInputAdded(SinkInputInfo<'static>),
+    /// A sink input changed.
+    SinkInputChanged(SinkInputInfo<'static>),
+    /// A sink input was removed. Holds the index and the last-known object, where available.
+    SinkInputRemoved(u32, Option<SinkInputInfo<'static>>),
+    /// A source output was added.
+    SourceOutputAdded(SourceOutputInfo<'static>),
+    /// A source output changed.
+    SourceOutputChanged(SourceOutputInfo<'static>),
+    /// A source output was removed. Holds the index and the last-known object, where available.
+    SourceOutputRemoved(u32, Option<SourceOutputInfo<'static>>),
+    /// A module was loaded.
+    ModuleAdded(ModuleInfo<'static>),
+    /// A module changed.
+    ModuleChanged(ModuleInfo<'static>),
+    /// A module was unloaded. Holds the index and the last-known object, where available.
+    ModuleRemoved(u32, Option<ModuleInfo<'static>>),
+    /// A client connected.
+    ClientAdded(ClientInfo<'static>),
+    /// A client changed.
+    ClientChanged(ClientInfo<'static>),
+    /// A client disconnected. Holds the index and the last-known object, where available.
+    ClientRemoved(u32, Option<ClientInfo<'static>>),
+    /// A sample was added to the sample cache.
+    SampleAdded(SampleInfo<'static>),
+    /// A sample in the sample cache changed.
+    SampleChanged(SampleInfo<'static>),
+    /// A sample was removed from the sample cache. Holds the index and the last-known object,
+    /// where available.
+    SampleRemoved(u32, Option<SampleInfo<'static>>),
+    /// A card was added.
+    CardAdded(CardInfo<'static>),
+    /// A card changed.
+    CardChanged(CardInfo<'static>),
+    /// A card was removed. Holds the index and the last-known object, where available.
+    CardRemoved(u32, Option<CardInfo<'static>>),
+    /// The server information changed.
+    ServerChanged(ServerInfo<'static>),
+}
+
+/// An object fetched upon an event.
+enum Object {
+    Sink(SinkInfo<'static>),
+    Source(SourceInfo<'static>),
+    SinkInput(SinkInputInfo<'static>),
+    SourceOutput(SourceOutputInfo<'static>),
+    Module(ModuleInfo<'static>),
+    Client(ClientInfo<'static>),
+    Sample(SampleInfo<'static>),
+    Card(CardInfo<'static>),
+    Server(ServerInfo<'static>),
+}
+
+impl Object {
+    /// Makes a copy of the object, for holding as the last-known object.
+    fn copy(&self) -> Option<Self> {
+        match self {
+            Object::Sink(i) => Some(Object::Sink(i.to_owned())),
+            Object::Source(i) => Some(Object::Source(i.to_owned())),
+            Object::SinkInput(i) => Some(Object::SinkInput(i.to_owned())),
+            Object::SourceOutput(i) => Some(Object::SourceOutput(i.to_owned())),
+            Object::Module(i) => Some(Object::Module(i.to_owned())),
+            Object::Client(i) => Some(Object::Client(i.to_owned())),
+            Object::Sample(i) => Some(Object::Sample(i.to_owned())),
+            Object::Card(i) => Some(Object::Card(i.to_owned())),
+            // Never removed, thus no need to keep hold of it
+            Object::Server(_) => None,
+        }
+    }
+
+    /// Converts into an event for the object having been added (`added` being `true`) or changed.
+    fn into_event(self, added: bool) -> Event {
+        match (self, added) {
+            (Object::Sink(i), true) => Event::SinkAdded(i),
+            (Object::Sink(i), false) => Event::SinkChanged(i),
+            (Object::Source(i), true) => Event::SourceAdded(i),
+            (Object::Source(i), false) => Event::SourceChanged(i),
+            (Object::SinkInput(i), true) => Event::SinkInputAdded(i),
+            (Object::SinkInput(i), false) => Event::SinkInputChanged(i),
+            (Object::SourceOutput(i), true) => Event::SourceOutputAdded(i),
+            (Object::SourceOutput(i), false) => Event::SourceOutputChanged(i),
+            (Object::Module(i), true) => Event::ModuleAdded(i),
+            (Object::Module(i), false) => Event::ModuleChanged(i),
+            (Object::Client(i), true) => Event::ClientAdded(i),
+            (Object::Client(i), false) => Event::ClientChanged(i),
+            (Object::Sample(i), true) => Event::SampleAdded(i),
+            (Object::Sample(i), false) => Event::SampleChanged(i),
+            (Object::Card(i), true) => Event::CardAdded(i),
+            (Object::Card(i), false) => Event::CardChanged(i),
+            (Object::Server(i), _) => Event::ServerChanged(i),
+        }
+    }
+}
+
+/// Creates the removal event for an object, given its last-known state, if any.
+fn removal_event(facility: Facility, index: u32, last: Option<Object>) -> Option<Event> {
+    Some(match (facility, last) {
+        (Facility::Sink, Some(Object::Sink(i))) => Event::SinkRemoved(index, Some(i)),
+        (Facility::Sink, _) => Event::SinkRemoved(index, None),
+        (Facility::Source, Some(Object::Source(i))) => Event::SourceRemoved(index, Some(i)),
+        (Facility::Source, _) => Event::SourceRemoved(index, None),
+        (Facility::SinkInput, Some(Object::SinkInput(i))) => {
+            Event::SinkInputRemoved(index, Some(i))
+        },
+        (Facility::SinkInput, _) => Event::SinkInputRemoved(index, None),
+        (Facility::SourceOutput, Some(Object::SourceOutput(i))) => {
+            Event::SourceOutputRemoved(index, Some(i))
+        },
+        (Facility::SourceOutput, _) => Event::SourceOutputRemoved(index, None),
+        (Facility::Module, Some(Object::Module(i))) => Event::ModuleRemoved(index, Some(i)),
+        (Facility::Module, _) => Event::ModuleRemoved(index, None),
+        (Facility::Client, Some(Object::Client(i))) => Event::ClientRemoved(index, Some(i)),
+        (Facility::Client, _) => Event::ClientRemoved(index, None),
+        (Facility::SampleCache, Some(Object::Sample(i))) => Event::SampleRemoved(index, Some(i)),
+        (Facility::SampleCache, _) => Event::SampleRemoved(index, None),
+        (Facility::Card, Some(Object::Card(i))) => Event::CardRemoved(index, Some(i)),
+        (Facility::Card, _) => Event::CardRemoved(index, None),
+        (Facility::Server, _) => return None,
+    })
+}
+
+/// An event awaiting delivery, in order of receipt.
+enum Slot {
+    /// The object is being fetched.
+    Pending,
+    /// The object was added (`added` being `true`) or changed, and has been fetched, unless it
+    /// has since vanished.
+    Fetched { facility: Facility, index: u32, added: bool, object: Option<Box<Object>> },
+    /// The object was removed.
+    Removed { facility: Facility, index: u32 },
+}
+
+/// State of the resolution of events, held by the subscription callback.
+struct Resolver {
+    /// The context, used in issuing queries from within callbacks.
+    context: *mut ContextInternal,
+    /// Events awaiting delivery, in order of receipt.
+    queue: RefCell<VecDeque<Slot>>,
+    /// Sequence number of the event at the front of the queue.
+    front: Cell<u64>,
+    /// Last-known objects, keyed by facility and index.
+    cache: RefCell<HashMap<(u32, u32), Object>>,
+    /// The user callback.
+    callback: RefCell<Box<dyn FnMut(Event)>>,
+}
+
+impl Resolver {
+    /// Acts upon a subscription event, fetching the object concerned where applicable.
+    fn handle_event(self: &Rc<Self>, facility: Facility, operation: Operation, index: u32) {
+        if operation == Operation::Removed {
+            self.enqueue(Slot::Removed { facility, index });
+            self.flush();
+            return;
+        }
+        let seq = self.enqueue(Slot::Pending);
+
+        let added = operation == Operation::New;
+        let introspector = Context::from_raw_weak(self.context).introspect();
+
+        macro_rules! fetch {
+            ($method:ident, $variant:ident) => {{
+                let resolver = Rc::clone(self);
+                let mut object = None;
+                introspector.$method(index, move |r| match r {
+                    ListResult::Item(i) => {
+                        object = Some(Box::new(Object::$variant(i.to_owned())));
+                    },
+                    ListResult::End | ListResult::Error => {
+                        let object = object.take();
+                        resolver.fill(seq, Slot::Fetched { facility, index, added, object });
+                    },
+                });
+            }};
+        }
+
+        match facility {
+            Facility::Sink => fetch!(get_sink_info_by_index, Sink),
+            Facility::Source => fetch!(get_source_info_by_index, Source),
+            Facility::SinkInput => fetch!(get_sink_input_info, SinkInput),
+            Facility::SourceOutput => fetch!(get_source_output_info, SourceOutput),
+            Facility::Module => fetch!(get_module_info, Module),
+            Facility::Client => fetch!(get_client_info, Client),
+            Facility::SampleCache => fetch!(get_sample_info_by_index, Sample),
+            Facility::Card => fetch!(get_card_info_by_index, Card),
+            Facility::Server => {
+                let resolver = Rc::clone(self);
+                introspector.get_server_info(move |info| {
+                    let object = Some(Box::new(Object::Server(info.to_owned())));
+                    resolver.fill(seq, Slot::Fetched { facility, index, added, object });
+                });
+            },
+        }
+    }
+
+    /// Queues an event, returning its sequence number.
+    fn enqueue(&self, slot: Slot) -> u64 {
+        let mut queue = self.queue.borrow_mut();
+        queue.push_back(slot);
+        self.front.get() + queue.len() as u64 - 1
+    }
+
+    /// Fills the slot of a pending event.
+    fn fill(&self, seq: u64, slot: Slot) {
+        let pos = (seq - self.front.get()) as usize;
+        self.queue.borrow_mut()[pos] = slot;
+        self.flush();
+    }
+
+    /// Delivers events from the front of the queue, up to the first still pending.
+    fn flush(&self) {
+        let mut events = Vec::new();
+        {
+            let mut queue = self.queue.borrow_mut();
+            let mut cache = self.cache.borrow_mut();
+            while !matches!(queue.front(), None | Some(Slot::Pending)) {
+                self.front.set(self.front.get() + 1);
+                match queue.pop_front() {
+                    Some(Slot::Fetched { facility, index, added, object: Some(object) }) => {
+                        if let Some(copy) = object.copy() {
+                            cache.insert((facility as u32, index), copy);
+                        }
+                        events.push((*object).into_event(added));
+                    },
+                    Some(Slot::Removed { facility, index }) => {
+                        let last = cache.remove(&(facility as u32, index));
+                        events.extend(removal_event(facility, index, last));
+                    },
+                    _ => {},
+                }
+            }
+        }
+        let mut callback = self.callback.borrow_mut();
+        for event in events {
+            (callback)(event);
+        }
+    }
+}
+
+/// A stream of resolved events.
+///
+/// See [`Context::subscribe_event_stream()`].
+///
+/// With the `futures` feature enabled, this implements the `futures_core::Stream` trait. The
+/// stream never ends.
+#[must_use = "streams do nothing unless polled"]
+pub struct EventStream {
+    /// State shared with the subscription callback.
+    shared: Arc<Mutex<EventStreamShared>>,
+}
+
+/// State shared between an [`EventStream`] and the subscription callback.
+struct EventStreamShared {
+    /// Events delivered but not yet taken.
+    events: VecDeque<Event>,
+    /// Waker of the task last polling the stream.
+    waker: Option<Waker>,
+}
+
+impl EventStream {
+    /// Attempts to pull out the next event, registering the current task for wakeup if none is
+    /// available yet.
+    pub fn poll_next(self: Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Option<Event>> {
+        let mut shared = self.shared.lock().unwrap();
+        match shared.events.pop_front() {
+            Some(event) => Poll::Ready(Some(event)),
+            None => {
+                shared.waker = Some(cx.waker().clone());
+                Poll::Pending
+            },
+        }
+    }
+}
+
+#[cfg(feature = "futures")]
+#[cfg_attr(docsrs, doc(cfg(feature = "futures")))]
+impl futures_core::Stream for EventStream {
+    type Item = Event;
+
+    #[inline]
+    fn poll_next(self: Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Option<Event>> {
+        EventStream::poll_next(self, cx)
+    }
+}
+
+impl Context {
+    /// Enables event notification, with events resolved to the objects concerned.
+    ///
+    /// This is an alternative to using [`subscribe()`] along with [`set_subscribe_callback()`],
+    /// which it uses, thus replacing any callback previously set. Rather than being given just the
+    /// index of the object concerned, the callback is given an [`Event`] holding the object
+    /// itself, fetched upon receipt of the event. Removal events hold the last-known state of the
+    /// object, where available, which is only the case if it has been added or changed since
+    /// subscribing. Events are delivered in the order received.
+    ///
+    /// A context has only the one subscription callback, thus this cannot be used along with a
+    /// topology [`Monitor`], which is built upon it: whichever is set up last replaces the other,
+    /// which then silently receives no further events.
+    ///
+    /// The returned operation reports the success of the subscription request.
+    ///
+    /// Panics if the underlying C function returns a null pointer.
+    ///
+    /// [`subscribe()`]: Self::subscribe
+    /// [`set_subscribe_callback()`]: Self::set_subscribe_callback
+    /// [`Monitor`]: super::topology::Monitor
+    pub fn subscribe_events<F>(&mut self, mask: InterestMaskSet, callback: F)
+        -> operation::Operation<dyn FnMut(bool)>
+        where F: FnMut(Event) + 'static
+    {
+        let resolver = Rc::new(Resolver {
+            context: self.ptr,
+            queue: RefCell::new(VecDeque::new()),
+            front: Cell::new(0),
+            cache: RefCell::new(HashMap::new()),
+            callback: RefCell::new(Box::new(callback)),
+        });
+        self.set_subscribe_callback(Some(Box::new(move |facility, operation, index| {
+            if let (Some(facility), Some(operation)) = (facility, operation) {
+                resolver.handle_event(facility, operation, index);
+            }
+        })));
+        self.subscribe(mask, |_| {})
+    }
+
+    /// Enables event notification, with events resolved to the objects concerned, delivered
+    /// through a stream.
+    ///
+    /// This is the same as [`subscribe_events()`](Self::subscribe_events), except that the events
+    /// are delivered through the returned stream. Once the stream is dropped, events are no longer
+    /// acted upon, though the subscription callback remains set until replaced.
+    ///
+    /// Panics if the underlying C function returns a null pointer.
+    pub fn subscribe_event_stream(&mut self, mask: InterestMaskSet) -> EventStream {
+        let shared = EventStreamShared { events: VecDeque::new(), waker: None };
+        let shared = Arc::new(Mutex::new(shared));
+        let weak = Arc::downgrade(&shared);
+        self.subscribe_events(mask, move |event| {
+            if let Some(shared) = weak.upgrade() {
+                let mut shared = shared.lock().unwrap();
+                shared.events.push_back(event);
+                if let Some(waker) = shared.waker.take() {
+                    waker.wake();
+                }
+            }
+        });
+        EventStream { shared }
+    }
+}
+
+/// Makes a resolver recording the events delivered, for the tests.
+#[cfg(test)]
+fn test_resolver() -> (Resolver, Rc<RefCell<Vec<Event>>>) {
+    let events = Rc::new(RefCell::new(Vec::new()));
+    let events_ref = Rc::clone(&events);
+    let resolver = Resolver {
+        context: std::ptr::null_mut(),
+        queue: RefCell::new(VecDeque::new()),
+        front: Cell::new(0),
+        cache: RefCell::new(HashMap::new()),
+        callback: RefCell::new(Box::new(move |event| events_ref.borrow_mut().push(event))),
+    };
+    (resolver, events)
+}
+
+/// Makes a fetched client slot, for the tests.
+#[cfg(test)]
+fn test_fetched(index: u32, added: bool, name: &str) -> Slot {
+    let client = ClientInfo {
+        index,
+        name: Some(std::borrow::Cow::Owned(name.to_owned())),
+        owner_module: None,
+        driver: None,
+        proplist: crate::proplist::Proplist::new().unwrap(),
+    };
+    let object = Some(Box::new(Object::Client(client)));
+    Slot::Fetched { facility: Facility::Client, index, added, object }
+}
+
+/// Takes the client events delivered, summarised as the kind of event, the index, and the name of
+/// the client held, if any.
+#[cfg(test)]
+fn test_take(events: &RefCell<Vec<Event>>) -> Vec<(&'static str, u32, Option<String>)> {
+    let name = |i: &ClientInfo| i.name.as_ref().map(|n| n.to_string());
+    events.borrow_mut().drain(..).map(|event| match event {
+        Event::ClientAdded(i) => ("added", i.index, name(&i)),
+        Event::ClientChanged(i) => ("changed", i.index, name(&i)),
+        Event::ClientRemoved(index, i) => ("removed", index, i.as_ref().and_then(name)),
+        event => panic!("unexpected event {:?}", event),
+    }).collect()
+}
+
+/// Check that events whose objects are fetched out of order are still delivered in order
+#[test]
+fn resolver_order() {
+    let (resolver, events) = test_resolver();
+    let first = resolver.enqueue(Slot::Pending);
+    let second = resolver.enqueue(Slot::Pending);
+    let third = resolver.enqueue(Slot::Pending);
+    assert_eq!((first, second, third), (0, 1, 2));
+
+    resolver.fill(third, test_fetched(3, true, "c"));
+    resolver.fill(second, test_fetched(2, true, "b"));
+    assert_eq!(test_take(&events), []);
+    resolver.fill(first, test_fetched(1, false, "a"));
+    assert_eq!(test_take(&events), [
+        ("changed", 1, Some(String::from("a"))),
+        ("added", 2, Some(String::from("b"))),
+        ("added", 3, Some(String::from("c"))),
+    ]);
+    assert!(resolver.queue.borrow().is_empty());
+
+    // Sequence numbers carry on from those delivered
+    let fourth = resolver.enqueue(Slot::Pending);
+    let fifth = resolver.enqueue(Slot::Pending);
+    assert_eq!((fourth, fifth), (3, 4));
+    resolver.fill(fifth, test_fetched(5, true, "e"));
+    resolver.fill(fourth, test_fetched(4, true, "d"));
+    assert_eq!(test_take(&events), [
+        ("added", 4, Some(String::from("d"))),
+        ("added", 5, Some(String::from("e"))),
+    ]);
+}
+
+/// Check removals wait behind pending fetches, and carry the last-known object, where available
+#[test]
+fn resolver_removal() {
+    let (resolver, events) = test_resolver();
+
+    // No earlier change, queued behind a pending fetch
+    let seq = resolver.enqueue(Slot::Pending);
+    resolver.enqueue(Slot::Removed { facility: Facility::Client, index: 2 });
+    resolver.flush();
+    assert_eq!(test_take(&events), []);
+    resolver.fill(seq, test_fetched(1, true, "a"));
+    assert_eq!(test_take(&events), [("added", 1, Some(String::from("a"))), ("removed", 2, None)]);
+
+    // After a change, along with the earlier addition
+    let seq = resolver.enqueue(Slot::Pending);
+    resolver.fill(seq, test_fetched(1, false, "renamed"));
+    resolver.enqueue(Slot::Removed { facility: Facility::Client, index: 1 });
+    resolver.flush();
+    assert_eq!(test_take(&events), [
+        ("changed", 1, Some(String::from("renamed"))),
+        ("removed", 1, Some(String::from("renamed"))),
+    ]);
+    assert!(resolver.cache.borrow().is_empty());
+
+    // Of a different facility with the same index
+    let seq = resolver.enqueue(Slot::Pending);
+    resolver.fill(seq, test_fetched(3, true, "c"));
+    resolver.enqueue(Slot::Removed { facility: Facility::Module, index: 3 });
+    resolver.flush();
+    let delivered = events.borrow_mut().drain(..).map(|event| match event {
+        Event::ModuleRemoved(3, None) => "module removed",
+        Event::ClientAdded(_) => "client added",
+        event => panic!("unexpected event {:?}", event),
+    }).collect::<Vec<_>>();
+    assert_eq!(delivered, ["client added", "module removed"]);
+    assert_eq!(resolver.cache.borrow().len(), 1);
+}
+
+/// Check that events for objects which vanished before being fetched are dropped
+#[test]
+fn resolver_vanished() {
+    let (resolver, events) = test_resolver();
+    let first = resolver.enqueue(Slot::Pending);
+    let second = resolver.enqueue(Slot::Pending);
+    resolver.fill(second, test_fetched(2, true, "b"));
+    resolver.fill(first, Slot::Fetched {
+        facility: Facility::Client,
+        index: 1,
+        added: true,
+        object: None,
+    });
+    assert_eq!(test_take(&events), [("added", 2, Some(String::from("b")))]);
+    assert_eq!(resolver.front.get(), 2);
+
+    // Nor is anything known of it upon removal
+    resolver.enqueue(Slot::Removed { facility: Facility::Client, index: 1 });
+    resolver.flush();
+    assert_eq!(test_take(&events), [("removed", 1, None)]);
+}
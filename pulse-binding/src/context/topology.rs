@@ -0,0 +1,947 @@
+// Copyright 2026 Lyndon Brown
+//
+// This file is part of the PulseAudio Rust language binding.
+//
+// Licensed under the MIT license or the Apache license (version 2.0), at your option. You may not
+// copy, modify, or distribute this file except in compliance with said license. You can find copies
+// of these licenses either in the LICENSE-MIT and LICENSE-APACHE files, or alternatively at
+// <http://opensource.org/licenses/MIT> and <http://www.apache.org/licenses/LICENSE-2.0>
+// respectively.
+
+//! Server topology snapshots, kept up to date through event subscription.
+//!
+//! # Overview
+//!
+//! A [`Topology`] is a snapshot of the objects of the server: the server information itself, along
+//! with its sinks, sources, cards, modules, clients, sink inputs and source outputs, each held by
+//! index.
+//!
+//! A snapshot can be fetched in one go with [`Topology::fetch()`], which performs all of the
+//! necessary introspection queries, calling back once they have all completed.
+//!
+//! Alternatively a [`Monitor`] can be created, which fetches a snapshot and then keeps it up to
+//! date, using the [subscription](mod@crate::context::subscribe) mechanism. Upon each event, only
+//! the affected object is queried again, and the differences found are reported to a callback as
+//! [`Change`]s, for instance a [`PropertyChange::Volume`] change for a sink, holding the old and
+//! new volumes.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use libpulse_binding::context::topology::{Change, Monitor};
+//!
+//! let monitor = Monitor::new(&mut my_context, |topology, change| {
+//!     match change {
+//!         Change::Loaded => println!("{} sinks", topology.sinks.len()),
+//!         Change::LoadFailed(e) => eprintln!("failed to fetch the topology: {}", e),
+//!         _ => println!("{}", change), // e.g. “sink 3 volume changed from … to …”
+//!     }
+//! });
+//! ```
+
+use std::borrow::Cow;
+use std::cell::{Cell, Ref, RefCell};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::rc::Rc;
+use super::{Context, ContextInternal};
+use super::introspect::{CardInfo, ClientInfo, ModuleInfo, ServerInfo, SinkInfo, SinkInputInfo,
+    SourceInfo, SourceOutputInfo};
+use super::subscribe::{Event, Facility, InterestMaskSet};
+use crate::callbacks::ListResult;
+use crate::def::{SinkState, SourceState};
+use crate::error::PAErr;
+use crate::proplist::Proplist;
+use crate::volume::ChannelVolumes;
+
+/// A snapshot of the objects of the server.
+///
+/// Objects are held in maps, keyed by their index.
+#[derive(Debug, Default)]
+pub struct Topology {
+    /// Server information, if fetched.
+    pub server: Option<ServerInfo<'static>>,
+    /// Sinks.
+    pub sinks: BTreeMap<u32, SinkInfo<'static>>,
+    /// Sources.
+    pub sources: BTreeMap<u32, SourceInfo<'static>>,
+    /// Cards.
+    pub cards: BTreeMap<u32, CardInfo<'static>>,
+    /// Modules.
+    pub modules: BTreeMap<u32, ModuleInfo<'static>>,
+    /// Clients.
+    pub clients: BTreeMap<u32, ClientInfo<'static>>,
+    /// Sink inputs.
+    pub sink_inputs: BTreeMap<u32, SinkInputInfo<'static>>,
+    /// Source outputs.
+    pub source_outputs: BTreeMap<u32, SourceOutputInfo<'static>>,
+}
+
+/// A change to a [`Topology`], as reported by a [`Monitor`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum Change {
+    /// The initial snapshot has been fetched. No other changes are reported before this.
+    Loaded,
+    /// Fetching the initial snapshot failed, with the error of the first query to fail.
+    ///
+    /// This is reported instead of [`Change::Loaded`]. The topology is left incomplete, and no
+    /// further changes are reported; a new monitor is needed to try again.
+    LoadFailed(PAErr),
+    /// An object was added.
+    Added {
+        /// The facility of the object.
+        facility: Facility,
+        /// The index of the object.
+        index: u32,
+    },
+    /// An object was removed.
+    Removed {
+        /// The facility of the object.
+        facility: Facility,
+        /// The index of the object.
+        index: u32,
+    },
+    /// A property of an object changed.
+    ///
+    /// For [`Facility::Server`] the index is [`INVALID_INDEX`](crate::def::INVALID_INDEX).
+    Changed {
+        /// The facility of the object.
+        facility: Facility,
+        /// The index of the object.
+        index: u32,
+        /// The property that changed.
+        property: PropertyChange,
+    },
+}
+
+/// A change to a property of an object, holding the old and new values where applicable.
+///
+/// Note that only the properties listed here are compared, any change to another property goes
+/// unreported.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PropertyChange {
+    /// The name changed (clients only).
+    Name(ValueChange<Option<String>>),
+    /// The description changed (sinks and sources).
+    Description(ValueChange<Option<String>>),
+    /// The volume changed.
+    Volume(Box<ValueChange<ChannelVolumes>>),
+    /// The mute state changed.
+    Mute(ValueChange<bool>),
+    /// The state of a sink changed.
+    SinkState(ValueChange<SinkState>),
+    /// The state of a source changed.
+    SourceState(ValueChange<SourceState>),
+    /// The active port changed, given by name (sinks and sources).
+    ActivePort(ValueChange<Option<String>>),
+    /// The active profile changed, given by name (cards).
+    ActiveProfile(ValueChange<Option<String>>),
+    /// A sink input was moved to another sink.
+    Sink(ValueChange<u32>),
+    /// A source output was moved to another source.
+    Source(ValueChange<u32>),
+    /// The corked state changed (sink inputs and source outputs).
+    Corked(ValueChange<bool>),
+    /// The argument changed (modules).
+    Argument(ValueChange<Option<String>>),
+    /// The default sink changed, given by name (server).
+    DefaultSink(ValueChange<Option<String>>),
+    /// The default source changed, given by name (server).
+    DefaultSource(ValueChange<Option<String>>),
+    /// One or more entries of the property list changed.
+    Properties,
+}
+
+/// The old and new values of a property that changed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValueChange<T> {
+    /// The old value.
+    pub old: T,
+    /// The new value.
+    pub new: T,
+}
+
+impl Topology {
+    /// Fetches a snapshot of the server.
+    ///
+    /// The callback is called once all of the queries have completed, with the snapshot, or with
+    /// the error of the context should any of them have failed. Should the context fail before
+    /// then, the callback is never called.
+    ///
+    /// Panics if the underlying C functions return null pointers, i.e. invalid state.
+    pub fn fetch<F>(context: &Context, callback: F)
+        where F: FnOnce(Result<Topology, PAErr>) + 'static
+    {
+        let target = Rc::new(RefCell::new(Topology::default()));
+        fetch_all(context, target, |t| t, Box::new(|target, error| {
+            match error {
+                Some(e) => callback(Err(e)),
+                None => callback(Ok(target.replace(Topology::default()))),
+            }
+        }));
+    }
+}
+
+/// An object type held by a [`Topology`].
+trait Object: Sized {
+    /// The facility of objects of this type.
+    const FACILITY: Facility;
+
+    /// Gets the map holding objects of this type.
+    fn map(topology: &mut Topology) -> &mut BTreeMap<u32, Self>;
+
+    /// Compares an old and new version of an object, reporting the properties that changed.
+    fn diff(old: &Self, new: &Self, report: &mut dyn FnMut(PropertyChange));
+}
+
+/// Gets an optional string as an owned string.
+fn owned(s: &Option<Cow<'_, str>>) -> Option<String> {
+    s.as_ref().map(|s| s.to_string())
+}
+
+/// Compares proplists, reporting a [`PropertyChange::Properties`] change if they differ.
+fn diff_proplist(old: &Proplist, new: &Proplist, report: &mut dyn FnMut(PropertyChange)) {
+    if old != new {
+        report(PropertyChange::Properties);
+    }
+}
+
+/// Compares values, reporting the change constructed by `change` if they differ.
+macro_rules! diff_value {
+    ($report:ident, $change:ident, $old:expr, $new:expr) => {
+        let (old, new) = ($old, $new);
+        if old != new {
+            $report(PropertyChange::$change(ValueChange { old, new }));
+        }
+    };
+}
+
+impl Object for SinkInfo<'static> {
+    const FACILITY: Facility = Facility::Sink;
+
+    fn map(topology: &mut Topology) -> &mut BTreeMap<u32, Self> {
+        &mut topology.sinks
+    }
+
+    fn diff(old: &Self, new: &Self, report: &mut dyn FnMut(PropertyChange)) {
+        diff_value!(report, Description, owned(&old.description), owned(&new.description));
+        if old.volume != new.volume {
+            let change = ValueChange { old: old.volume, new: new.volume };
+            report(PropertyChange::Volume(Box::new(change)));
+        }
+        diff_value!(report, Mute, old.mute, new.mute);
+        diff_value!(report, SinkState, old.state, new.state);
+        diff_value!(report, ActivePort,
+            old.active_port.as_ref().and_then(|p| owned(&p.name)),
+            new.active_port.as_ref().and_then(|p| owned(&p.name)));
+        diff_proplist(&old.proplist, &new.proplist, report);
+    }
+}
+
+impl Object for SourceInfo<'static> {
+    const FACILITY: Facility = Facility::Source;
+
+    fn map(topology: &mut Topology) -> &mut BTreeMap<u32, Self> {
+        &mut topology.sources
+    }
+
+    fn diff(old: &Self, new: &Self, report: &mut dyn FnMut(PropertyChange)) {
+        diff_value!(report, Description, owned(&old.description), owned(&new.description));
+        if old.volume != new.volume {
+            let change = ValueChange { old: old.volume, new: new.volume };
+            report(PropertyChange::Volume(Box::new(change)));
+        }
+        diff_value!(report, Mute, old.mute, new.mute);
+        diff_value!(report, SourceState, old.state, new.state);
+        diff_value!(report, ActivePort,
+            old.active_port.as_ref().and_then(|p| owned(&p.name)),
+            new.active_port.as_ref().and_then(|p| owned(&p.name)));
+        diff_proplist(&old.proplist, &new.proplist, report);
+    }
+}
+
+impl Object for CardInfo<'static> {
+    const FACILITY: Facility = Facility::Card;
+
+    fn map(topology: &mut Topology) -> &mut BTreeMap<u32, Self> {
+        &mut topology.cards
+    }
+
+    fn diff(old: &Self, new: &Self, report: &mut dyn FnMut(PropertyChange)) {
+        diff_value!(report, ActiveProfile,
+            old.active_profile.as_ref().and_then(|p| owned(&p.name)),
+            new.active_profile.as_ref().and_then(|p| owned(&p.name)));
+        diff_proplist(&old.proplist, &new.proplist, report);
+    }
+}
+
+impl Object for ModuleInfo<'static> {
+    const FACILITY: Facility = Facility::Module;
+
+    fn map(topology: &mut Topology) -> &mut BTreeMap<u32, Self> {
+        &mut topology.modules
+    }
+
+    fn diff(old: &Self, new: &Self, report: &mut dyn FnMut(PropertyChange)) {
+        diff_value!(report, Argument, owned(&old.argument), owned(&new.argument));
+        diff_proplist(&old.proplist, &new.proplist, report);
+    }
+}
+
+impl Object for ClientInfo<'static> {
+    const FACILITY: Facility = Facility::Client;
+
+    fn map(topology: &mut Topology) -> &mut BTreeMap<u32, Self> {
+        &mut topology.clients
+    }
+
+    fn diff(old: &Self, new: &Self, report: &mut dyn FnMut(PropertyChange)) {
+        diff_value!(report, Name, owned(&old.name), owned(&new.name));
+        diff_proplist(&old.proplist, &new.proplist, report);
+    }
+}
+
+impl Object for SinkInputInfo<'static> {
+    const FACILITY: Facility = Facility::SinkInput;
+
+    fn map(topology: &mut Topology) -> &mut BTreeMap<u32, Self> {
+        &mut topology.sink_inputs
+    }
+
+    fn diff(old: &Self, new: &Self, report: &mut dyn FnMut(PropertyChange)) {
+        diff_value!(report, Sink, old.sink, new.sink);
+        if old.volume != new.volume {
+            let change = ValueChange { old: old.volume, new: new.volume };
+            report(PropertyChange::Volume(Box::new(change)));
+        }
+        diff_value!(report, Mute, old.mute, new.mute);
+        diff_value!(report, Corked, old.corked, new.corked);
+        diff_proplist(&old.proplist, &new.proplist, report);
+    }
+}
+
+impl Object for SourceOutputInfo<'static> {
+    const FACILITY: Facility = Facility::SourceOutput;
+
+    fn map(topology: &mut Topology) -> &mut BTreeMap<u32, Self> {
+        &mut topology.source_outputs
+    }
+
+    fn diff(old: &Self, new: &Self, report: &mut dyn FnMut(PropertyChange)) {
+        diff_value!(report, Source, old.source, new.source);
+        if old.volume != new.volume {
+            let change = ValueChange { old: old.volume, new: new.volume };
+            report(PropertyChange::Volume(Box::new(change)));
+        }
+        diff_value!(report, Mute, old.mute, new.mute);
+        diff_value!(report, Corked, old.corked, new.corked);
+        diff_proplist(&old.proplist, &new.proplist, report);
+    }
+}
+
+/// Compares an old and new version of the server information, reporting the properties that
+/// changed.
+fn diff_server(old: &ServerInfo, new: &ServerInfo, report: &mut dyn FnMut(PropertyChange)) {
+    diff_value!(report, DefaultSink, owned(&old.default_sink_name), owned(&new.default_sink_name));
+    diff_value!(report, DefaultSource, owned(&old.default_source_name),
+        owned(&new.default_source_name));
+}
+
+/// Called once all queries of a fetch have completed, with the error of the first that failed.
+type FetchDone<T> = Box<dyn FnOnce(&T, Option<PAErr>)>;
+
+/// Progress of the set of queries making up a full fetch, filling the topology held in `T`.
+struct Fetch<T> {
+    /// The object holding the topology being filled.
+    target: Rc<T>,
+    /// Gets the topology from the target.
+    get: fn(&T) -> &RefCell<Topology>,
+    /// The context, for obtaining its error should a query fail.
+    context: *mut ContextInternal,
+    /// Number of queries yet to complete.
+    pending: Cell<usize>,
+    /// The error of the first query to fail, if any.
+    error: Cell<Option<PAErr>>,
+    /// Called once all queries have completed.
+    done: RefCell<Option<FetchDone<T>>>,
+}
+
+impl<T> Fetch<T> {
+    /// Gives mutable access to the topology being filled.
+    fn with<F: FnOnce(&mut Topology)>(&self, f: F) {
+        f(&mut (self.get)(&self.target).borrow_mut());
+    }
+
+    /// Records completion of a query.
+    fn finish(&self, failed: bool) {
+        if failed && self.error.get().is_none() {
+            self.error.set(Some(PAErr(unsafe { capi::pa_context_errno(self.context) })));
+        }
+        self.pending.set(self.pending.get() - 1);
+        if self.pending.get() == 0 {
+            let done = self.done.borrow_mut().take();
+            if let Some(done) = done {
+                done(&self.target, self.error.get());
+            }
+        }
+    }
+}
+
+/// Performs all of the queries needed to fill a topology, held within `target`.
+fn fetch_all<T: 'static>(context: &Context, target: Rc<T>, get: fn(&T) -> &RefCell<Topology>,
+    done: FetchDone<T>)
+{
+    let fetch = Rc::new(Fetch {
+        target,
+        get,
+        context: context.ptr,
+        pending: Cell::new(8),
+        error: Cell::new(None),
+        done: RefCell::new(Some(done)),
+    });
+    let introspector = context.introspect();
+
+    macro_rules! fetch_list {
+        ($method:ident, $field:ident) => {{
+            let fetch = Rc::clone(&fetch);
+            introspector.$method(move |r| match r {
+                ListResult::Item(i) => fetch.with(|t| { t.$field.insert(i.index, i.to_owned()); }),
+                ListResult::End => fetch.finish(false),
+                ListResult::Error => fetch.finish(true),
+            });
+        }};
+    }
+
+    {
+        let fetch = Rc::clone(&fetch);
+        introspector.get_server_info(move |info| {
+            fetch.with(|t| t.server = Some(info.to_owned()));
+            fetch.finish(false);
+        });
+    }
+    fetch_list!(get_sink_info_list, sinks);
+    fetch_list!(get_source_info_list, sources);
+    fetch_list!(get_card_info_list, cards);
+    fetch_list!(get_module_info_list, modules);
+    fetch_list!(get_client_info_list, clients);
+    fetch_list!(get_sink_input_info_list, sink_inputs);
+    fetch_list!(get_source_output_info_list, source_outputs);
+}
+
+/// Keeps a [`Topology`] up to date, reporting changes to a callback.
+///
+/// See the [module level documentation](mod@self) for details.
+///
+/// A monitor is built upon [`Context::subscribe_events()`], which it calls, thus the two cannot be
+/// used together on the same context: creating a monitor takes over the subscription callback of
+/// the context, replacing that of any earlier call to [`Context::subscribe_events()`] or
+/// [`Context::set_subscribe_callback()`], and any later such call stops the monitor from receiving
+/// events. Once the monitor is dropped, events are no longer acted upon, though the callback
+/// remains set until replaced.
+pub struct Monitor {
+    /// State shared with the callbacks.
+    shared: Rc<MonitorShared>,
+}
+
+/// The user callback of a [`Monitor`].
+type MonitorCb = Box<dyn FnMut(&Topology, &Change)>;
+
+/// State shared between a [`Monitor`] and its callbacks.
+struct MonitorShared {
+    /// The topology being maintained.
+    topology: RefCell<Topology>,
+    /// Whether or not the initial snapshot has been fetched.
+    loaded: Cell<bool>,
+    /// The error with which fetching the initial snapshot failed, if it did.
+    error: Cell<Option<PAErr>>,
+    /// The user callback.
+    callback: RefCell<MonitorCb>,
+}
+
+impl Monitor {
+    /// The set of facilities monitored.
+    const INTEREST: InterestMaskSet = InterestMaskSet::SINK
+        .union(InterestMaskSet::SOURCE)
+        .union(InterestMaskSet::SINK_INPUT)
+        .union(InterestMaskSet::SOURCE_OUTPUT)
+        .union(InterestMaskSet::MODULE)
+        .union(InterestMaskSet::CLIENT)
+        .union(InterestMaskSet::SERVER)
+        .union(InterestMaskSet::CARD);
+
+    /// Creates a new monitor, subscribing to events and fetching the initial snapshot.
+    ///
+    /// The context must be ready. The callback is given the updated topology along with each
+    /// change, the first of which is [`Change::Loaded`], once the initial snapshot has been
+    /// fetched, or otherwise [`Change::LoadFailed`], should any of the queries fail, in which case
+    /// nothing further is reported.
+    ///
+    /// Panics if the underlying C functions return null pointers, i.e. invalid state.
+    pub fn new<F>(context: &mut Context, callback: F) -> Self
+        where F: FnMut(&Topology, &Change) + 'static
+    {
+        let shared = Rc::new(MonitorShared {
+            topology: RefCell::new(Topology::default()),
+            loaded: Cell::new(false),
+            error: Cell::new(None),
+            callback: RefCell::new(Box::new(callback)),
+        });
+
+        // Events arriving while the initial snapshot is being fetched are handled as normal, only
+        // not reported. Since replies and events arrive in order, the end result is consistent.
+        let weak = Rc::downgrade(&shared);
+        context.subscribe_events(Self::INTEREST, move |event| {
+            if let Some(shared) = weak.upgrade() {
+                shared.apply(event);
+            }
+        });
+
+        fetch_all(context, Rc::clone(&shared), |s| &s.topology, Box::new(|shared, error| {
+            match error {
+                None => {
+                    shared.loaded.set(true);
+                    shared.notify(&[Change::Loaded]);
+                },
+                Some(e) => {
+                    shared.error.set(Some(e));
+                    shared.report(&[Change::LoadFailed(e)]);
+                },
+            }
+        }));
+
+        Self { shared }
+    }
+
+    /// Gets the current topology.
+    ///
+    /// Panics if called from within the callback of the monitor; use the reference given to the
+    /// callback instead.
+    #[inline]
+    pub fn topology(&self) -> Ref<'_, Topology> {
+        self.shared.topology.borrow()
+    }
+
+    /// Checks whether or not the initial snapshot has been fetched.
+    ///
+    /// This remains `false` should fetching it fail.
+    #[inline]
+    pub fn is_loaded(&self) -> bool {
+        self.shared.loaded.get()
+    }
+
+    /// Gets the error with which fetching the initial snapshot failed, if it did.
+    #[inline]
+    pub fn error(&self) -> Option<PAErr> {
+        self.shared.error.get()
+    }
+}
+
+impl MonitorShared {
+    /// Reports changes to the user callback, if the initial snapshot has been fetched.
+    fn notify(&self, changes: &[Change]) {
+        if self.loaded.get() {
+            self.report(changes);
+        }
+    }
+
+    /// Reports changes to the user callback.
+    fn report(&self, changes: &[Change]) {
+        if changes.is_empty() {
+            return;
+        }
+        let topology = self.topology.borrow();
+        let mut callback = self.callback.borrow_mut();
+        for change in changes {
+            (callback)(&topology, change);
+        }
+    }
+
+    /// Acts upon a subscription event, holding the object concerned as fetched upon receipt.
+    fn apply(&self, event: Event) {
+        match event {
+            Event::SinkAdded(i) | Event::SinkChanged(i) => self.store(i.index, i),
+            Event::SourceAdded(i) | Event::SourceChanged(i) => self.store(i.index, i),
+            Event::CardAdded(i) | Event::CardChanged(i) => self.store(i.index, i),
+            Event::ModuleAdded(i) | Event::ModuleChanged(i) => self.store(i.index, i),
+            Event::ClientAdded(i) | Event::ClientChanged(i) => self.store(i.index, i),
+            Event::SinkInputAdded(i) | Event::SinkInputChanged(i) => self.store(i.index, i),
+            Event::SourceOutputAdded(i) | Event::SourceOutputChanged(i) => self.store(i.index, i),
+            Event::ServerChanged(i) => self.store_server(i),
+            Event::SinkRemoved(index, _) => self.remove(Facility::Sink, index),
+            Event::SourceRemoved(index, _) => self.remove(Facility::Source, index),
+            Event::CardRemoved(index, _) => self.remove(Facility::Card, index),
+            Event::ModuleRemoved(index, _) => self.remove(Facility::Module, index),
+            Event::ClientRemoved(index, _) => self.remove(Facility::Client, index),
+            Event::SinkInputRemoved(index, _) => self.remove(Facility::SinkInput, index),
+            Event::SourceOutputRemoved(index, _) => self.remove(Facility::SourceOutput, index),
+            Event::SampleAdded(_) | Event::SampleChanged(_) | Event::SampleRemoved(..) => {},
+        }
+    }
+
+    /// Stores a fetched object, reporting it as added, or reporting how it changed.
+    fn store<T: Object>(&self, index: u32, new: T) {
+        let mut changes = Vec::new();
+        {
+            let mut topology = self.topology.borrow_mut();
+            let map = T::map(&mut topology);
+            match map.insert(index, new) {
+                Some(old) => T::diff(&old, &map[&index], &mut |property| {
+                    changes.push(Change::Changed { facility: T::FACILITY, index, property });
+                }),
+                None => changes.push(Change::Added { facility: T::FACILITY, index }),
+            }
+        }
+        self.notify(&changes);
+    }
+
+    /// Stores fetched server information, reporting how it changed.
+    fn store_server(&self, new: ServerInfo<'static>) {
+        let mut changes = Vec::new();
+        {
+            let mut topology = self.topology.borrow_mut();
+            if let Some(old) = topology.server.as_ref() {
+                diff_server(old, &new, &mut |property| {
+                    changes.push(Change::Changed {
+                        facility: Facility::Server,
+                        index: crate::def::INVALID_INDEX,
+                        property,
+                    });
+                });
+            }
+            topology.server = Some(new);
+        }
+        self.notify(&changes);
+    }
+
+    /// Removes an object, reporting its removal if it was known.
+    fn remove(&self, facility: Facility, index: u32) {
+        fn remove_from<T: Object>(topology: &mut Topology, index: u32) -> bool {
+            T::map(topology).remove(&index).is_some()
+        }
+
+        let removed = {
+            let topology = &mut self.topology.borrow_mut();
+            match facility {
+                Facility::Sink => remove_from::<SinkInfo>(topology, index),
+                Facility::Source => remove_from::<SourceInfo>(topology, index),
+                Facility::Card => remove_from::<CardInfo>(topology, index),
+                Facility::Module => remove_from::<ModuleInfo>(topology, index),
+                Facility::Client => remove_from::<ClientInfo>(topology, index),
+                Facility::SinkInput => remove_from::<SinkInputInfo>(topology, index),
+                Facility::SourceOutput => remove_from::<SourceOutputInfo>(topology, index),
+                Facility::Server | Facility::SampleCache => false,
+            }
+        };
+        if removed {
+            self.notify(&[Change::Removed { facility, index }]);
+        }
+    }
+}
+
+/// Gets a description of a facility, for display purposes.
+fn facility_name(facility: Facility) -> &'static str {
+    match facility {
+        Facility::Sink => "sink",
+        Facility::Source => "source",
+        Facility::SinkInput => "sink input",
+        Facility::SourceOutput => "source output",
+        Facility::Module => "module",
+        Facility::Client => "client",
+        Facility::SampleCache => "sample",
+        Facility::Server => "server",
+        Facility::Card => "card",
+    }
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Change::Loaded => write!(f, "topology loaded"),
+            Change::LoadFailed(e) => write!(f, "topology failed to load: {}", e),
+            Change::Added { facility, index } => {
+                write!(f, "{} {} added", facility_name(*facility), index)
+            },
+            Change::Removed { facility, index } => {
+                write!(f, "{} {} removed", facility_name(*facility), index)
+            },
+            Change::Changed { facility: Facility::Server, property, .. } => {
+                write!(f, "server {}", property)
+            },
+            Change::Changed { facility, index, property } => {
+                write!(f, "{} {} {}", facility_name(*facility), index, property)
+            },
+        }
+    }
+}
+
+impl fmt::Display for PropertyChange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = |s: &Option<String>| s.clone().unwrap_or_else(|| String::from("(none)"));
+        match self {
+            PropertyChange::Name(ValueChange { old, new }) => {
+                write!(f, "name changed from {} to {}", name(old), name(new))
+            },
+            PropertyChange::Description(ValueChange { old, new }) => {
+                write!(f, "description changed from {} to {}", name(old), name(new))
+            },
+            PropertyChange::Volume(change) => {
+                write!(f, "volume changed from {} to {}", change.old, change.new)
+            },
+            PropertyChange::Mute(ValueChange { old, new }) => {
+                write!(f, "mute changed from {} to {}", old, new)
+            },
+            PropertyChange::SinkState(ValueChange { old, new }) => {
+                write!(f, "state changed from {:?} to {:?}", old, new)
+            },
+            PropertyChange::SourceState(ValueChange { old, new }) => {
+                write!(f, "state changed from {:?} to {:?}", old, new)
+            },
+            PropertyChange::ActivePort(ValueChange { old, new }) => {
+                write!(f, "active port changed from {} to {}", name(old), name(new))
+            },
+            PropertyChange::ActiveProfile(ValueChange { old, new }) => {
+                write!(f, "active profile changed from {} to {}", name(old), name(new))
+            },
+            PropertyChange::Sink(ValueChange { old, new }) => {
+                write!(f, "moved from sink {} to sink {}", old, new)
+            },
+            PropertyChange::Source(ValueChange { old, new }) => {
+                write!(f, "moved from source {} to source {}", old, new)
+            },
+            PropertyChange::Corked(ValueChange { old, new }) => {
+                write!(f, "corked changed from {} to {}", old, new)
+            },
+            PropertyChange::Argument(ValueChange { old, new }) => {
+                write!(f, "argument changed from {} to {}", name(old), name(new))
+            },
+            PropertyChange::DefaultSink(ValueChange { old, new }) => {
+                write!(f, "default sink changed from {} to {}", name(old), name(new))
+            },
+            PropertyChange::DefaultSource(ValueChange { old, new }) => {
+                write!(f, "default source changed from {} to {}", name(old), name(new))
+            },
+            PropertyChange::Properties => write!(f, "properties changed"),
+        }
+    }
+}
+
+/// Check the display form of changes.
+#[test]
+fn change_display() {
+    let change = Change::Changed {
+        facility: Facility::SinkInput,
+        index: 7,
+        property: PropertyChange::Sink(ValueChange { old: 1, new: 2 }),
+    };
+    assert_eq!(change.to_string(), "sink input 7 moved from sink 1 to sink 2");
+
+    let change = Change::Changed {
+        facility: Facility::Server,
+        index: crate::def::INVALID_INDEX,
+        property: PropertyChange::DefaultSink(ValueChange {
+            old: None,
+            new: Some(String::from("hdmi")),
+        }),
+    };
+    assert_eq!(change.to_string(), "server default sink changed from (none) to hdmi");
+
+    assert_eq!(Change::Removed { facility: Facility::Card, index: 0 }.to_string(),
+        "card 0 removed");
+}
+
+/// Gets the property changes reported between an old and new version of an object.
+#[cfg(test)]
+fn test_diff<T: Object>(old: &T, new: &T) -> Vec<PropertyChange> {
+    let mut changes = Vec::new();
+    T::diff(old, new, &mut |change| changes.push(change));
+    changes
+}
+
+/// Makes channel volumes for two channels, for the tests.
+#[cfg(test)]
+fn test_volumes(left: u32, right: u32) -> ChannelVolumes {
+    use crate::volume::Volume;
+
+    let mut volumes = ChannelVolumes::default();
+    volumes.set_len(2);
+    volumes.get_mut().copy_from_slice(&[Volume(left), Volume(right)]);
+    volumes
+}
+
+/// Makes a property list with a single string property, for the tests.
+#[cfg(test)]
+fn test_proplist(name: &str) -> Proplist {
+    let mut proplist = Proplist::new().unwrap();
+    proplist.set_str(crate::proplist::properties::DEVICE_DESCRIPTION, name).unwrap();
+    proplist
+}
+
+/// Makes a sink, for the tests.
+#[cfg(test)]
+fn test_sink(volume: ChannelVolumes, state: SinkState, port: &'static str, prop: &str)
+    -> SinkInfo<'static>
+{
+    use crate::context::introspect::SinkPortInfo;
+
+    SinkInfo {
+        name: Some(Cow::Borrowed("out")),
+        index: 3,
+        description: Some(Cow::Borrowed("Speakers")),
+        sample_spec: crate::sample::Spec {
+            format: crate::sample::Format::S16le,
+            rate: 44100,
+            channels: 2,
+        },
+        channel_map: crate::channelmap::Map::default(),
+        owner_module: Some(1),
+        volume,
+        mute: false,
+        monitor_source: 4,
+        monitor_source_name: None,
+        latency: crate::time::MicroSeconds(0),
+        driver: None,
+        flags: crate::def::SinkFlagSet::empty(),
+        proplist: test_proplist(prop),
+        configured_latency: crate::time::MicroSeconds(0),
+        base_volume: crate::volume::Volume::NORMAL,
+        state,
+        n_volume_steps: 0,
+        card: None,
+        ports: Vec::new(),
+        active_port: Some(Box::new(SinkPortInfo {
+            name: Some(Cow::Borrowed(port)),
+            description: None,
+            priority: 0,
+            available: crate::def::PortAvailable::Yes,
+            #[cfg(any(doc, feature = "pa_v14"))]
+            availability_group: None,
+            #[cfg(any(doc, feature = "pa_v14"))]
+            r#type: crate::def::DevicePortType::Speaker,
+        })),
+        formats: Vec::new(),
+    }
+}
+
+/// Makes a sink input, for the tests.
+#[cfg(test)]
+fn test_sink_input(sink: u32, volume: ChannelVolumes, corked: bool) -> SinkInputInfo<'static> {
+    SinkInputInfo {
+        index: 9,
+        name: Some(Cow::Borrowed("playback")),
+        owner_module: None,
+        client: Some(2),
+        sink,
+        sample_spec: crate::sample::Spec {
+            format: crate::sample::Format::S16le,
+            rate: 44100,
+            channels: 2,
+        },
+        channel_map: crate::channelmap::Map::default(),
+        volume,
+        buffer_usec: crate::time::MicroSeconds(0),
+        sink_usec: crate::time::MicroSeconds(0),
+        resample_method: None,
+        driver: None,
+        mute: false,
+        proplist: test_proplist("playback"),
+        corked,
+        has_volume: true,
+        volume_writable: true,
+        format: crate::format::Info::new().unwrap(),
+    }
+}
+
+/// Makes server information, for the tests.
+#[cfg(test)]
+fn test_server(default_sink: Option<&'static str>, default_source: Option<&'static str>)
+    -> ServerInfo<'static>
+{
+    ServerInfo {
+        user_name: None,
+        host_name: None,
+        server_version: None,
+        server_name: None,
+        sample_spec: crate::sample::Spec {
+            format: crate::sample::Format::S16le,
+            rate: 44100,
+            channels: 2,
+        },
+        default_sink_name: default_sink.map(Cow::Borrowed),
+        default_source_name: default_source.map(Cow::Borrowed),
+        cookie: 0,
+        channel_map: crate::channelmap::Map::default(),
+    }
+}
+
+/// Check the changes reported between two versions of a sink
+#[test]
+fn sink_diff() {
+    let (quiet, loud) = (test_volumes(0x8000, 0x8000), test_volumes(0x10000, 0x8000));
+    let old = test_sink(quiet, SinkState::Idle, "speaker", "Speakers");
+    let same = test_sink(quiet, SinkState::Idle, "speaker", "Speakers");
+    assert_eq!(test_diff(&old, &same), []);
+
+    let new = test_sink(loud, SinkState::Idle, "speaker", "Speakers");
+    assert_eq!(test_diff(&old, &new), [
+        PropertyChange::Volume(Box::new(ValueChange { old: quiet, new: loud })),
+    ]);
+
+    let mut new = test_sink(quiet, SinkState::Running, "headphones", "Headphones");
+    new.description = Some(Cow::Borrowed("Headphones"));
+    new.mute = true;
+    assert_eq!(test_diff(&old, &new), [
+        PropertyChange::Description(ValueChange {
+            old: Some(String::from("Speakers")),
+            new: Some(String::from("Headphones")),
+        }),
+        PropertyChange::Mute(ValueChange { old: false, new: true }),
+        PropertyChange::SinkState(ValueChange { old: SinkState::Idle, new: SinkState::Running }),
+        PropertyChange::ActivePort(ValueChange {
+            old: Some(String::from("speaker")),
+            new: Some(String::from("headphones")),
+        }),
+        PropertyChange::Properties,
+    ]);
+
+    let mut new = test_sink(quiet, SinkState::Idle, "speaker", "Speakers");
+    new.active_port = None;
+    assert_eq!(test_diff(&old, &new), [
+        PropertyChange::ActivePort(ValueChange { old: Some(String::from("speaker")), new: None }),
+    ]);
+}
+
+/// Check the changes reported between two versions of a sink input
+#[test]
+fn sink_input_diff() {
+    let (quiet, loud) = (test_volumes(0x8000, 0x8000), test_volumes(0x8000, 0x10000));
+    let old = test_sink_input(3, quiet, false);
+    assert_eq!(test_diff(&old, &test_sink_input(3, quiet, false)), []);
+
+    assert_eq!(test_diff(&old, &test_sink_input(5, loud, true)), [
+        PropertyChange::Sink(ValueChange { old: 3, new: 5 }),
+        PropertyChange::Volume(Box::new(ValueChange { old: quiet, new: loud })),
+        PropertyChange::Corked(ValueChange { old: false, new: true }),
+    ]);
+}
+
+/// Check the changes reported between two versions of the server information
+#[test]
+fn server_diff() {
+    let diff = |old: &ServerInfo, new: &ServerInfo| {
+        let mut changes = Vec::new();
+        diff_server(old, new, &mut |change| changes.push(change));
+        changes
+    };
+
+    let old = test_server(Some("speakers"), Some("mic"));
+    assert_eq!(diff(&old, &test_server(Some("speakers"), Some("mic"))), []);
+    assert_eq!(diff(&old, &test_server(Some("hdmi"), Some("mic"))), [
+        PropertyChange::DefaultSink(ValueChange {
+            old: Some(String::from("speakers")),
+            new: Some(String::from("hdmi")),
+        }),
+    ]);
+    assert_eq!(diff(&old, &test_server(Some("speakers"), None)), [
+        PropertyChange::DefaultSource(ValueChange { old: Some(String::from("mic")), new: None }),
+    ]);
+}
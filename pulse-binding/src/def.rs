@@ -16,6 +16,7 @@
 use std::os::raw::c_void;
 use bitflags::bitflags;
 use num_derive::{FromPrimitive, ToPrimitive};
+use crate::sample::Spec;
 use crate::time::{MicroSeconds, UnixTs};
 
 pub use capi::PA_INVALID_INDEX as INVALID_INDEX;
@@ -23,6 +24,69 @@ pub use capi::pa_device_type_t as Device;
 pub use capi::pa_port_available_t as PortAvailable;
 pub use capi::pa_device_port_type_t as DevicePortType;
 
+/// Serde support for [`PortAvailable`], which is defined in the `sys` crate.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(remote = "PortAvailable")]
+pub(crate) enum PortAvailableDef {
+    Unknown,
+    No,
+    Yes,
+}
+
+/// Serde support for [`DevicePortType`], which is defined in the `sys` crate.
+#[cfg(all(feature = "serde", any(doc, feature = "pa_v14")))]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(remote = "DevicePortType")]
+#[allow(clippy::upper_case_acronyms)]
+pub(crate) enum DevicePortTypeDef {
+    Unknown,
+    Aux,
+    Speaker,
+    Headphones,
+    Line,
+    Mic,
+    Headset,
+    Handset,
+    Earpiece,
+    SPDIF,
+    HDMI,
+    TV,
+    Radio,
+    Video,
+    USB,
+    Bluetooth,
+    Portable,
+    Handsfree,
+    Car,
+    HiFi,
+    Phone,
+    Network,
+    Analog,
+}
+
+/// Check serialization of the enums defined in the `sys` crate, rejecting unknown variants
+#[cfg(feature = "serde")]
+#[test]
+fn remote_enums_serde() {
+    use serde_json::{json, value::Serializer};
+
+    let value = PortAvailableDef::serialize(&PortAvailable::Yes, Serializer).unwrap();
+    assert_eq!(value, json!("Yes"));
+    assert_eq!(PortAvailableDef::deserialize(json!("No")).ok(), Some(PortAvailable::No));
+    assert!(PortAvailableDef::deserialize(json!("Maybe")).is_err());
+    assert!(PortAvailableDef::deserialize(json!(1)).is_err());
+
+    #[cfg(feature = "pa_v14")]
+    {
+        let value = DevicePortTypeDef::serialize(&DevicePortType::HDMI, Serializer).unwrap();
+        assert_eq!(value, json!("HDMI"));
+        let port_type = DevicePortTypeDef::deserialize(json!("Headset")).ok();
+        assert_eq!(port_type, Some(DevicePortType::Headset));
+        assert!(DevicePortTypeDef::deserialize(json!("Hdmi")).is_err());
+    }
+}
+
 /// A callback type for releasing allocations.
 pub type FreeCb = extern "C" fn(p: *mut c_void);
 
@@ -37,6 +101,7 @@ pub struct Retval(pub RetvalActual);
 /// Playback and record buffer metrics.
 #[repr(C)]
 #[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BufferAttr {
     /* NOTE: This struct must be directly usable by the C API, thus same attributes/layout/etc */
 
@@ -135,6 +200,147 @@ impl AsRef<BufferAttr> for capi::pa_buffer_attr {
     }
 }
 
+/// Buffering strategy, for [`BufferAttr::for_latency()`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum LatencyMode {
+    /// Playback, keeping the latency low, at the cost of more frequent requests for data. The
+    /// buffer is capped at twice the target latency, and playback starts as soon as a quarter of
+    /// it has been written.
+    LowLatency,
+    /// Playback, with the target latency, leaving everything else to the server.
+    Balanced,
+    /// Playback, requesting data in large chunks, allowing the client to wake up less often.
+    ///
+    /// Requests are for three quarters of the buffer, which is at odds with the server adjusting
+    /// the overall latency, so the buffer holds the target latency by itself, and
+    /// [`stream::FlagSet::ADJUST_LATENCY`] should not be used with it (nor is it by
+    /// [`StreamBuilder`]).
+    ///
+    /// [`stream::FlagSet::ADJUST_LATENCY`]: crate::stream::FlagSet::ADJUST_LATENCY
+    /// [`StreamBuilder`]: crate::stream::StreamBuilder
+    PowerSaving,
+    /// Recording, with data being delivered in fragments of the target latency.
+    Capture,
+}
+
+impl BufferAttr {
+    /// Buffer attributes leaving every value to the server.
+    pub const SERVER_DEFAULT: Self = Self {
+        maxlength: u32::MAX,
+        tlength: u32::MAX,
+        prebuf: u32::MAX,
+        minreq: u32::MAX,
+        fragsize: u32::MAX,
+    };
+
+    /// Calculates buffer attributes for the specified sample spec and target latency, according to
+    /// the specified mode, or `None` if the sample spec is invalid.
+    ///
+    /// Every size calculated is a whole number of frames, of at least one frame, with `tlength`
+    /// of at least two, such that `prebuf` and `minreq` never exceed it, nor reach it respectively.
+    /// Values not relevant to the mode are left to the server. For the latency to cover the
+    /// overall latency, including that of the device, connect the stream with
+    /// [`stream::FlagSet::ADJUST_LATENCY`], other than for [`LatencyMode::PowerSaving`].
+    ///
+    /// [`stream::FlagSet::ADJUST_LATENCY`]: crate::stream::FlagSet::ADJUST_LATENCY
+    pub fn for_latency(spec: &Spec, latency: MicroSeconds, mode: LatencyMode) -> Option<Self> {
+        match spec.is_valid() {
+            true => Some(Self::for_bytes(spec.frame_size(), spec.usec_to_bytes(latency), mode)),
+            false => None,
+        }
+    }
+
+    /// Calculates buffer attributes for the specified (non-zero) frame size and target latency in
+    /// bytes, according to the specified mode.
+    fn for_bytes(frame_size: usize, bytes: usize, mode: LatencyMode) -> Self {
+        // Whole frames, of at least one, within the range of valid values
+        let size = |n: usize| {
+            let n = std::cmp::min(n, (u32::MAX - 1) as usize);
+            std::cmp::max(n - n % frame_size, frame_size) as u32
+        };
+        let tlength = size(std::cmp::max(bytes, frame_size.saturating_mul(2)));
+        let mut attr = Self::SERVER_DEFAULT;
+        match mode {
+            LatencyMode::LowLatency => {
+                attr.maxlength = size((tlength as usize).saturating_mul(2));
+                attr.tlength = tlength;
+                attr.prebuf = size(tlength as usize / 4);
+                attr.minreq = size(tlength as usize / 4);
+            },
+            LatencyMode::Balanced => {
+                attr.tlength = tlength;
+            },
+            LatencyMode::PowerSaving => {
+                attr.tlength = tlength;
+                attr.minreq = size(tlength as usize / 4 * 3);
+            },
+            LatencyMode::Capture => {
+                attr.fragsize = size(bytes);
+            },
+        }
+        attr
+    }
+
+    /// Gets the playback latency corresponding to `tlength`, or `None` if left to the server.
+    pub fn playback_latency(&self, spec: &Spec) -> Option<MicroSeconds> {
+        match self.tlength {
+            u32::MAX => None,
+            n => Some(spec.bytes_to_usec(n as u64)),
+        }
+    }
+
+    /// Gets the recording latency corresponding to `fragsize`, or `None` if left to the server.
+    pub fn capture_latency(&self, spec: &Spec) -> Option<MicroSeconds> {
+        match self.fragsize {
+            u32::MAX => None,
+            n => Some(spec.bytes_to_usec(n as u64)),
+        }
+    }
+}
+
+/// Check the invariants of the buffer attributes of each latency mode
+#[test]
+fn bufferattr_latency_modes() {
+    let modes = [LatencyMode::LowLatency, LatencyMode::Balanced, LatencyMode::PowerSaving,
+        LatencyMode::Capture];
+    for mode in modes {
+        for frame_size in [1, 2, 3, 4, 6, 8, 24, 1020] {
+            for bytes in [0, 1, 5, 1000, 176_400, u32::MAX as usize, usize::MAX] {
+                let attr = BufferAttr::for_bytes(frame_size, bytes, mode);
+                let fields = [attr.maxlength, attr.tlength, attr.prebuf, attr.minreq,
+                    attr.fragsize];
+                for n in fields.into_iter().filter(|&n| n != u32::MAX) {
+                    assert!(n as usize >= frame_size && n as usize % frame_size == 0);
+                }
+
+                let set = fields.map(|n| n != u32::MAX);
+                match mode {
+                    LatencyMode::LowLatency => assert_eq!(set, [true, true, true, true, false]),
+                    LatencyMode::Balanced => assert_eq!(set, [false, true, false, false, false]),
+                    LatencyMode::PowerSaving => assert_eq!(set, [false, true, false, true, false]),
+                    LatencyMode::Capture => assert_eq!(set, [false, false, false, false, true]),
+                }
+                if set[0] {
+                    assert!(attr.maxlength >= attr.tlength);
+                }
+                if set[2] {
+                    assert!(attr.prebuf <= attr.tlength);
+                }
+                if set[3] {
+                    assert!(attr.minreq < attr.tlength);
+                }
+            }
+        }
+    }
+
+    // Sizes exactly as intended, where not constrained
+    let attr = BufferAttr::for_bytes(4, 17_640, LatencyMode::LowLatency);
+    assert_eq!((attr.maxlength, attr.tlength, attr.prebuf, attr.minreq),
+        (35_280, 17_640, 4_408, 4_408));
+    let attr = BufferAttr::for_bytes(4, 17_640, LatencyMode::PowerSaving);
+    assert_eq!((attr.tlength, attr.minreq), (17_640, 13_228));
+}
+
 /// A structure for all kinds of timing information of a stream.
 ///
 /// See [`Stream::update_timing_info()`] and [`Stream::get_timing_info()`].
@@ -300,6 +506,7 @@ impl AsRef<capi::pa_spawn_api> for SpawnApi {
 bitflags! {
     /// Set of sink flags.
     #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     #[repr(transparent)]
     pub struct SinkFlagSet: u32 {
         /// Flag to pass when no specific options are needed.
@@ -348,6 +555,7 @@ bitflags! {
 #[repr(C)]
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 #[derive(FromPrimitive, ToPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SinkState {
     /* NOTE: This enum’s variants and variant values **must** remain identical to the `sys` crate
        (C API) equivalent */
@@ -409,6 +617,7 @@ impl SinkState {
 bitflags! {
     /// Set of source flags.
     #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     #[repr(transparent)]
     pub struct SourceFlagSet: u32 {
         /// Flag to pass when no specific options are needed.
@@ -453,6 +662,7 @@ bitflags! {
 #[repr(C)]
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 #[derive(FromPrimitive, ToPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SourceState {
     /* NOTE: This enum’s variants and variant values **must** remain identical to the `sys` crate
        (C API) equivalent */
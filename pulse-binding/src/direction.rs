@@ -20,6 +20,7 @@ use bitflags::bitflags;
 bitflags! {
     /// Flag set.
     #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     #[repr(transparent)]
     pub struct FlagSet: i32 {
         /// Output flag.
@@ -0,0 +1,314 @@
+// Copyright 2026 Lyndon Brown
+//
+// This file is part of the PulseAudio Rust language binding.
+//
+// Licensed under the MIT license or the Apache license (version 2.0), at your option. You may not
+// copy, modify, or distribute this file except in compliance with said license. You can find copies
+// of these licenses either in the LICENSE-MIT and LICENSE-APACHE files, or alternatively at
+// <http://opensource.org/licenses/MIT> and <http://www.apache.org/licenses/LICENSE-2.0>
+// respectively.
+
+//! Sample format conversion.
+//!
+//! # Overview
+//!
+//! A [`Converter`] converts interleaved data from one sample format to another, between any pair
+//! of formats. Conversion between the little and big endian forms of a format is a simple byte
+//! swap. Otherwise, as the server does:
+//!
+//! * Integer samples, including A-law and μ-law ones decoded to 16-bit linear form, are scaled by
+//!   powers of two, with the least significant bits simply dropped when reducing bit depth.
+//! * Floating-point samples are scaled such that `-1.0` to `1.0` covers the range of integer
+//!   formats, rounding to the nearest integer, and clamping values out of range.
+//!
+//! Optionally, triangular probability density function (TPDF) dither may be applied when reducing
+//! bit depth, decorrelating the quantization error from the signal, at the cost of a little noise.
+//! The noise spans plus or minus one least significant bit of the destination format, with the
+//! result being rounded to the nearest integer. A-law and μ-law formats are treated as having 13
+//! and 14 bits, respectively, and floating-point formats as having 25 bits.
+//!
+//! For one-off conversions without dither, [`Format::convert()`] may be used.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use libpulse_binding::dsp::convert::Converter;
+//! use libpulse_binding::sample::Format;
+//!
+//! // Captured in whatever format the server negotiated
+//! let mut converter = Converter::new(&spec, Format::S16NE).unwrap().dither(true);
+//! let converted = converter.convert_to_vec(&data).unwrap();
+//! ```
+//!
+//! [`Format::convert()`]: crate::sample::Format::convert
+
+use super::g711;
+use crate::error::{Code, PAErr};
+use crate::sample::{Format, Spec};
+
+/// Converts data from one sample format to another.
+///
+/// See the [module level documentation](mod@self) for details.
+#[derive(Debug, Clone)]
+pub struct Converter {
+    /// Spec of the source data.
+    from: Spec,
+    /// Format of the destination data.
+    to: Format,
+    /// Dither, if enabled.
+    dither: Option<Dither>,
+}
+
+impl Converter {
+    /// Creates a converter of data of the specified spec to the specified format.
+    ///
+    /// Returns the [`PAErr`] form of the [`Code::Invalid`] error if the spec or format is invalid.
+    pub fn new(from: &Spec, to: Format) -> Result<Self, PAErr> {
+        super::check_frames(0, from)?;
+        super::sample_size(to).ok_or(PAErr::from(Code::Invalid))?;
+        Ok(Self { from: *from, to, dither: None })
+    }
+
+    /// Enables or disables TPDF dither, applied when reducing bit depth.
+    pub fn dither(mut self, enabled: bool) -> Self {
+        self.dither = match enabled {
+            true => Some(Dither::new(Dither::DEFAULT_SEED)),
+            false => None,
+        };
+        self
+    }
+
+    /// Gets the spec of the destination data.
+    #[inline]
+    pub fn output_spec(&self) -> Spec {
+        Spec { format: self.to, ..self.from }
+    }
+
+    /// Gets the length, in bytes, of the destination data for source data of the specified length.
+    pub fn output_len(&self, input_len: usize) -> usize {
+        // Cannot fail, both formats having been checked
+        let from = super::sample_size(self.from.format).unwrap_or(1);
+        let to = super::sample_size(self.to).unwrap_or(1);
+        input_len / from * to
+    }
+
+    /// Converts data, returning the number of frames converted.
+    ///
+    /// The output must be exactly the length given by [`output_len()`](Self::output_len). Returns
+    /// the [`PAErr`] form of the [`Code::Invalid`] error otherwise, or if the input is not a whole
+    /// number of frames.
+    pub fn convert(&mut self, input: &[u8], output: &mut [u8]) -> Result<usize, PAErr> {
+        let channels = super::check_frames(input.len(), &self.from)?;
+        let samples = convert(self.from.format, self.to, input, output, self.dither.as_mut())?;
+        Ok(samples / channels)
+    }
+
+    /// Converts data into a new buffer.
+    ///
+    /// Returns the [`PAErr`] form of the [`Code::Invalid`] error if the input is not a whole number
+    /// of frames.
+    pub fn convert_to_vec(&mut self, input: &[u8]) -> Result<Vec<u8>, PAErr> {
+        let mut output = vec![0; self.output_len(input.len())];
+        self.convert(input, &mut output)?;
+        Ok(output)
+    }
+}
+
+/// A source of TPDF dither noise.
+#[derive(Debug, Clone)]
+pub(crate) struct Dither {
+    /// State of the xorshift random number generator.
+    state: u32,
+}
+
+impl Dither {
+    /// The seed used, such that conversion is repeatable.
+    const DEFAULT_SEED: u32 = 0x2545_f491;
+
+    /// Creates a source of dither noise.
+    fn new(seed: u32) -> Self {
+        Self { state: seed | 1 }
+    }
+
+    /// Gets a random number uniformly distributed between zero and one.
+    fn uniform(&mut self) -> f64 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 17;
+        self.state ^= self.state << 5;
+        self.state as f64 / (u32::MAX as f64 + 1.0)
+    }
+
+    /// Gets noise with a triangular distribution between minus one and one.
+    #[inline]
+    fn noise(&mut self) -> f64 {
+        self.uniform() - self.uniform()
+    }
+}
+
+/// Gets the number of significant bits of a format.
+fn bits(format: Format) -> u32 {
+    match format {
+        Format::U8 => 8,
+        Format::ALaw => 13,
+        Format::ULaw => 14,
+        Format::S16le | Format::S16be => 16,
+        Format::S24le | Format::S24be | Format::S24_32le | Format::S24_32be => 24,
+        Format::F32le | Format::F32be => 25,
+        Format::S32le | Format::S32be | Format::Invalid => 32,
+    }
+}
+
+/// Checks whether or not two formats differ only in endianness.
+fn is_swap(from: Format, to: Format) -> bool {
+    matches!((from, to),
+        (Format::S16le, Format::S16be) | (Format::S16be, Format::S16le)
+        | (Format::S24le, Format::S24be) | (Format::S24be, Format::S24le)
+        | (Format::S24_32le, Format::S24_32be) | (Format::S24_32be, Format::S24_32le)
+        | (Format::S32le, Format::S32be) | (Format::S32be, Format::S32le)
+        | (Format::F32le, Format::F32be) | (Format::F32be, Format::F32le))
+}
+
+/// Decodes a sample to a value nominally between minus one and one.
+fn decode(format: Format, s: &[u8]) -> f64 {
+    /// Scale of a 32-bit integer, left-aligned from the significant bits.
+    const SCALE: f64 = 2147483648.0;
+    match format {
+        Format::U8 => (s[0] as f64 - 128.0) / 128.0,
+        Format::ALaw => g711::alaw_to_linear16(s[0]) as f64 / 32768.0,
+        Format::ULaw => g711::ulaw_to_linear16(s[0]) as f64 / 32768.0,
+        Format::S16le => i16::from_le_bytes([s[0], s[1]]) as f64 / 32768.0,
+        Format::S16be => i16::from_be_bytes([s[0], s[1]]) as f64 / 32768.0,
+        Format::S24le => i32::from_le_bytes([0, s[0], s[1], s[2]]) as f64 / SCALE,
+        Format::S24be => i32::from_be_bytes([s[0], s[1], s[2], 0]) as f64 / SCALE,
+        Format::S24_32le => ((u32::from_le_bytes([s[0], s[1], s[2], s[3]]) << 8) as i32) as f64
+            / SCALE,
+        Format::S24_32be => ((u32::from_be_bytes([s[0], s[1], s[2], s[3]]) << 8) as i32) as f64
+            / SCALE,
+        Format::S32le => i32::from_le_bytes([s[0], s[1], s[2], s[3]]) as f64 / SCALE,
+        Format::S32be => i32::from_be_bytes([s[0], s[1], s[2], s[3]]) as f64 / SCALE,
+        Format::F32le => f32::from_le_bytes([s[0], s[1], s[2], s[3]]) as f64,
+        Format::F32be => f32::from_be_bytes([s[0], s[1], s[2], s[3]]) as f64,
+        Format::Invalid => 0.0,
+    }
+}
+
+/// Encodes an integer sample, of the significant bits of the format.
+fn encode_int(format: Format, q: i32, d: &mut [u8]) {
+    match format {
+        Format::U8 => d[0] = (q + 128) as u8,
+        Format::ALaw => d[0] = g711::linear13_to_alaw(q as i16),
+        Format::ULaw => d[0] = g711::linear14_to_ulaw(q as i16),
+        Format::S16le => d.copy_from_slice(&(q as i16).to_le_bytes()),
+        Format::S16be => d.copy_from_slice(&(q as i16).to_be_bytes()),
+        Format::S24le => d.copy_from_slice(&q.to_le_bytes()[..3]),
+        Format::S24be => d.copy_from_slice(&q.to_be_bytes()[1..]),
+        Format::S24_32le | Format::S32le => d.copy_from_slice(&q.to_le_bytes()),
+        Format::S24_32be | Format::S32be => d.copy_from_slice(&q.to_be_bytes()),
+        Format::F32le | Format::F32be | Format::Invalid => {},
+    }
+}
+
+/// Converts samples from one format to another, returning the number of samples converted.
+///
+/// The output must be exactly as long as needed for the samples of the input, and the input a
+/// whole number of samples.
+pub(crate) fn convert(from: Format, to: Format, input: &[u8], output: &mut [u8],
+    mut dither: Option<&mut Dither>) -> Result<usize, PAErr>
+{
+    let in_size = super::sample_size(from).ok_or(PAErr::from(Code::Invalid))?;
+    let out_size = super::sample_size(to).ok_or(PAErr::from(Code::Invalid))?;
+    let samples = input.len() / in_size;
+    if input.len() % in_size != 0 || output.len() != samples * out_size {
+        return Err(PAErr::from(Code::Invalid));
+    }
+
+    if from == to {
+        output.copy_from_slice(input);
+        return Ok(samples);
+    }
+    let pairs = input.chunks_exact(in_size).zip(output.chunks_exact_mut(out_size));
+    if is_swap(from, to) {
+        for (s, d) in pairs {
+            d.iter_mut().zip(s.iter().rev()).for_each(|(d, s)| *d = *s);
+        }
+        return Ok(samples);
+    }
+
+    let float = matches!(from, Format::F32le | Format::F32be);
+    let (to_bits, reduce) = (bits(to), bits(to) < bits(from));
+    // Bounds of the significant bits of the destination format
+    let max = ((1i64 << (to_bits - 1)) - 1) as f64;
+    let scale = max + 1.0;
+    for (s, d) in pairs {
+        let x = decode(from, s);
+        match to {
+            Format::F32le => d.copy_from_slice(&(x as f32).to_le_bytes()),
+            Format::F32be => d.copy_from_slice(&(x as f32).to_be_bytes()),
+            _ => {
+                let v = x * scale;
+                let v = match dither.as_deref_mut() {
+                    Some(dither) if reduce => (v + dither.noise()).round(),
+                    _ if float => v.round(),
+                    _ => v.floor(),
+                };
+                encode_int(to, v.clamp(-scale, max) as i32, d);
+            },
+        }
+    }
+    Ok(samples)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Check integer conversions, including endian swaps and companding
+    #[test]
+    fn integer() {
+        let spec = Spec { format: Format::S16le, channels: 2, rate: 44100 };
+        let input = [0x34, 0x12, 0x00, 0x80];
+        let convert = |to| Converter::new(&spec, to).unwrap().convert_to_vec(&input).unwrap();
+
+        assert_eq!(convert(Format::S16be), [0x12, 0x34, 0x80, 0x00]);
+        assert_eq!(convert(Format::U8), [0x92, 0x00]);
+        assert_eq!(convert(Format::S24be), [0x12, 0x34, 0x00, 0x80, 0x00, 0x00]);
+        assert_eq!(convert(Format::S24_32le), [0x00, 0x34, 0x12, 0x00, 0x00, 0x00, 0x80, 0xff]);
+        assert_eq!(convert(Format::ALaw)[0], g711::linear13_to_alaw(0x1234 >> 3));
+        assert_eq!(convert(Format::ULaw)[1], g711::linear14_to_ulaw(-0x8000 >> 2));
+
+        let spec = Spec { format: Format::S32be, channels: 1, rate: 8000 };
+        let mut converter = Converter::new(&spec, Format::S16le).unwrap();
+        let mut output = [0; 2];
+        assert_eq!(converter.convert(&[0x12, 0x34, 0xff, 0xff], &mut output), Ok(1));
+        assert_eq!(output, [0x34, 0x12]);
+        assert!(converter.convert(&[0; 3], &mut output).is_err());
+    }
+
+    /// Check floating-point conversions, with rounding and clamping
+    #[test]
+    fn float() {
+        let spec = Spec { format: Format::F32le, channels: 3, rate: 48000 };
+        let input = [0.5f32, -1.0, 2.0].iter().flat_map(|f| f.to_le_bytes()).collect::<Vec<_>>();
+        let output = Converter::new(&spec, Format::S16be).unwrap().convert_to_vec(&input).unwrap();
+        assert_eq!(output, [0x40, 0x00, 0x80, 0x00, 0x7f, 0xff]);
+
+        let spec = Spec { format: Format::U8, channels: 1, rate: 8000 };
+        let output = Converter::new(&spec, Format::F32be).unwrap().convert_to_vec(&[0x40]).unwrap();
+        assert_eq!(output, (-0.5f32).to_be_bytes());
+    }
+
+    /// Check that dither stays within one least significant bit, only when reducing bit depth
+    #[test]
+    fn dither() {
+        let spec = Spec { format: Format::S16le, channels: 1, rate: 44100 };
+        let input = [0x80, 0x00].repeat(256);
+        let mut converter = Converter::new(&spec, Format::U8).unwrap().dither(true);
+        let output = converter.convert_to_vec(&input).unwrap();
+        assert!(output.iter().all(|s| (0x80..=0x81).contains(s)));
+        assert!(output.contains(&0x80) && output.contains(&0x81));
+
+        let mut converter = Converter::new(&spec, Format::S32le).unwrap().dither(true);
+        let output = converter.convert_to_vec(&input).unwrap();
+        assert!(output.chunks(4).all(|s| s == [0x00, 0x00, 0x80, 0x00]));
+    }
+}
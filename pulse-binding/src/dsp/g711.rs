@@ -0,0 +1,113 @@
+// Copyright 2026 Lyndon Brown
+//
+// This file is part of the PulseAudio Rust language binding.
+//
+// Licensed under the MIT license or the Apache license (version 2.0), at your option. You may not
+// copy, modify, or distribute this file except in compliance with said license. You can find copies
+// of these licenses either in the LICENSE-MIT and LICENSE-APACHE files, or alternatively at
+// <http://opensource.org/licenses/MIT> and <http://www.apache.org/licenses/LICENSE-2.0>
+// respectively.
+
+//! G.711 A-law and μ-law companding, as done by the server.
+//!
+//! These follow the widely used reference implementation from Sun Microsystems, which decodes to
+//! 16-bit linear samples, and encodes from 13-bit (A-law) and 14-bit (μ-law) linear samples.
+
+/// Mask of the quantization field.
+const QUANT_MASK: u8 = 0x0f;
+/// Mask of the segment field.
+const SEG_MASK: u8 = 0x70;
+/// Shift of the segment field.
+const SEG_SHIFT: u32 = 4;
+/// Sign bit.
+const SIGN_BIT: u8 = 0x80;
+/// Bias for linear code, μ-law.
+const BIAS: i16 = 0x84;
+/// Maximum magnitude of 14-bit linear input, μ-law.
+const CLIP: i16 = 8159;
+
+/// Segment end points, A-law.
+const SEG_AEND: [i16; 8] = [0x1f, 0x3f, 0x7f, 0xff, 0x1ff, 0x3ff, 0x7ff, 0xfff];
+/// Segment end points, μ-law.
+const SEG_UEND: [i16; 8] = [0x3f, 0x7f, 0xff, 0x1ff, 0x3ff, 0x7ff, 0xfff, 0x1fff];
+
+/// Finds the segment of a value, being the number of end points it exceeds.
+#[inline]
+fn segment(val: i16, ends: &[i16; 8]) -> u8 {
+    ends.iter().position(|&end| val <= end).unwrap_or(ends.len()) as u8
+}
+
+/// Decodes an A-law sample to a 16-bit linear sample.
+pub(crate) fn alaw_to_linear16(a_val: u8) -> i16 {
+    let a_val = a_val ^ 0x55;
+    let mut t = ((a_val & QUANT_MASK) as i16) << 4;
+    let seg = (a_val & SEG_MASK) >> SEG_SHIFT;
+    match seg {
+        0 => t += 8,
+        1 => t += 0x108,
+        _ => t = (t + 0x108) << (seg - 1),
+    }
+    match a_val & SIGN_BIT {
+        0 => -t,
+        _ => t,
+    }
+}
+
+/// Encodes a 13-bit linear sample to A-law.
+pub(crate) fn linear13_to_alaw(pcm_val: i16) -> u8 {
+    let (mask, pcm_val) = match pcm_val >= 0 {
+        true => (0xd5, pcm_val),
+        false => (0x55, -pcm_val - 1),
+    };
+    let seg = segment(pcm_val, &SEG_AEND);
+    if seg >= 8 {
+        return 0x7f ^ mask;
+    }
+    let quant = match seg {
+        0 | 1 => (pcm_val >> 1) as u8,
+        _ => (pcm_val >> seg) as u8,
+    };
+    ((seg << SEG_SHIFT) | (quant & QUANT_MASK)) ^ mask
+}
+
+/// Decodes a μ-law sample to a 16-bit linear sample.
+pub(crate) fn ulaw_to_linear16(u_val: u8) -> i16 {
+    let u_val = !u_val;
+    let t = ((((u_val & QUANT_MASK) as i16) << 3) + BIAS) << ((u_val & SEG_MASK) >> SEG_SHIFT);
+    match u_val & SIGN_BIT {
+        0 => t - BIAS,
+        _ => BIAS - t,
+    }
+}
+
+/// Encodes a 14-bit linear sample to μ-law.
+pub(crate) fn linear14_to_ulaw(pcm_val: i16) -> u8 {
+    let (mask, pcm_val) = match pcm_val < 0 {
+        true => (0x7f, -pcm_val),
+        false => (0xff, pcm_val),
+    };
+    let pcm_val = std::cmp::min(pcm_val, CLIP) + (BIAS >> 2);
+    let seg = segment(pcm_val, &SEG_UEND);
+    if seg >= 8 {
+        return 0x7f ^ mask;
+    }
+    ((seg << 4) | ((pcm_val >> (seg + 1)) as u8 & QUANT_MASK)) ^ mask
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Check that encoding a decoded sample gives back an equivalent sample
+    #[test]
+    fn round_trip() {
+        for code in 0..=255u8 {
+            let linear = alaw_to_linear16(code);
+            assert_eq!(linear13_to_alaw(linear >> 3), code);
+            let linear = ulaw_to_linear16(code);
+            assert_eq!(ulaw_to_linear16(linear14_to_ulaw(linear >> 2)), linear);
+        }
+        assert_eq!(linear13_to_alaw(0), 0xd5);
+        assert_eq!(linear14_to_ulaw(0), 0xff);
+    }
+}
@@ -0,0 +1,56 @@
+// Copyright 2026 Lyndon Brown
+//
+// This file is part of the PulseAudio Rust language binding.
+//
+// Licensed under the MIT license or the Apache license (version 2.0), at your option. You may not
+// copy, modify, or distribute this file except in compliance with said license. You can find copies
+// of these licenses either in the LICENSE-MIT and LICENSE-APACHE files, or alternatively at
+// <http://opensource.org/licenses/MIT> and <http://www.apache.org/licenses/LICENSE-2.0>
+// respectively.
+
+//! Client-side audio processing.
+//!
+//! # Overview
+//!
+//! The server applies volumes, converts between formats and channel maps, and resamples, as needed
+//! when mixing streams. These modules offer the same processing, implemented purely in Rust, for
+//! when it needs to be done on the client side, such as when mixing several sources into a single
+//! stream, and where the results are expected to match those of the server.
+//!
+//! All of these work upon interleaved data, of any of the sample formats of a [`Spec`].
+//!
+//! [`Spec`]: crate::sample::Spec
+
+use crate::error::{Code, PAErr};
+use crate::sample::{Format, Spec};
+
+mod g711;
+pub mod convert;
+pub mod remix;
+pub mod resample;
+pub mod volume;
+
+/// Gets the size of a sample of the specified format, or `None` if invalid.
+///
+/// This avoids calling into the PulseAudio client library for every buffer processed.
+pub(crate) fn sample_size(format: Format) -> Option<usize> {
+    match format {
+        Format::U8 | Format::ALaw | Format::ULaw => Some(1),
+        Format::S16le | Format::S16be => Some(2),
+        Format::S24le | Format::S24be => Some(3),
+        Format::F32le | Format::F32be | Format::S32le | Format::S32be | Format::S24_32le
+            | Format::S24_32be => Some(4),
+        Format::Invalid => None,
+    }
+}
+
+/// Checks that data of the specified length forms a whole number of frames of a valid spec,
+/// returning the number of channels.
+pub(crate) fn check_frames(len: usize, spec: &Spec) -> Result<usize, PAErr> {
+    let size = sample_size(spec.format).ok_or(PAErr::from(Code::Invalid))?;
+    let channels = spec.channels as usize;
+    match spec.channels {
+        1..=Spec::CHANNELS_MAX if spec.rate != 0 && len % (size * channels) == 0 => Ok(channels),
+        _ => Err(PAErr::from(Code::Invalid)),
+    }
+}
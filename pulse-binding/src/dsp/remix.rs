@@ -0,0 +1,485 @@
+// Copyright 2026 Lyndon Brown
+//
+// This file is part of the PulseAudio Rust language binding.
+//
+// Licensed under the MIT license or the Apache license (version 2.0), at your option. You may not
+// copy, modify, or distribute this file except in compliance with said license. You can find copies
+// of these licenses either in the LICENSE-MIT and LICENSE-APACHE files, or alternatively at
+// <http://opensource.org/licenses/MIT> and <http://www.apache.org/licenses/LICENSE-2.0>
+// respectively.
+
+//! Channel remapping, upmixing and downmixing.
+//!
+//! # Overview
+//!
+//! A [`Remixer`] converts frames from one channel map to another, through a mixing matrix giving
+//! the contribution of each source channel to each destination channel. The matrix is built
+//! following the rules the server uses, as chosen with [`Mode`]:
+//!
+//! 1. Channels of the same position are connected. A mono source channel is copied into all
+//!    destination channels, and a mono destination channel takes the average of all source
+//!    channels. Rear and side channels stand in for each other where only one of them is present,
+//!    such as between the “5.1” and “5.1 (side)” layouts.
+//! 2. Unconnected left, right and center destination channels take the average of the source
+//!    channels on the same side, with a center channel being synthesized from the left and right
+//!    ones if necessary, such that stereo is copied to the rear channels when upmixing.
+//! 3. An unconnected LFE destination channel takes the average of all source channels, only if
+//!    [`Options::produce_lfe`] is set.
+//! 4. Unconnected left and right source channels are mixed into the destination channels of the
+//!    same side at a gain of 1/9, and center channels into the center destination channels, or
+//!    failing that, into the left and right ones, preferring those of the same front, rear or side
+//!    placement, at a gain of 0.5. An LFE source channel is mixed into all destination channels at
+//!    a gain of 0.375, only if [`Options::consume_lfe`] is set.
+//! 5. Each row of the matrix is normalized, such that its sum does not exceed one, avoiding
+//!    clipping.
+//!
+//! No filtering of LFE channels is done.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use libpulse_binding::channelmap::{Map, MapDef};
+//! use libpulse_binding::dsp::remix::{Options, Remixer};
+//!
+//! let mut stereo = Map::default();
+//! stereo.init_stereo();
+//! let mut surround = Map::default();
+//! surround.init_auto(6, MapDef::Default).unwrap();
+//!
+//! let remixer = Remixer::new(&stereo, &surround, &Options::default()).unwrap();
+//! let mut output = vec![0.0; input.len() / 2 * 6];
+//! remixer.remix_f32(&input, &mut output).unwrap();
+//! ```
+
+use crate::channelmap::{Map, Position};
+use crate::error::{Code, PAErr};
+
+/// How channels are to be mixed.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Mode {
+    /// Full upmixing and downmixing, as described in the
+    /// [module level documentation](mod@self).
+    Remix,
+    /// Only channels of the same position are connected, with other destination channels left
+    /// silent and other source channels dropped. No source channel is dropped if the destination
+    /// map [is a superset of](Map::is_superset_of) the source map.
+    CommonOnly,
+    /// Channels are connected by index, regardless of position.
+    ByIndex,
+}
+
+/// Options for building a mixing matrix.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Options {
+    /// How channels are to be mixed.
+    pub mode: Mode,
+    /// Whether or not to synthesize an LFE destination channel from all source channels, when
+    /// upmixing.
+    pub produce_lfe: bool,
+    /// Whether or not to mix an LFE source channel into the other destination channels, when
+    /// downmixing.
+    pub consume_lfe: bool,
+}
+
+impl Default for Options {
+    /// Full remixing, neither producing nor consuming LFE channels, as the server does by default.
+    fn default() -> Self {
+        Self { mode: Mode::Remix, produce_lfe: false, consume_lfe: false }
+    }
+}
+
+/// Front, rear or side placement of a channel.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Placement {
+    /// At the front.
+    Front,
+    /// At the rear.
+    Rear,
+    /// At the side.
+    Side,
+    /// Anywhere else.
+    Other,
+}
+
+/// Checks whether or not a position is on the left.
+fn on_left(p: Position) -> bool {
+    matches!(p, Position::FrontLeft | Position::RearLeft | Position::FrontLeftOfCenter
+        | Position::SideLeft | Position::TopFrontLeft | Position::TopRearLeft)
+}
+
+/// Checks whether or not a position is on the right.
+fn on_right(p: Position) -> bool {
+    matches!(p, Position::FrontRight | Position::RearRight | Position::FrontRightOfCenter
+        | Position::SideRight | Position::TopFrontRight | Position::TopRearRight)
+}
+
+/// Checks whether or not a position is at the center.
+fn on_center(p: Position) -> bool {
+    matches!(p, Position::FrontCenter | Position::RearCenter | Position::TopCenter
+        | Position::TopFrontCenter | Position::TopRearCenter)
+}
+
+/// Gets the front, rear or side placement of a position.
+fn placement(p: Position) -> Placement {
+    match p {
+        Position::FrontLeft | Position::FrontRight | Position::FrontCenter
+            | Position::FrontLeftOfCenter | Position::FrontRightOfCenter | Position::TopFrontLeft
+            | Position::TopFrontRight | Position::TopFrontCenter => Placement::Front,
+        Position::RearLeft | Position::RearRight | Position::RearCenter | Position::TopRearLeft
+            | Position::TopRearRight | Position::TopRearCenter => Placement::Rear,
+        Position::SideLeft | Position::SideRight | Position::TopCenter => Placement::Side,
+        _ => Placement::Other,
+    }
+}
+
+/// Gets the position standing in for a rear or side position, where absent.
+fn rear_side_counterpart(p: Position) -> Option<Position> {
+    match p {
+        Position::RearLeft => Some(Position::SideLeft),
+        Position::RearRight => Some(Position::SideRight),
+        Position::SideLeft => Some(Position::RearLeft),
+        Position::SideRight => Some(Position::RearRight),
+        _ => None,
+    }
+}
+
+/// Converts frames from one channel map to another.
+///
+/// See the [module level documentation](mod@self) for details.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Remixer {
+    /// The number of source channels.
+    inputs: usize,
+    /// The number of destination channels.
+    outputs: usize,
+    /// The mixing matrix, a row of source channel coefficients for each destination channel.
+    matrix: Vec<f32>,
+    /// The matrix in 16.16 fixed-point form, for integer samples.
+    fixed: Vec<i32>,
+}
+
+impl Remixer {
+    /// Creates a remixer from one channel map to another.
+    ///
+    /// Returns the [`PAErr`] form of the [`Code::Invalid`] error if either map has no channels,
+    /// too many, or an invalid position.
+    pub fn new(from: &Map, to: &Map, options: &Options) -> Result<Self, PAErr> {
+        let valid = |map: &Map| {
+            (1..=Map::CHANNELS_MAX).contains(&map.len())
+                && map.get().iter().all(|p| *p != Position::Invalid)
+        };
+        if !valid(from) || !valid(to) {
+            return Err(PAErr::from(Code::Invalid));
+        }
+        let matrix = match options.mode {
+            Mode::ByIndex => {
+                Self::build(from, to, |m, ic, oc| if ic == oc { m[oc][ic] = 1.0 })
+            },
+            Mode::CommonOnly => {
+                Self::build(from, to, |m, ic, oc| {
+                    if from.get()[ic] == to.get()[oc] {
+                        m[oc][ic] = 1.0;
+                    }
+                })
+            },
+            Mode::Remix => remix_matrix(from.get(), to.get(), options),
+        };
+        Ok(Self::from_rows(from.len() as usize, to.len() as usize, &matrix))
+    }
+
+    /// Builds a matrix by calling the function for each pair of source and destination channels.
+    fn build(from: &Map, to: &Map, mut f: impl FnMut(&mut Rows, usize, usize)) -> Rows {
+        let mut m = vec![vec![0.0; from.len() as usize]; to.len() as usize];
+        for oc in 0..to.len() as usize {
+            for ic in 0..from.len() as usize {
+                f(&mut m, ic, oc);
+            }
+        }
+        m
+    }
+
+    /// Creates a remixer from the rows of a matrix.
+    fn from_rows(inputs: usize, outputs: usize, rows: &Rows) -> Self {
+        let matrix: Vec<f32> = rows.iter().flatten().copied().collect();
+        let fixed = matrix.iter().map(|f| (f * 0x10000 as f32).round() as i32).collect();
+        Self { inputs, outputs, matrix, fixed }
+    }
+
+    /// Gets the number of source channels.
+    #[inline]
+    pub fn input_channels(&self) -> usize {
+        self.inputs
+    }
+
+    /// Gets the number of destination channels.
+    #[inline]
+    pub fn output_channels(&self) -> usize {
+        self.outputs
+    }
+
+    /// Gets the coefficient with which a source channel is mixed into a destination channel.
+    ///
+    /// Panics if either channel is out of range.
+    #[inline]
+    pub fn coefficient(&self, output: usize, input: usize) -> f32 {
+        assert!(output < self.outputs && input < self.inputs);
+        self.matrix[output * self.inputs + input]
+    }
+
+    /// Gets the mixing matrix, a row of source channel coefficients for each destination channel.
+    #[inline]
+    pub fn matrix(&self) -> &[f32] {
+        &self.matrix
+    }
+
+    /// Checks whether or not each destination channel is simply a copy of the source channel of the
+    /// same index.
+    pub fn is_identity(&self) -> bool {
+        self.inputs == self.outputs && self.matrix.chunks(self.inputs).enumerate()
+            .all(|(oc, row)| row.iter().enumerate().all(|(ic, f)| *f == (ic == oc) as u8 as f32))
+    }
+
+    /// Remixes interleaved floating-point frames, returning the number of frames.
+    ///
+    /// The output must hold exactly as many frames as the input. Returns the [`PAErr`] form of the
+    /// [`Code::Invalid`] error otherwise, or if the input is not a whole number of frames.
+    pub fn remix_f32(&self, input: &[f32], output: &mut [f32]) -> Result<usize, PAErr> {
+        let frames = self.check(input.len(), output.len())?;
+        let frames_out = output.chunks_exact_mut(self.outputs);
+        for (src, dst) in input.chunks_exact(self.inputs).zip(frames_out) {
+            for (d, row) in dst.iter_mut().zip(self.matrix.chunks_exact(self.inputs)) {
+                *d = src.iter().zip(row).map(|(s, f)| s * f).sum();
+            }
+        }
+        Ok(frames)
+    }
+
+    /// Remixes interleaved signed 16-bit frames, returning the number of frames.
+    ///
+    /// This uses a fixed-point form of the matrix, with results clamped to the range of the format.
+    /// The output must hold exactly as many frames as the input. Returns the [`PAErr`] form of the
+    /// [`Code::Invalid`] error otherwise, or if the input is not a whole number of frames.
+    pub fn remix_i16(&self, input: &[i16], output: &mut [i16]) -> Result<usize, PAErr> {
+        let frames = self.check(input.len(), output.len())?;
+        let frames_out = output.chunks_exact_mut(self.outputs);
+        for (src, dst) in input.chunks_exact(self.inputs).zip(frames_out) {
+            for (d, row) in dst.iter_mut().zip(self.fixed.chunks_exact(self.inputs)) {
+                let sum: i64 = src.iter().zip(row).map(|(s, f)| *s as i64 * *f as i64).sum();
+                *d = ((sum + 0x8000) >> 16).clamp(i16::MIN as i64, i16::MAX as i64) as i16;
+            }
+        }
+        Ok(frames)
+    }
+
+    /// Checks input and output lengths, returning the number of frames.
+    fn check(&self, input: usize, output: usize) -> Result<usize, PAErr> {
+        let frames = input / self.inputs;
+        match input % self.inputs == 0 && output == frames * self.outputs {
+            true => Ok(frames),
+            false => Err(PAErr::from(Code::Invalid)),
+        }
+    }
+}
+
+/// The rows of a matrix being built.
+type Rows = Vec<Vec<f32>>;
+
+/// Builds a matrix for full remixing.
+fn remix_matrix(from: &[Position], to: &[Position], options: &Options) -> Rows {
+    let (n_ic, n_oc) = (from.len(), to.len());
+    let mut m = vec![vec![0.0f32; n_ic]; n_oc];
+    let mut ic_connected = vec![false; n_ic];
+    let mut oc_connected = vec![false; n_oc];
+
+    // Connect channels of the same position, along with mono channels
+    for (oc, &b) in to.iter().enumerate() {
+        for (ic, &a) in from.iter().enumerate() {
+            let f = if a == b || a == Position::Mono {
+                1.0
+            }
+            else if b == Position::Mono {
+                1.0 / n_ic as f32
+            }
+            else {
+                continue;
+            };
+            m[oc][ic] = f;
+            oc_connected[oc] = true;
+            ic_connected[ic] = true;
+        }
+    }
+
+    // Let rear and side channels stand in for each other
+    for (oc, &b) in to.iter().enumerate() {
+        let counterpart = match (oc_connected[oc], rear_side_counterpart(b)) {
+            (false, Some(counterpart)) => counterpart,
+            _ => continue,
+        };
+        if let Some(ic) = (0..n_ic).find(|&ic| from[ic] == counterpart && !ic_connected[ic]) {
+            m[oc][ic] = 1.0;
+            oc_connected[oc] = true;
+            ic_connected[ic] = true;
+        }
+    }
+
+    // Fill unconnected destination channels from source channels of the same side
+    let ic_left = from.iter().filter(|p| on_left(**p)).count();
+    let ic_right = from.iter().filter(|p| on_right(**p)).count();
+    let ic_center = from.iter().filter(|p| on_center(**p)).count();
+    for (oc, &b) in to.iter().enumerate() {
+        if oc_connected[oc] {
+            continue;
+        }
+        let (select, count): (fn(Position) -> bool, usize) = if on_left(b) {
+            (on_left, ic_left)
+        }
+        else if on_right(b) {
+            (on_right, ic_right)
+        }
+        else if on_center(b) && ic_center > 0 {
+            (on_center, ic_center)
+        }
+        else if on_center(b) {
+            (|p| on_left(p) || on_right(p), ic_left + ic_right)
+        }
+        else if b == Position::Lfe && options.produce_lfe {
+            // Source channels are not considered connected through the LFE channel
+            m[oc].iter_mut().for_each(|f| *f = 1.0 / n_ic as f32);
+            continue;
+        }
+        else {
+            continue;
+        };
+        for (ic, &a) in from.iter().enumerate() {
+            if select(a) {
+                m[oc][ic] = 1.0 / count as f32;
+                ic_connected[ic] = true;
+            }
+        }
+    }
+
+    // Mix unconnected source channels into destination channels of the same side
+    let unconnected = |select: fn(Position) -> bool| {
+        (0..n_ic).filter(|&ic| !ic_connected[ic] && select(from[ic])).count()
+    };
+    let ic_unconnected_left = unconnected(on_left);
+    let ic_unconnected_right = unconnected(on_right);
+    let ic_unconnected_center = unconnected(on_center);
+    let ic_unconnected_lfe = unconnected(|p| p == Position::Lfe);
+    let mut center_mixed_in = false;
+    for (ic, &a) in from.iter().enumerate() {
+        if ic_connected[ic] {
+            continue;
+        }
+        for (oc, &b) in to.iter().enumerate() {
+            if on_left(a) && on_left(b) {
+                m[oc][ic] = (1.0 / 9.0) / ic_unconnected_left as f32;
+            }
+            else if on_right(a) && on_right(b) {
+                m[oc][ic] = (1.0 / 9.0) / ic_unconnected_right as f32;
+            }
+            else if on_center(a) && on_center(b) {
+                m[oc][ic] = (1.0 / 9.0) / ic_unconnected_center as f32;
+                center_mixed_in = true;
+            }
+            else if a == Position::Lfe && options.consume_lfe {
+                m[oc][ic] = 0.375 / ic_unconnected_lfe as f32;
+            }
+        }
+    }
+
+    // Failing a center destination channel, mix center source channels into the left and right
+    // ones, preferring those of the same placement
+    if ic_unconnected_center > 0 && !center_mixed_in {
+        let side = |oc: usize| on_left(to[oc]) || on_right(to[oc]);
+        let mut ncenter = vec![0usize; n_oc];
+        let mut found = vec![false; n_ic];
+        for ic in (0..n_ic).filter(|&ic| !ic_connected[ic] && on_center(from[ic])) {
+            found[ic] = (0..n_oc).any(|oc| side(oc) && placement(from[ic]) == placement(to[oc]));
+            for oc in (0..n_oc).filter(|&oc| side(oc)) {
+                if !found[ic] || placement(from[ic]) == placement(to[oc]) {
+                    ncenter[oc] += 1;
+                }
+            }
+        }
+        for oc in (0..n_oc).filter(|&oc| side(oc) && ncenter[oc] > 0) {
+            for ic in (0..n_ic).filter(|&ic| !ic_connected[ic] && on_center(from[ic])) {
+                if !found[ic] || placement(from[ic]) == placement(to[oc]) {
+                    m[oc][ic] = 0.5 / ncenter[oc] as f32;
+                }
+            }
+        }
+    }
+
+    // Normalize, avoiding clipping
+    for row in m.iter_mut() {
+        let sum: f32 = row.iter().sum();
+        if sum > 1.0 {
+            row.iter_mut().for_each(|f| *f /= sum);
+        }
+    }
+    m
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Creates a map of the specified positions.
+    fn map(positions: &[Position]) -> Map {
+        let mut map = Map::default();
+        map.set_len(positions.len() as u8);
+        map.get_mut().copy_from_slice(positions);
+        map
+    }
+
+    /// Check upmixing stereo to 5.1, and downmixing back again
+    #[test]
+    fn stereo_surround() {
+        use Position::*;
+        let stereo = map(&[FrontLeft, FrontRight]);
+        let surround = map(&[FrontLeft, FrontRight, RearLeft, RearRight, FrontCenter, Lfe]);
+
+        let up = Remixer::new(&stereo, &surround, &Options::default()).unwrap();
+        assert_eq!(up.matrix(), &[1.0, 0.0, 0.0, 1.0, 1.0, 0.0, 0.0, 1.0, 0.5, 0.5, 0.0, 0.0]);
+        let mut output = [0.0; 6];
+        assert_eq!(up.remix_f32(&[0.5, -0.5], &mut output), Ok(1));
+        assert_eq!(output, [0.5, -0.5, 0.5, -0.5, 0.0, 0.0]);
+
+        let options = Options { produce_lfe: true, ..Options::default() };
+        let up = Remixer::new(&stereo, &surround, &options).unwrap();
+        assert_eq!(up.coefficient(5, 0), 0.5);
+
+        // Left: 1 front left, 1/9 rear left, 0.5 center, normalized
+        let down = Remixer::new(&surround, &stereo, &Options::default()).unwrap();
+        let sum = 1.0 + 1.0 / 9.0 + 0.5;
+        assert!((down.coefficient(0, 0) - 1.0 / sum).abs() < 1e-6);
+        assert!((down.coefficient(0, 2) - (1.0 / 9.0) / sum).abs() < 1e-6);
+        assert!((down.coefficient(0, 4) - 0.5 / sum).abs() < 1e-6);
+        assert_eq!(down.coefficient(0, 3), 0.0);
+        assert_eq!(down.coefficient(0, 5), 0.0);
+    }
+
+    /// Check rear and side channels standing in for each other, and the other modes
+    #[test]
+    fn rear_side_and_modes() {
+        use Position::*;
+        let rear = map(&[FrontLeft, FrontRight, RearLeft, RearRight]);
+        let side = map(&[FrontLeft, FrontRight, SideLeft, SideRight]);
+        assert!(Remixer::new(&rear, &side, &Options::default()).unwrap().is_identity());
+
+        let options = Options { mode: Mode::CommonOnly, ..Options::default() };
+        let common = Remixer::new(&rear, &side, &options).unwrap();
+        assert_eq!(common.matrix()[8..], [0.0; 8]);
+        let mut output = [0; 4];
+        assert_eq!(common.remix_i16(&[100, -100, 50, 50], &mut output), Ok(1));
+        assert_eq!(output, [100, -100, 0, 0]);
+
+        let options = Options { mode: Mode::ByIndex, ..Options::default() };
+        let mono = map(&[Mono]);
+        let by_index = Remixer::new(&mono, &side, &options).unwrap();
+        assert_eq!(by_index.matrix(), &[1.0, 0.0, 0.0, 0.0]);
+        let remix = Remixer::new(&mono, &side, &Options::default()).unwrap();
+        assert_eq!(remix.matrix(), &[1.0; 4]);
+    }
+}
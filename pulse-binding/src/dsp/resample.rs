@@ -0,0 +1,481 @@
+// Copyright 2026 Lyndon Brown
+//
+// This file is part of the PulseAudio Rust language binding.
+//
+// Licensed under the MIT license or the Apache license (version 2.0), at your option. You may not
+// copy, modify, or distribute this file except in compliance with said license. You can find copies
+// of these licenses either in the LICENSE-MIT and LICENSE-APACHE files, or alternatively at
+// <http://opensource.org/licenses/MIT> and <http://www.apache.org/licenses/LICENSE-2.0>
+// respectively.
+
+//! Sample rate conversion.
+//!
+//! # Overview
+//!
+//! The rate of a stream can only be changed with [`Stream::update_sample_rate()`] if it was
+//! connected with [`FlagSet::VARIABLE_RATE`]. Otherwise, audio of a different rate must be
+//! resampled on the client side, which a [`Resampler`] does, converting between the rates of two
+//! sample specs, at a [`Quality`] of either linear interpolation or windowed sinc interpolation.
+//!
+//! Data is processed in chunks, as it arrives, with the resampler keeping whatever it needs of
+//! previous chunks. Once the end of the data is reached, [`Resampler::flush()`] gives the remaining
+//! output.
+//!
+//! # Drift correction
+//!
+//! The sound card clock usually runs at a slightly different rate than the clock of whatever is
+//! producing the audio, such that the latency of a stream slowly grows or shrinks. This can be
+//! corrected by adjusting the conversion ratio slightly, with
+//! [`Resampler::set_adjustment()`], according to the drift estimated from the time of the stream,
+//! such as by a [`Clock`]:
+//!
+//! ```rust,ignore
+//! use libpulse_binding::dsp::resample::{Quality, Resampler};
+//!
+//! let mut resampler = Resampler::new(&source_spec, &stream_spec, Quality::Sinc).unwrap();
+//!
+//! // Upon each chunk
+//! if let Some(ppm) = clock.drift_ppm() {
+//!     // The sound card consuming faster needs more frames from the same input
+//!     resampler.set_adjustment(1.0 + ppm / 1_000_000.0).unwrap();
+//! }
+//! let mut output = Vec::new();
+//! resampler.process(&chunk, &mut output).unwrap();
+//! ```
+//!
+//! [`Stream::update_sample_rate()`]: crate::stream::Stream::update_sample_rate
+//! [`FlagSet::VARIABLE_RATE`]: crate::stream::FlagSet::VARIABLE_RATE
+//! [`Clock`]: crate::clock::Clock
+
+use std::f64::consts::PI;
+use super::convert;
+use crate::error::{Code, PAErr};
+use crate::sample::{Format, Spec};
+
+/// Resolution of the windowed sinc table, in points per zero crossing.
+const TABLE_RESOLUTION: usize = 256;
+
+/// Scale of the fixed-point step, by which rates are multiplied, such that the position advances
+/// exactly, with any adjustment applied at this resolution.
+const STEP_SCALE: f64 = (1u64 << 20) as f64;
+
+/// Quality of sample rate conversion.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Quality {
+    /// Linear interpolation between neighbouring frames. Cheap, but with poor suppression of
+    /// aliasing.
+    Linear,
+    /// Windowed sinc interpolation, over 16 zero crossings either side.
+    Sinc,
+    /// Windowed sinc interpolation, over 48 zero crossings either side, for the best suppression of
+    /// aliasing, at a greater cost.
+    SincBest,
+}
+
+impl Quality {
+    /// Gets the number of zero crossings of the sinc function either side, if sinc interpolation.
+    fn zero_crossings(self) -> Option<usize> {
+        match self {
+            Quality::Linear => None,
+            Quality::Sinc => Some(16),
+            Quality::SincBest => Some(48),
+        }
+    }
+}
+
+/// Converts data from the rate of one sample spec to that of another.
+///
+/// See the [module level documentation](mod@self) for details.
+#[derive(Debug, Clone)]
+pub struct Resampler {
+    /// Spec of the source data.
+    from: Spec,
+    /// Spec of the destination data.
+    to: Spec,
+    /// The number of channels.
+    channels: usize,
+    /// Table of the windowed sinc function, from zero to the last zero crossing, if sinc
+    /// interpolation.
+    table: Option<Vec<f32>>,
+    /// Adjustment of the conversion ratio.
+    adjustment: f64,
+    /// Source frames per destination frame, as a numerator and denominator.
+    step: (u64, u64),
+    /// Cutoff frequency, relative to the Nyquist frequency of the source.
+    cutoff: f64,
+    /// Source frames yet needed, interleaved, including those before the position.
+    buf: Vec<f32>,
+    /// Position of the next destination frame in the source frames held, in whole frames.
+    pos: usize,
+    /// Fractional part of the position, in units of the step denominator.
+    frac: u64,
+    /// Whether or not the buffer has been primed with silence before the first frame.
+    primed: bool,
+    /// Source data converted to floating-point form, kept for reuse.
+    scratch_in: Vec<f32>,
+    /// Destination data in floating-point form, kept for reuse.
+    scratch_out: Vec<f32>,
+}
+
+impl Resampler {
+    /// Creates a resampler from the rate of one spec to that of another.
+    ///
+    /// The formats may differ, with data being converted as with [`Converter`], but the number of
+    /// channels must match; see [`Remixer`] for converting between channel maps. Returns the
+    /// [`PAErr`] form of the [`Code::Invalid`] error if either spec is invalid, or the number of
+    /// channels differs.
+    ///
+    /// [`Converter`]: super::convert::Converter
+    /// [`Remixer`]: super::remix::Remixer
+    pub fn new(from: &Spec, to: &Spec, quality: Quality) -> Result<Self, PAErr> {
+        let channels = super::check_frames(0, from)?;
+        super::check_frames(0, to)?;
+        if from.channels != to.channels {
+            return Err(PAErr::from(Code::Invalid));
+        }
+        let table = quality.zero_crossings().map(sinc_table);
+        let mut resampler = Self {
+            from: *from,
+            to: *to,
+            channels,
+            table,
+            adjustment: 1.0,
+            step: (1, 1),
+            cutoff: 1.0,
+            buf: Vec::new(),
+            pos: 0,
+            frac: 0,
+            primed: false,
+            scratch_in: Vec::new(),
+            scratch_out: Vec::new(),
+        };
+        resampler.update_step();
+        Ok(resampler)
+    }
+
+    /// Gets the spec of the source data.
+    #[inline]
+    pub fn input_spec(&self) -> &Spec {
+        &self.from
+    }
+
+    /// Gets the spec of the destination data.
+    #[inline]
+    pub fn output_spec(&self) -> &Spec {
+        &self.to
+    }
+
+    /// Sets the adjustment of the conversion ratio, by which the number of destination frames
+    /// produced from the same source frames is multiplied, for drift correction.
+    ///
+    /// This takes effect from the next destination frame. Returns the [`PAErr`] form of the
+    /// [`Code::Invalid`] error if not between 0.5 and 2.0.
+    pub fn set_adjustment(&mut self, adjustment: f64) -> Result<(), PAErr> {
+        if !(0.5..=2.0).contains(&adjustment) {
+            return Err(PAErr::from(Code::Invalid));
+        }
+        self.adjustment = adjustment;
+        self.update_step();
+        Ok(())
+    }
+
+    /// Gets the adjustment of the conversion ratio.
+    #[inline]
+    pub fn adjustment(&self) -> f64 {
+        self.adjustment
+    }
+
+    /// Gets the effective conversion ratio, being the number of destination frames produced per
+    /// source frame.
+    #[inline]
+    pub fn ratio(&self) -> f64 {
+        self.step.1 as f64 / self.step.0 as f64
+    }
+
+    /// Resamples interleaved data, appending the destination data to the output, and returning the
+    /// number of frames appended.
+    ///
+    /// Returns the [`PAErr`] form of the [`Code::Invalid`] error if the input is not a whole number
+    /// of frames.
+    pub fn process(&mut self, input: &[u8], output: &mut Vec<u8>) -> Result<usize, PAErr> {
+        super::check_frames(input.len(), &self.from)?;
+        let mut scratch_in = std::mem::take(&mut self.scratch_in);
+        scratch_in.clear();
+        scratch_in.resize(input.len() / self.sample_size(self.from.format), 0.0);
+        convert::convert(self.from.format, Format::FLOAT32NE, input,
+            crate::buffer::as_bytes_mut(&mut scratch_in[..]), None)?;
+        let mut scratch_out = std::mem::take(&mut self.scratch_out);
+        scratch_out.clear();
+        let result = self.process_f32(&scratch_in, &mut scratch_out)
+            .and_then(|frames| self.append(&scratch_out, output).map(|_| frames));
+        self.scratch_in = scratch_in;
+        self.scratch_out = scratch_out;
+        result
+    }
+
+    /// Resamples interleaved floating-point samples, appending the destination samples to the
+    /// output, and returning the number of frames appended.
+    ///
+    /// Returns the [`PAErr`] form of the [`Code::Invalid`] error if the input is not a whole number
+    /// of frames.
+    pub fn process_f32(&mut self, input: &[f32], output: &mut Vec<f32>) -> Result<usize, PAErr> {
+        if input.len() % self.channels != 0 {
+            return Err(PAErr::from(Code::Invalid));
+        }
+        if !self.primed {
+            // Silence before the first frame, such that sinc interpolation introduces no delay
+            self.buf.resize(self.history() * self.channels, 0.0);
+            self.pos = self.history();
+            self.primed = true;
+        }
+        self.buf.extend_from_slice(input);
+        Ok(self.run(None, output))
+    }
+
+    /// Gives the remaining destination data, appending it to the output and returning the number
+    /// of frames appended, once the end of the source data has been reached.
+    ///
+    /// The resampler is then reset, ready for new data.
+    pub fn flush(&mut self, output: &mut Vec<u8>) -> Result<usize, PAErr> {
+        let mut scratch_out = std::mem::take(&mut self.scratch_out);
+        scratch_out.clear();
+        let frames = self.flush_f32(&mut scratch_out);
+        let result = self.append(&scratch_out, output).map(|_| frames);
+        self.scratch_out = scratch_out;
+        result
+    }
+
+    /// Gives the remaining destination samples, appending them to the output and returning the
+    /// number of frames appended, once the end of the source data has been reached.
+    ///
+    /// The resampler is then reset, ready for new data.
+    pub fn flush_f32(&mut self, output: &mut Vec<f32>) -> usize {
+        let frames = match self.primed {
+            true => {
+                let end = self.buf.len() / self.channels;
+                self.buf.resize(self.buf.len() + self.reach() * self.channels, 0.0);
+                self.run(Some(end), output)
+            },
+            false => 0,
+        };
+        self.reset();
+        frames
+    }
+
+    /// Discards all data held, ready for new data, keeping the adjustment of the conversion ratio.
+    pub fn reset(&mut self) {
+        self.buf.clear();
+        self.pos = 0;
+        self.frac = 0;
+        self.primed = false;
+    }
+
+    /// Updates the step and cutoff frequency from the rates and adjustment.
+    fn update_step(&mut self) {
+        let num = self.from.rate as u64 * STEP_SCALE as u64;
+        let den = (self.to.rate as f64 * self.adjustment * STEP_SCALE).round() as u64;
+        // The fractional position is rescaled, to keep the same position
+        self.frac = (self.frac as f64 * den as f64 / self.step.1 as f64) as u64;
+        self.step = (num, den);
+        self.cutoff = self.ratio().min(1.0);
+    }
+
+    /// Gets the size of a sample of a format already checked to be valid.
+    #[inline]
+    fn sample_size(&self, format: Format) -> usize {
+        super::sample_size(format).unwrap_or(1)
+    }
+
+    /// Gets the number of source frames needed after the position.
+    fn reach(&self) -> usize {
+        match self.table.as_ref() {
+            Some(table) => {
+                let crossings = (table.len() - 2) / TABLE_RESOLUTION;
+                (crossings as f64 / self.cutoff).ceil() as usize
+            },
+            None => 1,
+        }
+    }
+
+    /// Gets the number of source frames needed before the position.
+    fn history(&self) -> usize {
+        match self.table {
+            Some(_) => self.reach(),
+            None => 0,
+        }
+    }
+
+    /// Produces as many destination frames as the source frames held allow, though not beyond the
+    /// end given, if any, discarding source frames no longer needed.
+    fn run(&mut self, end: Option<usize>, output: &mut Vec<f32>) -> usize {
+        let channels = self.channels;
+        let frames = self.buf.len() / channels;
+        let reach = self.reach();
+        let (num, den) = self.step;
+        let mut produced = 0;
+
+        while end.map_or(true, |end| self.pos < end) {
+            let base = self.pos;
+            if base + reach >= frames {
+                break;
+            }
+            let start = output.len();
+            output.resize(start + channels, 0.0);
+            let out = &mut output[start..];
+
+            match self.table.as_ref() {
+                None => {
+                    let frac = (self.frac as f64 / den as f64) as f32;
+                    let a = &self.buf[base * channels..][..channels];
+                    let b = &self.buf[(base + 1) * channels..][..channels];
+                    for ((o, a), b) in out.iter_mut().zip(a).zip(b) {
+                        *o = a + (b - a) * frac;
+                    }
+                },
+                Some(table) => {
+                    let first = (base + 1).saturating_sub(reach);
+                    for i in first..=base + reach {
+                        let offset = i as f64 - base as f64 - self.frac as f64 / den as f64;
+                        let w = kernel(table, offset * self.cutoff);
+                        if w == 0.0 {
+                            continue;
+                        }
+                        let frame = &self.buf[i * channels..][..channels];
+                        for (o, s) in out.iter_mut().zip(frame) {
+                            *o += s * w;
+                        }
+                    }
+                    out.iter_mut().for_each(|o| *o *= self.cutoff as f32);
+                },
+            }
+            produced += 1;
+            self.frac += num;
+            self.pos += (self.frac / den) as usize;
+            self.frac %= den;
+        }
+
+        // Discard source frames no longer needed
+        let discard = std::cmp::min(self.pos.saturating_sub(self.history()), frames);
+        self.buf.drain(..discard * channels);
+        self.pos -= discard;
+        produced
+    }
+
+    /// Converts destination samples from floating-point form, appending them to the output.
+    fn append(&self, samples: &[f32], output: &mut Vec<u8>) -> Result<(), PAErr> {
+        let start = output.len();
+        output.resize(start + samples.len() * self.sample_size(self.to.format), 0);
+        convert::convert(Format::FLOAT32NE, self.to.format, crate::buffer::as_bytes(samples),
+            &mut output[start..], None)?;
+        Ok(())
+    }
+}
+
+/// Builds a table of the windowed sinc function, from zero to the specified number of zero
+/// crossings, with a Blackman window.
+fn sinc_table(crossings: usize) -> Vec<f32> {
+    let len = crossings * TABLE_RESOLUTION;
+    (0..len + 2).map(|i| {
+        let x = i as f64 / TABLE_RESOLUTION as f64;
+        if i >= len {
+            return 0.0;
+        }
+        let sinc = match i {
+            0 => 1.0,
+            _ => (PI * x).sin() / (PI * x),
+        };
+        let phase = PI * x / crossings as f64;
+        let window = 0.42 + 0.5 * phase.cos() + 0.08 * (2.0 * phase).cos();
+        (sinc * window) as f32
+    }).collect()
+}
+
+/// Looks up the windowed sinc function, interpolating linearly between points of the table.
+#[inline]
+fn kernel(table: &[f32], x: f64) -> f32 {
+    let x = x.abs() * TABLE_RESOLUTION as f64;
+    let i = x as usize;
+    if i + 1 >= table.len() {
+        return 0.0;
+    }
+    let frac = (x - i as f64) as f32;
+    table[i] + (table[i + 1] - table[i]) * frac
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Gets a spec of floating-point samples.
+    fn spec(channels: u8, rate: u32) -> Spec {
+        Spec { format: Format::FLOAT32NE, channels, rate }
+    }
+
+    /// Check that equal rates give the data back unchanged, and that linear interpolation falls
+    /// between neighbouring frames
+    #[test]
+    fn interpolation() {
+        let input: Vec<f32> = (0..40).map(|i| (i as f32 * 0.7).sin()).collect();
+        for quality in [Quality::Linear, Quality::Sinc, Quality::SincBest] {
+            let mut resampler = Resampler::new(&spec(2, 8000), &spec(2, 8000), quality).unwrap();
+            let mut output = Vec::new();
+            resampler.process_f32(&input, &mut output).unwrap();
+            resampler.flush_f32(&mut output);
+            assert_eq!(output.len(), input.len());
+            assert!(output.iter().zip(&input).all(|(o, i)| (o - i).abs() < 1e-5));
+        }
+
+        let mut resampler = Resampler::new(&spec(1, 4000), &spec(1, 8000), Quality::Linear)
+            .unwrap();
+        let mut output = Vec::new();
+        assert_eq!(resampler.process_f32(&[0.0, 1.0, 0.5], &mut output), Ok(4));
+        assert_eq!(resampler.flush_f32(&mut output), 2);
+        assert_eq!(output, [0.0, 0.5, 1.0, 0.75, 0.5, 0.25]);
+    }
+
+    /// Check that processing in chunks gives the same result as processing all at once, and that
+    /// the level of a constant signal is kept
+    #[test]
+    fn streaming() {
+        let input = vec![0.5f32; 4410];
+        let all_at_once = {
+            let mut resampler = Resampler::new(&spec(1, 44100), &spec(1, 48000), Quality::Sinc)
+                .unwrap();
+            let mut output = Vec::new();
+            resampler.process_f32(&input, &mut output).unwrap();
+            resampler.flush_f32(&mut output);
+            output
+        };
+        assert_eq!(all_at_once.len(), 4800);
+        assert!(all_at_once[100..4700].iter().all(|s| (s - 0.5).abs() < 1e-3));
+
+        let mut resampler = Resampler::new(&spec(1, 44100), &spec(1, 48000), Quality::Sinc)
+            .unwrap();
+        let mut output = Vec::new();
+        for chunk in input.chunks(441) {
+            resampler.process_f32(chunk, &mut output).unwrap();
+        }
+        resampler.flush_f32(&mut output);
+        assert_eq!(output, all_at_once);
+    }
+
+    /// Check byte conversion around resampling, and adjustment of the ratio
+    #[test]
+    fn adjustment() {
+        let from = Spec { format: Format::S16le, channels: 1, rate: 8000 };
+        let to = Spec { format: Format::U8, channels: 1, rate: 8000 };
+        let mut resampler = Resampler::new(&from, &to, Quality::Linear).unwrap();
+        let mut output = Vec::new();
+        resampler.process(&[0x00, 0x40, 0x00, 0xc0], &mut output).unwrap();
+        resampler.flush(&mut output).unwrap();
+        assert_eq!(output, [0xc0, 0x40]);
+
+        resampler.set_adjustment(0.5).unwrap();
+        let input = vec![0; 2000];
+        resampler.process(&input, &mut output).unwrap();
+        resampler.flush(&mut output).unwrap();
+        assert_eq!(output.len(), 2 + 500);
+        assert!(resampler.set_adjustment(3.0).is_err());
+        assert!(resampler.process(&[0], &mut output).is_err());
+    }
+}
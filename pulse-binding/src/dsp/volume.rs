@@ -0,0 +1,224 @@
+// Copyright 2026 Lyndon Brown
+//
+// This file is part of the PulseAudio Rust language binding.
+//
+// Licensed under the MIT license or the Apache license (version 2.0), at your option. You may not
+// copy, modify, or distribute this file except in compliance with said license. You can find copies
+// of these licenses either in the LICENSE-MIT and LICENSE-APACHE files, or alternatively at
+// <http://opensource.org/licenses/MIT> and <http://www.apache.org/licenses/LICENSE-2.0>
+// respectively.
+
+//! Software volume.
+//!
+//! # Overview
+//!
+//! Volumes are applied just as the server applies them, using the same cubic curve as conversion
+//! to [`VolumeLinear`], and for integer formats, the same 16.16 fixed-point factors, with results
+//! clamped to the range of the format. A-law and μ-law samples are decoded to 16-bit linear form,
+//! scaled, and encoded again.
+//!
+//! [`apply()`] takes volumes in the channel order of the data, whereas [`apply_mapped()`] takes
+//! volumes in the channel order of another channel map, such as that of a sink, remapping them to
+//! the channel map of the data first.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use libpulse_binding::dsp::volume;
+//! use libpulse_binding::volume::{ChannelVolumes, Volume, VolumeDB};
+//!
+//! let mut volumes = ChannelVolumes::default();
+//! volumes.set(spec.channels, Volume::from(VolumeDB(-6.0)));
+//! volume::apply(&mut data, &spec, &volumes).unwrap();
+//! ```
+//!
+//! [`VolumeLinear`]: crate::volume::VolumeLinear
+
+use super::g711;
+use crate::channelmap::Map;
+use crate::error::{Code, PAErr};
+use crate::sample::{Format, Spec};
+use crate::volume::{ChannelVolumes, Volume};
+
+/// Converts a volume to a linear factor, using the cubic volume curve.
+///
+/// This gives the same result as converting to [`VolumeLinear`], without calling into the
+/// PulseAudio client library. Invalid volumes give a factor of zero.
+///
+/// [`VolumeLinear`]: crate::volume::VolumeLinear
+pub fn to_linear(volume: Volume) -> f64 {
+    if !volume.is_valid() || volume <= Volume::MUTED {
+        return 0.0;
+    }
+    if volume.is_normal() {
+        return 1.0;
+    }
+    let f = volume.0 as f64 / Volume::NORMAL.0 as f64;
+    f * f * f
+}
+
+/// Applies volumes to interleaved data, in place.
+///
+/// The volumes are in the channel order of the data, and there must be as many as there are
+/// channels. Returns the [`PAErr`] form of the [`Code::Invalid`] error if the spec is invalid, the
+/// number of volumes does not match the number of channels, or the data is not a whole number of
+/// frames.
+pub fn apply(data: &mut [u8], spec: &Spec, volumes: &ChannelVolumes) -> Result<(), PAErr> {
+    let channels = super::check_frames(data.len(), spec)?;
+    if volumes.len() != spec.channels {
+        return Err(PAErr::from(Code::Invalid));
+    }
+    let volumes = volumes.get();
+    if volumes.iter().all(Volume::is_normal) {
+        return Ok(());
+    }
+
+    // Fixed-point factors for integer formats, floating-point ones otherwise
+    let mut fixed = [0i64; ChannelVolumes::CHANNELS_MAX as usize];
+    let mut float = [0f32; ChannelVolumes::CHANNELS_MAX as usize];
+    for (i, v) in volumes.iter().enumerate() {
+        let linear = to_linear(*v);
+        fixed[i] = (linear * 0x10000 as f64).round() as i64;
+        float[i] = linear as f32;
+    }
+    let scale = |t: i64, c: usize, min: i64, max: i64| ((t * fixed[c]) >> 16).clamp(min, max);
+    let scale16 = |t: i16, c: usize| scale(t as i64, c, i16::MIN as i64, i16::MAX as i64) as i16;
+    let scale32 = |t: i32, c: usize| scale(t as i64, c, i32::MIN as i64, i32::MAX as i64) as i32;
+
+    match spec.format {
+        Format::U8 => for_each::<1>(data, channels, |s, c| {
+            s[0] = (scale(s[0] as i64 - 0x80, c, -0x80, 0x7f) + 0x80) as u8;
+        }),
+        Format::ALaw => for_each::<1>(data, channels, |s, c| {
+            s[0] = g711::linear13_to_alaw(scale16(g711::alaw_to_linear16(s[0]), c) >> 3);
+        }),
+        Format::ULaw => for_each::<1>(data, channels, |s, c| {
+            s[0] = g711::linear14_to_ulaw(scale16(g711::ulaw_to_linear16(s[0]), c) >> 2);
+        }),
+        Format::S16le => for_each::<2>(data, channels, |s, c| {
+            *s = scale16(i16::from_le_bytes(*s), c).to_le_bytes();
+        }),
+        Format::S16be => for_each::<2>(data, channels, |s, c| {
+            *s = scale16(i16::from_be_bytes(*s), c).to_be_bytes();
+        }),
+        Format::F32le => for_each::<4>(data, channels, |s, c| {
+            *s = (f32::from_le_bytes(*s) * float[c]).to_le_bytes();
+        }),
+        Format::F32be => for_each::<4>(data, channels, |s, c| {
+            *s = (f32::from_be_bytes(*s) * float[c]).to_be_bytes();
+        }),
+        Format::S32le => for_each::<4>(data, channels, |s, c| {
+            *s = scale32(i32::from_le_bytes(*s), c).to_le_bytes();
+        }),
+        Format::S32be => for_each::<4>(data, channels, |s, c| {
+            *s = scale32(i32::from_be_bytes(*s), c).to_be_bytes();
+        }),
+        // 24-bit samples are scaled in the upper bits of a 32-bit value
+        Format::S24le => for_each::<3>(data, channels, |s, c| {
+            let t = scale32(i32::from_le_bytes([0, s[0], s[1], s[2]]), c).to_le_bytes();
+            s.copy_from_slice(&t[1..]);
+        }),
+        Format::S24be => for_each::<3>(data, channels, |s, c| {
+            let t = scale32(i32::from_be_bytes([s[0], s[1], s[2], 0]), c).to_be_bytes();
+            s.copy_from_slice(&t[..3]);
+        }),
+        Format::S24_32le => for_each::<4>(data, channels, |s, c| {
+            let t = scale32((u32::from_le_bytes(*s) << 8) as i32, c);
+            *s = ((t >> 8) as u32).to_le_bytes();
+        }),
+        Format::S24_32be => for_each::<4>(data, channels, |s, c| {
+            let t = scale32((u32::from_be_bytes(*s) << 8) as i32, c);
+            *s = ((t >> 8) as u32).to_be_bytes();
+        }),
+        Format::Invalid => return Err(PAErr::from(Code::Invalid)),
+    }
+    Ok(())
+}
+
+/// Applies volumes to interleaved data, in place, with the volumes in the channel order of another
+/// channel map.
+///
+/// The volumes are first remapped from `volumes_map` to `map`, the channel map of the data, as with
+/// [`ChannelVolumes::remap()`], which is what the server does when applying the volume of a sink to
+/// a stream. Returns the [`PAErr`] form of the [`Code::Invalid`] error if either channel map does
+/// not match the number of channels it relates to, or as for [`apply()`].
+pub fn apply_mapped(data: &mut [u8], spec: &Spec, map: &Map, volumes: &ChannelVolumes,
+    volumes_map: &Map) -> Result<(), PAErr>
+{
+    if map.len() != spec.channels || volumes_map.len() != volumes.len() {
+        return Err(PAErr::from(Code::Invalid));
+    }
+    let mut volumes = *volumes;
+    volumes.remap(volumes_map, map);
+    apply(data, spec, &volumes)
+}
+
+/// Calls the function for each sample of the data, with its channel.
+#[inline]
+fn for_each<const N: usize>(data: &mut [u8], channels: usize,
+    mut f: impl FnMut(&mut [u8; N], usize))
+{
+    for (i, sample) in data.chunks_exact_mut(N).enumerate() {
+        // Cannot fail, the chunks being exactly the size of the array
+        if let Ok(sample) = <&mut [u8; N]>::try_from(sample) {
+            f(sample, i % channels);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Gets a volume with the specified linear factor.
+    fn volume(linear: f64) -> Volume {
+        Volume((linear.cbrt() * Volume::NORMAL.0 as f64).round() as u32)
+    }
+
+    /// Check the volume curve
+    #[test]
+    fn curve() {
+        assert_eq!(to_linear(Volume::MUTED), 0.0);
+        assert_eq!(to_linear(Volume::NORMAL), 1.0);
+        assert_eq!(to_linear(Volume::INVALID), 0.0);
+        assert!((to_linear(volume(0.5)) - 0.5).abs() < 1e-4);
+        assert!((to_linear(Volume(Volume::NORMAL.0 * 2)) - 8.0).abs() < 1e-9);
+    }
+
+    /// Check volumes applied per channel, with clamping, across formats
+    #[test]
+    fn formats() {
+        let mut volumes = ChannelVolumes::default();
+        volumes.set_len(2);
+        volumes.get_mut().copy_from_slice(&[volume(0.5), volume(4.0)]);
+
+        let spec = Spec { format: Format::S16le, channels: 2, rate: 44100 };
+        let mut data = [1000i16.to_le_bytes(), 10000i16.to_le_bytes()].concat();
+        apply(&mut data, &spec, &volumes).unwrap();
+        assert_eq!(data, [500i16.to_le_bytes(), i16::MAX.to_le_bytes()].concat());
+
+        let spec = Spec { format: Format::S24be, channels: 2, rate: 44100 };
+        let mut data = vec![0x01, 0x00, 0x00, 0x00, 0x00, 0x02];
+        apply(&mut data, &spec, &volumes).unwrap();
+        assert_eq!(data, [0x00, 0x80, 0x00, 0x00, 0x00, 0x08]);
+
+        let spec = Spec { format: Format::U8, channels: 2, rate: 8000 };
+        let mut data = vec![0x90, 0x10];
+        apply(&mut data, &spec, &volumes).unwrap();
+        assert_eq!(data, [0x88, 0x00]);
+
+        let spec = Spec { format: Format::ULaw, channels: 2, rate: 8000 };
+        let mut data = vec![0xff, 0x7f];
+        apply(&mut data, &spec, &volumes).unwrap();
+        assert_eq!(data, [0xff, 0xff]);
+
+        let spec = Spec { format: Format::F32be, channels: 2, rate: 8000 };
+        let mut data = [0.5f32.to_be_bytes(), 0.5f32.to_be_bytes()].concat();
+        apply(&mut data, &spec, &volumes).unwrap();
+        let value = |b: &[u8]| f32::from_be_bytes([b[0], b[1], b[2], b[3]]);
+        assert!((value(&data[..4]) - 0.25).abs() < 1e-4);
+        assert!((value(&data[4..]) - 2.0).abs() < 1e-3);
+
+        assert!(apply(&mut data[..6], &spec, &volumes).is_err());
+    }
+}
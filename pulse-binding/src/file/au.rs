@@ -0,0 +1,152 @@
+// Copyright 2026 Lyndon Brown
+//
+// This file is part of the PulseAudio Rust language binding.
+//
+// Licensed under the MIT license or the Apache license (version 2.0), at your option. You may not
+// copy, modify, or distribute this file except in compliance with said license. You can find copies
+// of these licenses either in the LICENSE-MIT and LICENSE-APACHE files, or alternatively at
+// <http://opensource.org/licenses/MIT> and <http://www.apache.org/licenses/LICENSE-2.0>
+// respectively.
+
+//! Sun AU files.
+//!
+//! # Overview
+//!
+//! Signed 16, 24 and 32-bit big endian, 32-bit float big endian, A-law and μ-law data is supported.
+//!
+//! AU files do not give the positions of their channels, thus they are given the same default
+//! channel map as WAV files without a channel mask, and the channel map given when writing serves
+//! only to check the number of channels.
+
+use std::io::{self, Read, Write};
+use crate::channelmap::Map;
+use crate::sample::{Format, Spec};
+use super::{check_read_spec, check_spec, invalid_data, invalid_input, skip, wav, Info, Kind};
+
+/// Length of the header, without any annotation.
+const HEADER_LEN: u32 = 24;
+
+/// Reads the header of an AU file, as with [`file::read_header()`], but failing for any other type
+/// of file.
+///
+/// Upon success, the reader is left at the start of the sample data.
+///
+/// [`file::read_header()`]: super::read_header
+pub fn read_header<R: Read>(reader: &mut R) -> io::Result<Info> {
+    match super::read_header(reader)? {
+        info if info.kind == Kind::Au => Ok(info),
+        _ => Err(invalid_data("not an AU file")),
+    }
+}
+
+/// Reads the header of an AU file, following the magic number.
+pub(super) fn read_header_body<R: Read>(reader: &mut R) -> io::Result<Info> {
+    let mut header = [0; HEADER_LEN as usize - 4];
+    reader.read_exact(&mut header)?;
+    let u32_at = |i: usize| {
+        u32::from_be_bytes([header[i], header[i + 1], header[i + 2], header[i + 3]])
+    };
+
+    let offset = u32_at(0);
+    let format = match u32_at(8) {
+        1 => Format::ULaw,
+        3 => Format::S16be,
+        4 => Format::S24be,
+        5 => Format::S32be,
+        6 => Format::F32be,
+        27 => Format::ALaw,
+        _ => return Err(invalid_data("unsupported sample format")),
+    };
+    let channels = u8::try_from(u32_at(16))
+        .map_err(|_| invalid_data("unsupported number of channels or rate"))?;
+    let spec = Spec { format, channels, rate: u32_at(12) };
+    check_read_spec(&spec)?;
+    if offset < HEADER_LEN {
+        return Err(invalid_data("invalid data offset"));
+    }
+
+    // Skip the annotation
+    skip(reader, u64::from(offset - HEADER_LEN))?;
+
+    let data_len = match u32_at(4) {
+        u32::MAX => None,
+        len => Some(u64::from(len)),
+    };
+    Ok(Info { kind: Kind::Au, spec, map: wav::channel_map(0, channels), data_len })
+}
+
+/// Writes the header of an AU file.
+///
+/// With a `data_len` of `None`, or one too long to be given, the length of the data is left
+/// unspecified.
+///
+/// Returns an error of kind [`InvalidInput`] if the spec is not valid, the channel map does not
+/// match it, or the sample format cannot be stored in an AU file.
+///
+/// [`InvalidInput`]: std::io::ErrorKind::InvalidInput
+pub fn write_header<W: Write>(writer: &mut W, spec: &Spec, map: &Map, data_len: Option<u64>)
+    -> io::Result<()>
+{
+    check_spec(spec, map)?;
+    let encoding: u32 = match spec.format {
+        Format::ULaw => 1,
+        Format::S16be => 3,
+        Format::S24be => 4,
+        Format::S32be => 5,
+        Format::F32be => 6,
+        Format::ALaw => 27,
+        _ => return Err(invalid_input("sample format cannot be stored in an AU file")),
+    };
+    let data_len = data_len.and_then(|len| u32::try_from(len).ok()).unwrap_or(u32::MAX);
+
+    let mut header = Vec::with_capacity(HEADER_LEN as usize);
+    header.extend_from_slice(b".snd");
+    for value in [HEADER_LEN, data_len, encoding, spec.rate, u32::from(spec.channels)] {
+        header.extend_from_slice(&value.to_be_bytes());
+    }
+    writer.write_all(&header)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+    use crate::channelmap::Position;
+    use crate::file::{Reader, Writer};
+
+    /// Check writing and reading back a file, and skipping of the annotation
+    #[test]
+    fn round_trip() {
+        let spec = Spec { format: Format::ULaw, channels: 1, rate: 8000 };
+        let map = wav::channel_map(0, 1);
+        let mut writer = Writer::new(Cursor::new(Vec::new()), Kind::Au, &spec, &map).unwrap();
+        writer.write_all(&[0xff, 0x7f, 0x00]).unwrap();
+        let mut file = writer.finish().unwrap().into_inner();
+        assert_eq!(&file[4..12], &[0, 0, 0, 24, 0, 0, 0, 3]);
+
+        // Move the data along, behind an annotation
+        file[7] = 28;
+        file.splice(24..24, *b"note");
+        let mut reader = Reader::new(Cursor::new(file)).unwrap();
+        let info = *reader.info();
+        assert_eq!((info.kind, info.spec.format, info.spec.rate), (Kind::Au, Format::ULaw, 8000));
+        assert_eq!(info.map.get(), &[Position::Mono]);
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data).unwrap();
+        assert_eq!(data, [0xff, 0x7f, 0x00]);
+
+        // Unknown length
+        let spec = Spec { format: Format::S16be, channels: 2, rate: 44100 };
+        let mut file = Vec::new();
+        write_header(&mut file, &spec, &wav::channel_map(0, 2), None).unwrap();
+        file.extend_from_slice(&[1, 2, 3, 4]);
+        assert!(read_header(&mut &file[..]).is_ok());
+        let err = wav::read_header(&mut &file[..]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        let mut reader = Reader::new(Cursor::new(file)).unwrap();
+        assert_eq!(reader.info().data_len, None);
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data).unwrap();
+        assert_eq!(data, [1, 2, 3, 4]);
+    }
+}
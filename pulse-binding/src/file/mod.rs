@@ -0,0 +1,265 @@
+// Copyright 2026 Lyndon Brown
+//
+// This file is part of the PulseAudio Rust language binding.
+//
+// Licensed under the MIT license or the Apache license (version 2.0), at your option. You may not
+// copy, modify, or distribute this file except in compliance with said license. You can find copies
+// of these licenses either in the LICENSE-MIT and LICENSE-APACHE files, or alternatively at
+// <http://opensource.org/licenses/MIT> and <http://www.apache.org/licenses/LICENSE-2.0>
+// respectively.
+
+//! Reading and writing of sound files.
+//!
+//! # Overview
+//!
+//! Playing a file, recording to disk, or uploading a file to the sample cache, all need the sample
+//! spec and channel map of the data, which come from the header of the file. This module reads and
+//! writes the headers of WAV files (including `WAVE_FORMAT_EXTENSIBLE` ones, with a channel mask)
+//! and Sun AU files, straight to and from a [`Spec`] and a [`Map`], through any [`Read`] or
+//! [`Write`] implementation.
+//!
+//! [`Reader`] reads the header of a file, detecting its type, and then reads the sample data, and
+//! no more. [`Writer`] writes a header, then the sample data, filling in the lengths within the
+//! header upon [`Writer::finish()`]. Where the output cannot be seeked, such as a pipe, the header
+//! can be written with [`write_header()`], with the length given upfront, or left unspecified.
+//!
+//! Only sample formats which can be stored in a file of the given type, as is, are supported; data
+//! of any other format must first be converted, as with [`Format::convert()`]. Errors are returned
+//! as [`std::io::Error`], with kind [`InvalidData`] for malformed or unsupported files, and
+//! [`InvalidInput`] for a spec or channel map which cannot be written.
+//!
+//! # Example
+//!
+//! Uploading a WAV file to the sample cache:
+//!
+//! ```rust,ignore
+//! use std::io::Read;
+//! use libpulse_binding::file::Reader;
+//!
+//! let mut reader = Reader::new(std::fs::File::open("bell.wav")?)?;
+//! let mut data = Vec::new();
+//! reader.read_to_end(&mut data)?;
+//! let info = reader.info();
+//! context.upload_sample("bell", &info.spec, Some(&info.map), data, None, None)?;
+//! ```
+//!
+//! [`Format::convert()`]: crate::sample::Format::convert
+//! [`InvalidData`]: std::io::ErrorKind::InvalidData
+//! [`InvalidInput`]: std::io::ErrorKind::InvalidInput
+
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use crate::channelmap::Map;
+use crate::sample::Spec;
+
+pub mod au;
+pub mod wav;
+
+/// Type of sound file.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Kind {
+    /// Microsoft RIFF WAVE.
+    Wav,
+    /// Sun/NeXT AU.
+    Au,
+}
+
+/// Information from the header of a sound file.
+#[derive(Debug, Copy, Clone)]
+pub struct Info {
+    /// Type of file.
+    pub kind: Kind,
+    /// Sample spec of the data.
+    pub spec: Spec,
+    /// Channel map of the data.
+    pub map: Map,
+    /// Length of the sample data, in bytes, or `None` if not given in the header.
+    pub data_len: Option<u64>,
+}
+
+/// Reads the header of a sound file, detecting its type.
+///
+/// Upon success, the reader is left at the start of the sample data.
+pub fn read_header<R: Read>(reader: &mut R) -> io::Result<Info> {
+    let mut magic = [0; 4];
+    reader.read_exact(&mut magic)?;
+    match &magic {
+        b"RIFF" => wav::read_header_body(reader),
+        b".snd" => au::read_header_body(reader),
+        _ => Err(invalid_data("not a WAV or AU file")),
+    }
+}
+
+/// Writes the header of a sound file of the specified type.
+///
+/// With a `data_len` of `None`, the length of the data is left unspecified, as is done for
+/// streaming, which most, but not all, readers accept.
+pub fn write_header<W: Write>(writer: &mut W, kind: Kind, spec: &Spec, map: &Map,
+    data_len: Option<u64>) -> io::Result<()>
+{
+    match kind {
+        Kind::Wav => wav::write_header(writer, spec, map, data_len),
+        Kind::Au => au::write_header(writer, spec, map, data_len),
+    }
+}
+
+/// A reader of a sound file.
+///
+/// Reading gives the sample data of the file, stopping at the end of it, regardless of anything
+/// which may follow it in the file.
+pub struct Reader<R: Read> {
+    /// The underlying reader.
+    inner: R,
+    /// Information from the header.
+    info: Info,
+    /// Bytes of sample data remaining, if known.
+    remaining: Option<u64>,
+}
+
+impl<R: Read> Reader<R> {
+    /// Creates a new reader, reading the header of the file.
+    pub fn new(mut inner: R) -> io::Result<Self> {
+        let info = read_header(&mut inner)?;
+        Ok(Self { inner, info, remaining: info.data_len })
+    }
+
+    /// Gets the information from the header of the file.
+    #[inline]
+    pub fn info(&self) -> &Info {
+        &self.info
+    }
+
+    /// Gets the number of bytes of sample data remaining, if known.
+    #[inline]
+    pub fn remaining(&self) -> Option<u64> {
+        self.remaining
+    }
+
+    /// Gets the underlying reader.
+    #[inline]
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read> Read for Reader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = match self.remaining {
+            Some(remaining) => buf.len().min(usize::try_from(remaining).unwrap_or(usize::MAX)),
+            None => buf.len(),
+        };
+        let read = self.inner.read(&mut buf[..len])?;
+        if let Some(remaining) = self.remaining.as_mut() {
+            *remaining -= read as u64;
+        }
+        Ok(read)
+    }
+}
+
+/// A writer of a sound file.
+///
+/// The header is written upon creation, with the length of the data left unspecified, and then
+/// filled in by [`finish()`](Self::finish). Dropping the writer without finishing it leaves the
+/// file as a streamed one, of unspecified length.
+pub struct Writer<W: Write + Seek> {
+    /// The underlying writer.
+    inner: W,
+    /// Type of file.
+    kind: Kind,
+    /// Sample spec of the data.
+    spec: Spec,
+    /// Channel map of the data.
+    map: Map,
+    /// Position of the start of the header.
+    start: u64,
+    /// Bytes of sample data written.
+    written: u64,
+}
+
+impl<W: Write + Seek> Writer<W> {
+    /// Creates a new writer, writing the header of the file at the current position.
+    ///
+    /// Returns an error of kind [`InvalidInput`] if the spec is not valid, the channel map does not
+    /// match it, or either cannot be stored in a file of this type.
+    ///
+    /// [`InvalidInput`]: std::io::ErrorKind::InvalidInput
+    pub fn new(mut inner: W, kind: Kind, spec: &Spec, map: &Map) -> io::Result<Self> {
+        let start = inner.stream_position()?;
+        write_header(&mut inner, kind, spec, map, None)?;
+        Ok(Self { inner, kind, spec: *spec, map: *map, start, written: 0 })
+    }
+
+    /// Gets the number of bytes of sample data written.
+    #[inline]
+    pub fn written(&self) -> u64 {
+        self.written
+    }
+
+    /// Finishes the file, filling in the length of the data within the header, and returns the
+    /// underlying writer, positioned at the end of the file.
+    pub fn finish(mut self) -> io::Result<W> {
+        if self.kind == Kind::Wav && self.written % 2 != 0 {
+            // Chunks are padded to an even length
+            self.inner.write_all(&[0])?;
+        }
+        let end = self.inner.stream_position()?;
+        self.inner.seek(SeekFrom::Start(self.start))?;
+        write_header(&mut self.inner, self.kind, &self.spec, &self.map, Some(self.written))?;
+        self.inner.seek(SeekFrom::Start(end))?;
+        self.inner.flush()?;
+        Ok(self.inner)
+    }
+}
+
+impl<W: Write + Seek> Write for Writer<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.written += written as u64;
+        Ok(written)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Checks that a spec and channel map are valid, and match, without calling into the PulseAudio
+/// client library, returning the size of a frame.
+fn check_spec(spec: &Spec, map: &Map) -> io::Result<usize> {
+    let size = crate::dsp::sample_size(spec.format)
+        .ok_or_else(|| invalid_input("invalid sample format"))?;
+    match spec.channels {
+        1..=Spec::CHANNELS_MAX if (1..=Spec::RATE_MAX).contains(&spec.rate) => {},
+        _ => return Err(invalid_input("invalid sample spec")),
+    }
+    if map.len() != spec.channels {
+        return Err(invalid_input("channel map does not match sample spec"));
+    }
+    Ok(size * spec.channels as usize)
+}
+
+/// Checks that a spec read from a file is valid.
+fn check_read_spec(spec: &Spec) -> io::Result<()> {
+    match spec.channels {
+        1..=Spec::CHANNELS_MAX if (1..=Spec::RATE_MAX).contains(&spec.rate) => Ok(()),
+        _ => Err(invalid_data("unsupported number of channels or rate")),
+    }
+}
+
+/// Skips over the specified number of bytes.
+fn skip<R: Read>(reader: &mut R, len: u64) -> io::Result<()> {
+    match io::copy(&mut reader.take(len), &mut io::sink())? {
+        copied if copied == len => Ok(()),
+        _ => Err(io::ErrorKind::UnexpectedEof.into()),
+    }
+}
+
+/// Makes an error for a malformed or unsupported file.
+fn invalid_data(msg: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+/// Makes an error for a spec or map which cannot be written.
+fn invalid_input(msg: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, msg)
+}
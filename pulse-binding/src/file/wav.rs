@@ -0,0 +1,368 @@
+// Copyright 2026 Lyndon Brown
+//
+// This file is part of the PulseAudio Rust language binding.
+//
+// Licensed under the MIT license or the Apache license (version 2.0), at your option. You may not
+// copy, modify, or distribute this file except in compliance with said license. You can find copies
+// of these licenses either in the LICENSE-MIT and LICENSE-APACHE files, or alternatively at
+// <http://opensource.org/licenses/MIT> and <http://www.apache.org/licenses/LICENSE-2.0>
+// respectively.
+
+//! WAV files.
+//!
+//! # Overview
+//!
+//! Unsigned 8-bit, signed 16, 24 and 32-bit little endian, 32-bit float little endian, A-law and
+//! μ-law data is supported, in both plain WAV files and `WAVE_FORMAT_EXTENSIBLE` ones.
+//!
+//! The channel mask of an extensible file gives the positions of its channels, those of the set
+//! bits in order, the bits being those of [`CHANNEL_POSITIONS`]. Any channels beyond those of the
+//! mask are mapped to auxiliary positions. Files without a mask get the default order, that of
+//! [`CHANNEL_POSITIONS`], with a single channel being mono. [`to_position_mask()`] and
+//! [`from_position_mask()`] convert between channel masks and the [`PositionMask`] form, as given
+//! by [`Map::get_mask()`].
+//!
+//! The extensible form is written where needed, that is, for more than two channels, for integer
+//! samples of more than 16 bits, or for a channel map other than the default.
+
+use std::io::{self, Read, Write};
+use num_traits::FromPrimitive;
+use crate::channelmap::{Map, Position, PositionMask};
+use crate::sample::{Format, Spec};
+use super::{check_read_spec, check_spec, invalid_data, invalid_input, skip, Info, Kind};
+
+/// Channel positions of the bits of a WAVE channel mask, from the lowest bit.
+pub const CHANNEL_POSITIONS: [Position; 18] = [
+    Position::FrontLeft,
+    Position::FrontRight,
+    Position::FrontCenter,
+    Position::Lfe,
+    Position::RearLeft,
+    Position::RearRight,
+    Position::FrontLeftOfCenter,
+    Position::FrontRightOfCenter,
+    Position::RearCenter,
+    Position::SideLeft,
+    Position::SideRight,
+    Position::TopCenter,
+    Position::TopFrontLeft,
+    Position::TopFrontCenter,
+    Position::TopFrontRight,
+    Position::TopRearLeft,
+    Position::TopRearCenter,
+    Position::TopRearRight,
+];
+
+/// Format tag of integer PCM data.
+const FORMAT_PCM: u16 = 0x0001;
+/// Format tag of floating point data.
+const FORMAT_IEEE_FLOAT: u16 = 0x0003;
+/// Format tag of A-law data.
+const FORMAT_ALAW: u16 = 0x0006;
+/// Format tag of μ-law data.
+const FORMAT_MULAW: u16 = 0x0007;
+/// Format tag of the extensible form, with the actual format tag within the sub-format GUID.
+const FORMAT_EXTENSIBLE: u16 = 0xfffe;
+
+/// The sub-format GUID of the extensible form, following the format tag in its first two bytes.
+const SUBFORMAT_GUID_TAIL: [u8; 14] =
+    [0x00, 0x00, 0x00, 0x00, 0x10, 0x00, 0x80, 0x00, 0x00, 0xaa, 0x00, 0x38, 0x9b, 0x71];
+
+/// Converts a WAVE channel mask to a position mask.
+pub fn to_position_mask(mask: u32) -> PositionMask {
+    CHANNEL_POSITIONS.iter().enumerate()
+        .filter(|(bit, _)| mask & (1 << bit) != 0)
+        .fold(0, |acc, (_, p)| acc | p.to_mask())
+}
+
+/// Converts a position mask, such as that given by [`Map::get_mask()`], to a WAVE channel mask.
+///
+/// Positions which have no counterpart in WAVE channel masks, such as mono and the auxiliary
+/// positions, are dropped.
+pub fn from_position_mask(mask: PositionMask) -> u32 {
+    CHANNEL_POSITIONS.iter().enumerate()
+        .filter(|(_, p)| mask & p.to_mask() != 0)
+        .fold(0, |acc, (bit, _)| acc | 1 << bit)
+}
+
+/// Gets the channel map given by a WAVE channel mask, for the specified number of channels.
+///
+/// A mask of zero gives the default channel map.
+pub fn channel_map(mask: u32, channels: u8) -> Map {
+    let mask = match mask {
+        0 if channels == 1 => return positions_map(&[Position::Mono], 1),
+        0 => !0,
+        _ => mask,
+    };
+    let mut positions = CHANNEL_POSITIONS.iter().enumerate()
+        .filter(|(bit, _)| mask & (1 << bit) != 0)
+        .map(|(_, p)| *p);
+    let mut map = Map::default();
+    map.set_len(channels);
+    let mut aux = Position::Aux0 as i32;
+    for p in map.get_mut() {
+        *p = positions.next().unwrap_or_else(|| {
+            aux += 1;
+            Position::from_i32(aux - 1).unwrap_or(Position::Aux31)
+        });
+    }
+    map
+}
+
+/// Gets the WAVE channel mask expressing a channel map.
+///
+/// Returns `None` if the channel map cannot be expressed, which is the case where channels with
+/// positions which have a counterpart in WAVE channel masks are not in the order of the mask, or
+/// come after any which do not. Channels without such a position are given no bit in the mask.
+pub fn channel_mask(map: &Map) -> Option<u32> {
+    let mut mask = 0u32;
+    let mut unmasked = false;
+    for p in map.get() {
+        match CHANNEL_POSITIONS.iter().position(|w| w == p) {
+            Some(bit) if !unmasked && mask >> bit == 0 => mask |= 1 << bit,
+            Some(_) => return None,
+            None => unmasked = true,
+        }
+    }
+    Some(mask)
+}
+
+/// Reads the header of a WAV file, as with [`file::read_header()`], but failing for any other type
+/// of file.
+///
+/// Upon success, the reader is left at the start of the sample data.
+///
+/// [`file::read_header()`]: super::read_header
+pub fn read_header<R: Read>(reader: &mut R) -> io::Result<Info> {
+    match super::read_header(reader)? {
+        info if info.kind == Kind::Wav => Ok(info),
+        _ => Err(invalid_data("not a WAV file")),
+    }
+}
+
+/// Reads the header of a WAV file, following the magic number.
+pub(super) fn read_header_body<R: Read>(reader: &mut R) -> io::Result<Info> {
+    let mut riff = [0; 8];
+    reader.read_exact(&mut riff)?;
+    if &riff[4..] != b"WAVE" {
+        return Err(invalid_data("not a WAV file"));
+    }
+
+    let mut format = None;
+    loop {
+        let mut chunk = [0; 8];
+        reader.read_exact(&mut chunk)?;
+        let len = u32::from_le_bytes([chunk[4], chunk[5], chunk[6], chunk[7]]);
+        match (&chunk[..4], format) {
+            (b"fmt ", _) => {
+                let mut fmt = [0; 40];
+                let read = (len as usize).min(fmt.len());
+                reader.read_exact(&mut fmt[..read])?;
+                skip(reader, u64::from(len) - read as u64 + u64::from(len % 2))?;
+                format = Some(parse_format(&fmt[..read])?);
+            },
+            (b"data", Some((spec, map))) => {
+                let data_len = match len {
+                    u32::MAX => None,
+                    _ => Some(u64::from(len)),
+                };
+                return Ok(Info { kind: Kind::Wav, spec, map, data_len });
+            },
+            (b"data", None) => return Err(invalid_data("data chunk precedes format chunk")),
+            _ => skip(reader, u64::from(len) + u64::from(len % 2))?,
+        }
+    }
+}
+
+/// Writes the header of a WAV file.
+///
+/// With a `data_len` of `None`, the lengths within the header are left unspecified, as is done for
+/// streaming.
+///
+/// Returns an error of kind [`InvalidInput`] if the spec is not valid, the channel map does not
+/// match it, or either cannot be stored in a WAV file, or if the data is too long.
+///
+/// [`InvalidInput`]: std::io::ErrorKind::InvalidInput
+pub fn write_header<W: Write>(writer: &mut W, spec: &Spec, map: &Map, data_len: Option<u64>)
+    -> io::Result<()>
+{
+    let frame_size = check_spec(spec, map)?;
+    let (tag, size) = match spec.format {
+        Format::U8 => (FORMAT_PCM, 1),
+        Format::S16le => (FORMAT_PCM, 2),
+        Format::S24le => (FORMAT_PCM, 3),
+        Format::S32le => (FORMAT_PCM, 4),
+        Format::F32le => (FORMAT_IEEE_FLOAT, 4),
+        Format::ALaw => (FORMAT_ALAW, 1),
+        Format::ULaw => (FORMAT_MULAW, 1),
+        _ => return Err(invalid_input("sample format cannot be stored in a WAV file")),
+    };
+    let is_default = map.get() == channel_map(0, spec.channels).get();
+    let extensible = spec.channels > 2 || (tag == FORMAT_PCM && size > 2) || !is_default;
+    let mask = channel_mask(map)
+        .ok_or_else(|| invalid_input("channel map cannot be expressed in a WAV file"))?;
+
+    let mut fmt = Vec::with_capacity(40);
+    fmt.extend_from_slice(&match extensible {
+        true => FORMAT_EXTENSIBLE,
+        false => tag,
+    }.to_le_bytes());
+    fmt.extend_from_slice(&u16::from(spec.channels).to_le_bytes());
+    fmt.extend_from_slice(&spec.rate.to_le_bytes());
+    fmt.extend_from_slice(&(spec.rate * frame_size as u32).to_le_bytes());
+    fmt.extend_from_slice(&(frame_size as u16).to_le_bytes());
+    fmt.extend_from_slice(&(size as u16 * 8).to_le_bytes());
+    if extensible {
+        fmt.extend_from_slice(&22u16.to_le_bytes());
+        fmt.extend_from_slice(&(size as u16 * 8).to_le_bytes());
+        fmt.extend_from_slice(&mask.to_le_bytes());
+        fmt.extend_from_slice(&tag.to_le_bytes());
+        fmt.extend_from_slice(&SUBFORMAT_GUID_TAIL);
+    } else if tag != FORMAT_PCM {
+        fmt.extend_from_slice(&0u16.to_le_bytes());
+    }
+
+    let (riff_len, data_len) = match data_len {
+        Some(len) => {
+            let riff_len = 4 + 8 + fmt.len() as u64 + 8 + len + len % 2;
+            match (u32::try_from(riff_len), u32::try_from(len)) {
+                (Ok(riff_len), Ok(len)) => (riff_len, len),
+                _ => return Err(invalid_input("too much data for a WAV file")),
+            }
+        },
+        None => (u32::MAX, u32::MAX),
+    };
+
+    let mut header = Vec::with_capacity(fmt.len() + 28);
+    header.extend_from_slice(b"RIFF");
+    header.extend_from_slice(&riff_len.to_le_bytes());
+    header.extend_from_slice(b"WAVEfmt ");
+    header.extend_from_slice(&(fmt.len() as u32).to_le_bytes());
+    header.extend_from_slice(&fmt);
+    header.extend_from_slice(b"data");
+    header.extend_from_slice(&data_len.to_le_bytes());
+    writer.write_all(&header)
+}
+
+/// Parses the content of a format chunk.
+fn parse_format(fmt: &[u8]) -> io::Result<(Spec, Map)> {
+    if fmt.len() < 16 {
+        return Err(invalid_data("format chunk too short"));
+    }
+    let u16_at = |i: usize| u16::from_le_bytes([fmt[i], fmt[i + 1]]);
+    let u32_at = |i: usize| u32::from_le_bytes([fmt[i], fmt[i + 1], fmt[i + 2], fmt[i + 3]]);
+
+    let channels = u8::try_from(u16_at(2))
+        .map_err(|_| invalid_data("unsupported number of channels or rate"))?;
+    let rate = u32_at(4);
+    let block_align = u16_at(12) as usize;
+    let bits = u16_at(14) as usize;
+    let (tag, mask) = match u16_at(0) {
+        FORMAT_EXTENSIBLE if fmt.len() >= 40 && fmt[26..] == SUBFORMAT_GUID_TAIL => {
+            (u16_at(24), u32_at(20))
+        },
+        FORMAT_EXTENSIBLE => return Err(invalid_data("unsupported sample format")),
+        tag => (tag, 0),
+    };
+
+    let size = match channels {
+        0 => 0,
+        _ => block_align / channels as usize,
+    };
+    if size * channels as usize != block_align || bits == 0 || bits > size * 8 {
+        return Err(invalid_data("invalid block alignment"));
+    }
+    let format = match (tag, size) {
+        (FORMAT_PCM, 1) => Format::U8,
+        (FORMAT_PCM, 2) => Format::S16le,
+        (FORMAT_PCM, 3) => Format::S24le,
+        // Samples are aligned to the most significant bits, thus 24 bits within 32 are as 32
+        (FORMAT_PCM, 4) => Format::S32le,
+        (FORMAT_IEEE_FLOAT, 4) => Format::F32le,
+        (FORMAT_ALAW, 1) => Format::ALaw,
+        (FORMAT_MULAW, 1) => Format::ULaw,
+        _ => return Err(invalid_data("unsupported sample format")),
+    };
+    let spec = Spec { format, channels, rate };
+    check_read_spec(&spec)?;
+    Ok((spec, channel_map(mask, channels)))
+}
+
+/// Gets a channel map with the specified positions.
+fn positions_map(positions: &[Position], channels: u8) -> Map {
+    let mut map = Map::default();
+    map.set_len(channels);
+    map.get_mut().copy_from_slice(positions);
+    map
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+    use crate::file::{Reader, Writer};
+
+    /// Check conversion between channel masks and channel maps
+    #[test]
+    fn channel_masks() {
+        let map = channel_map(0x3f, 6);
+        assert_eq!(map.get(), &[Position::FrontLeft, Position::FrontRight, Position::FrontCenter,
+            Position::Lfe, Position::RearLeft, Position::RearRight]);
+        assert_eq!(channel_mask(&map), Some(0x3f));
+
+        // Side channels rather than rear, and more channels than bits
+        let map = channel_map(0x607, 6);
+        assert_eq!(&map.get()[3..], &[Position::SideLeft, Position::SideRight, Position::Aux0]);
+        assert_eq!(channel_mask(&map), Some(0x607));
+
+        assert_eq!(channel_map(0, 1).get(), &[Position::Mono]);
+        assert_eq!(channel_mask(&channel_map(0, 1)), Some(0));
+        assert_eq!(channel_mask(&positions_map(&[Position::FrontRight, Position::FrontLeft], 2)),
+            None);
+
+        let mask = Position::FrontLeft.to_mask() | Position::TopRearRight.to_mask()
+            | Position::Aux3.to_mask();
+        assert_eq!(from_position_mask(mask), 0x20001);
+        assert_eq!(to_position_mask(0x20001), mask & !Position::Aux3.to_mask());
+    }
+
+    /// Check writing and reading back files, plain and extensible
+    #[test]
+    fn round_trip() {
+        let spec = Spec { format: Format::S16le, channels: 2, rate: 44100 };
+        let map = channel_map(0, 2);
+        let mut writer = Writer::new(Cursor::new(Vec::new()), Kind::Wav, &spec, &map).unwrap();
+        writer.write_all(&[1, 2, 3, 4]).unwrap();
+        let file = writer.finish().unwrap().into_inner();
+        assert_eq!(file.len(), 44 + 4);
+        assert_eq!(&file[20..22], &[1, 0]);
+
+        let mut reader = Reader::new(Cursor::new(file)).unwrap();
+        let info = *reader.info();
+        assert_eq!((info.kind, info.spec.format), (Kind::Wav, Format::S16le));
+        assert_eq!((info.spec.channels, info.spec.rate), (2, 44100));
+        assert_eq!(info.data_len, Some(4));
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data).unwrap();
+        assert_eq!(data, [1, 2, 3, 4]);
+
+        // Mono 24-bit, with a trailing chunk which must not be read as data
+        let spec = Spec { format: Format::S24le, channels: 1, rate: 48000 };
+        let map = channel_map(0, 1);
+        let mut file = Vec::new();
+        write_header(&mut file, &spec, &map, Some(3)).unwrap();
+        file.extend_from_slice(&[5, 6, 7, 0]);
+        file.extend_from_slice(b"LIST\0\0\0\0");
+        assert_eq!(&file[20..22], &[0xfe, 0xff]);
+
+        let mut reader = Reader::new(Cursor::new(file)).unwrap();
+        assert_eq!(reader.info().map.get(), &[Position::Mono]);
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data).unwrap();
+        assert_eq!(data, [5, 6, 7]);
+
+        let spec = Spec { format: Format::S16be, channels: 1, rate: 48000 };
+        let error = write_header(&mut Vec::new(), &spec, &map, None).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+    }
+}
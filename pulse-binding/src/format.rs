@@ -34,6 +34,7 @@ pub use capi::pa_prop_type_t as PropType;
 #[non_exhaustive]
 #[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
 #[derive(FromPrimitive, ToPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[allow(non_camel_case_types)]
 pub enum Encoding {
     /* NOTE: This enum’s variants and variant values **must** remain identical to the `sys` crate
@@ -614,3 +615,53 @@ impl Clone for Info {
         self.to_owned()
     }
 }
+
+/// Serialized as a structure holding the encoding and the properties.
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl serde::Serialize for Info {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+        let mut state = serializer.serialize_struct("Info", 2)?;
+        state.serialize_field("encoding", &self.get_encoding())?;
+        state.serialize_field("properties", self.get_properties())?;
+        state.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<'de> serde::Deserialize<'de> for Info {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(serde::Deserialize)]
+        #[serde(rename = "Info")]
+        struct Fields {
+            encoding: Encoding,
+            properties: Proplist,
+        }
+
+        let fields = Fields::deserialize(deserializer)?;
+        let mut info = Info::new()
+            .ok_or_else(|| serde::de::Error::custom("failed to create format info object"))?;
+        info.set_encoding(fields.encoding);
+        info.get_properties_mut().merge(&fields.properties, crate::proplist::UpdateMode::Set);
+        Ok(info)
+    }
+}
+
+/// Check serialization round-trips, with the encoding and properties
+#[cfg(feature = "serde")]
+#[test]
+fn info_serde() {
+    let mut info = Info::new().unwrap();
+    info.set_encoding(Encoding::AC3_IEC61937);
+    info.set_rate(48000);
+    let value = serde_json::to_value(&info).unwrap();
+    assert_eq!(value["encoding"], serde_json::json!("AC3_IEC61937"));
+
+    let back = serde_json::from_value::<Info>(value).unwrap();
+    assert_eq!(back.get_encoding(), Encoding::AC3_IEC61937);
+    let rate = back.get_properties().get_str(crate::proplist::properties::FORMAT_RATE);
+    assert_eq!(rate.as_deref(), Some("48000"));
+    assert!(serde_json::from_str::<Info>(r#"{ "encoding": "AC3_IEC61937" }"#).is_err());
+}
@@ -62,7 +62,8 @@
 //! [`mainloop`](mod@mainloop) module.
 //!
 //! By using the built-in threaded main loop, it is possible to achieve a pseudo-synchronous API,
-//! which can be useful in synchronous applications where the simple API is insufficient.
+//! which can be useful in synchronous applications where the simple API is insufficient. The
+//! [`blocking`](mod@blocking) module offers a ready made client of this kind for common tasks.
 //!
 //! ## Threads
 //!
@@ -168,16 +169,24 @@
 
 extern crate libpulse_sys as capi;
 
+pub mod blocking;
+pub mod buffer;
 pub mod callbacks;
+pub mod capture;
 pub mod channelmap;
+pub mod clock;
 pub mod context;
 pub mod def;
 pub mod direction;
+pub mod dsp;
 pub mod error;
+pub mod file;
 pub mod format;
 pub mod mainloop;
 pub mod operation;
+pub mod playback;
 pub mod proplist;
+pub mod ringbuffer;
 pub mod sample;
 pub mod stream;
 pub mod time;
@@ -0,0 +1,631 @@
+// Copyright 2026 Lyndon Brown
+//
+// This file is part of the PulseAudio Rust language binding.
+//
+// Licensed under the MIT license or the Apache license (version 2.0), at your option. You may not
+// copy, modify, or distribute this file except in compliance with said license. You can find copies
+// of these licenses either in the LICENSE-MIT and LICENSE-APACHE files, or alternatively at
+// <http://opensource.org/licenses/MIT> and <http://www.apache.org/licenses/LICENSE-2.0>
+// respectively.
+
+//! Custom main loop implementations.
+//!
+//! # Overview
+//!
+//! This module allows the main loop abstraction ([`mainloop::api`](mod@crate::mainloop::api)) to
+//! be backed by an event loop of your own choosing (for instance one based upon `mio` or
+//! `calloop`), without having to deal with the raw [`MainloopApi`] vtable.
+//!
+//! All that is required is an implementation of the safe [`CustomMainloopBackend`] trait. The
+//! [`Mainloop`] wrapper takes care of building a valid vtable around it, keeping track of the
+//! event sources the PulseAudio client library creates, including their callbacks, userdata and
+//! destroy callbacks. The backend is simply told, through its trait methods, which file
+//! descriptors to watch, when timers are due, and which deferred events are enabled, with each
+//! event source being identified by a [`SourceId`].
+//!
+//! When the backend’s event loop notices that an event source is ready, it must then call the
+//! corresponding dispatch method of the [`Mainloop`] ([`Mainloop::dispatch_io()`],
+//! [`Mainloop::dispatch_timer()`] or [`Mainloop::dispatch_defer()`]), which executes its callback.
+//!
+//! Since [`Mainloop`] implements the [`Mainloop`](crate::mainloop::api::Mainloop) trait, it can be
+//! used to create a [`Context`](crate::context::Context) just like any other main loop.
+//!
+//! # Re-entrancy
+//!
+//! Callbacks executed during dispatch commonly create, modify and free event sources, which results
+//! in calls to the backend’s trait methods. The backend is held within a [`RefCell`], thus you must
+//! not hold a borrow of it (obtained with [`Mainloop::backend()`] or [`Mainloop::backend_mut()`])
+//! while calling any of the dispatch methods, or otherwise calling into the PulseAudio client
+//! library, else a panic will occur.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! let mainloop = custom::Mainloop::new(MyBackend::new());
+//! let mut context = Context::new(&mainloop, "FooApp").expect("Failed to create new context");
+//! context.connect(None, ContextFlagSet::NOFLAGS, None).expect("Failed to connect context");
+//!
+//! loop {
+//!     // Wait for readiness with our own event loop, collecting what is ready
+//!     let ready = mainloop.backend_mut().wait();
+//!     for event in ready {
+//!         match event {
+//!             Ready::Io(id, events) => { mainloop.dispatch_io(id, events); },
+//!             Ready::Timer(id) => { mainloop.dispatch_timer(id); },
+//!             Ready::Defer(id) => { mainloop.dispatch_defer(id); },
+//!         }
+//!     }
+//! }
+//! ```
+
+use std::cell::{Cell, Ref, RefCell, RefMut};
+use std::collections::HashMap;
+use std::os::raw::c_void;
+use std::ptr::null_mut;
+use std::rc::Rc;
+use libc::timeval;
+use crate::def;
+use crate::mainloop::api::{MainloopInternalType, MainloopInner, MainloopInnerType, MainloopApi,
+    IoEventCb, IoEventDestroyCb, TimeEventCb, TimeEventDestroyCb, DeferEventCb, DeferEventDestroyCb,
+    Mainloop as MainloopTrait};
+use crate::mainloop::events::io::{IoEventInternal, FlagSet as IoEventFlagSet};
+use crate::mainloop::events::timer::TimeEventInternal;
+use crate::mainloop::events::deferred::DeferEventInternal;
+use crate::mainloop::signal::MainloopSignals;
+use crate::time::{Timeval, UnixTs};
+
+/// Identifier of an event source of a custom main loop.
+///
+/// Identifiers are unique across all event source types, and are never reused within the lifetime
+/// of the main loop.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SourceId(pub u64);
+
+/// An event loop backend for a custom main loop.
+///
+/// Note that all event sources are created in an enabled state, as per the parameters given to
+/// their `_new()` method.
+pub trait CustomMainloopBackend {
+    /// Starts watching the file descriptor `fd` for the specified events.
+    ///
+    /// Hangup and error conditions should always be reported, even when not requested. Once the
+    /// descriptor is ready, [`Mainloop::dispatch_io()`] must be called with the events that
+    /// occurred.
+    ///
+    /// Returns `false` if the descriptor cannot be watched, which fails the event source creation.
+    fn io_new(&mut self, id: SourceId, fd: i32, events: IoEventFlagSet) -> bool;
+
+    /// Changes the set of events watched for on an IO event source.
+    ///
+    /// An empty set disables the event source.
+    fn io_enable(&mut self, id: SourceId, events: IoEventFlagSet);
+
+    /// Stops watching an IO event source, which will not be used again.
+    fn io_free(&mut self, id: SourceId);
+
+    /// Arms a timer for the specified Unix time, or disarms it if `None`.
+    ///
+    /// Timers are one-shot. Once the time is reached, [`Mainloop::dispatch_timer()`] must be
+    /// called, after which the timer is considered disarmed until next restarted.
+    fn time_new(&mut self, id: SourceId, time: Option<UnixTs>);
+
+    /// Re-arms a running or expired timer for the specified Unix time, or disarms it if `None`.
+    fn time_restart(&mut self, id: SourceId, time: Option<UnixTs>);
+
+    /// Removes a timer, which will not be used again.
+    fn time_free(&mut self, id: SourceId);
+
+    /// Adds a deferred event source, which starts out enabled.
+    ///
+    /// For as long as it remains enabled, [`Mainloop::dispatch_defer()`] must be called for it on
+    /// each iteration of the event loop, without blocking.
+    fn defer_new(&mut self, id: SourceId);
+
+    /// Enables or disables a deferred event source.
+    fn defer_enable(&mut self, id: SourceId, enable: bool);
+
+    /// Removes a deferred event source, which will not be used again.
+    fn defer_free(&mut self, id: SourceId);
+
+    /// Requests that the event loop exits, with the specified return value.
+    fn quit(&mut self, retval: def::Retval);
+}
+
+/// The Rust side main loop state, to which the API vtable’s `userdata` points.
+pub struct MainloopInternal<B: CustomMainloopBackend> {
+    /// The API vtable handed out to the C library.
+    api: MainloopApi,
+    /// The user’s backend.
+    backend: RefCell<B>,
+    /// Next event source identifier.
+    next_id: Cell<u64>,
+    /// Live IO event sources.
+    io_events: RefCell<HashMap<SourceId, *mut IoSource<B>>>,
+    /// Live timer event sources.
+    time_events: RefCell<HashMap<SourceId, *mut TimeSource<B>>>,
+    /// Live deferred event sources.
+    defer_events: RefCell<HashMap<SourceId, *mut DeferSource<B>>>,
+    /// Freed event sources, awaiting destruction once no longer dispatching.
+    dead: RefCell<Vec<DeadSource<B>>>,
+    /// Dispatch nesting depth.
+    dispatching: Cell<u32>,
+}
+
+impl<B: CustomMainloopBackend> MainloopInternalType for MainloopInternal<B> {}
+
+struct IoSource<B: CustomMainloopBackend> {
+    mainloop: *mut MainloopInternal<B>,
+    id: SourceId,
+    fd: i32,
+    cb: Option<IoEventCb>,
+    userdata: *mut c_void,
+    destroy: Option<IoEventDestroyCb>,
+}
+
+struct TimeSource<B: CustomMainloopBackend> {
+    mainloop: *mut MainloopInternal<B>,
+    id: SourceId,
+    tv: Option<timeval>,
+    cb: Option<TimeEventCb>,
+    userdata: *mut c_void,
+    destroy: Option<TimeEventDestroyCb>,
+}
+
+struct DeferSource<B: CustomMainloopBackend> {
+    mainloop: *mut MainloopInternal<B>,
+    id: SourceId,
+    enabled: bool,
+    cb: Option<DeferEventCb>,
+    userdata: *mut c_void,
+    destroy: Option<DeferEventDestroyCb>,
+}
+
+enum DeadSource<B: CustomMainloopBackend> {
+    Io(*mut IoSource<B>),
+    Time(*mut TimeSource<B>),
+    Defer(*mut DeferSource<B>),
+}
+
+/// This acts as a safe interface to a custom main loop.
+///
+/// The mainloop object pointers are further enclosed here in a ref counted wrapper, allowing this
+/// outer wrapper to have clean methods for creating event objects, which can cleanly pass a copy of
+/// the inner ref counted mainloop object to them. Giving this to events serves two purposes,
+/// firstly because they need the API pointer, secondly, it ensures that event objects do not
+/// outlive the mainloop object.
+pub struct Mainloop<B: CustomMainloopBackend + 'static> {
+    /// The ref-counted inner data.
+    pub _inner: Rc<MainloopInner<MainloopInternal<B>>>,
+}
+
+impl<B: CustomMainloopBackend + 'static> MainloopTrait for Mainloop<B> {
+    type MI = MainloopInner<MainloopInternal<B>>;
+
+    #[inline(always)]
+    fn inner(&self) -> Rc<MainloopInner<MainloopInternal<B>>> {
+        Rc::clone(&self._inner)
+    }
+}
+
+impl<B: CustomMainloopBackend + 'static> MainloopSignals for Mainloop<B> {}
+
+/// Drop function for MainloopInner<MainloopInternal>.
+fn drop_actual<B: CustomMainloopBackend>(self_: &mut MainloopInner<MainloopInternal<B>>) {
+    drop(unsafe { Box::from_raw(self_.get_ptr()) });
+}
+
+impl<B: CustomMainloopBackend + 'static> Mainloop<B> {
+    /// Creates a new main loop object, backed by the specified backend.
+    pub fn new(backend: B) -> Self {
+        let internal = Box::new(MainloopInternal {
+            api: MainloopApi {
+                userdata: null_mut::<c_void>(),
+                io_new: Some(io_new::<B>),
+                io_enable: Some(io_enable::<B>),
+                io_free: Some(io_free::<B>),
+                io_set_destroy: Some(io_set_destroy::<B>),
+                time_new: Some(time_new::<B>),
+                time_restart: Some(time_restart::<B>),
+                time_free: Some(time_free::<B>),
+                time_set_destroy: Some(time_set_destroy::<B>),
+                defer_new: Some(defer_new::<B>),
+                defer_enable: Some(defer_enable::<B>),
+                defer_free: Some(defer_free::<B>),
+                defer_set_destroy: Some(defer_set_destroy::<B>),
+                quit: Some(quit::<B>),
+            },
+            backend: RefCell::new(backend),
+            next_id: Cell::new(0),
+            io_events: RefCell::new(HashMap::new()),
+            time_events: RefCell::new(HashMap::new()),
+            defer_events: RefCell::new(HashMap::new()),
+            dead: RefCell::new(Vec::new()),
+            dispatching: Cell::new(0),
+        });
+        let ptr = Box::into_raw(internal);
+        let api_ptr = unsafe {
+            (*ptr).api.userdata = ptr as *mut c_void;
+            &(*ptr).api as *const MainloopApi
+        };
+        let ml_inner = unsafe {
+            MainloopInner::<MainloopInternal<B>>::new(ptr, api_ptr, drop_actual::<B>, false)
+        };
+        Self { _inner: Rc::new(ml_inner) }
+    }
+
+    /// Gets the abstract main loop abstraction layer vtable for this main loop.
+    ///
+    /// No need to free the API as it is owned by the loop and is destroyed when the loop is freed.
+    ///
+    /// Talking to PA directly with C requires fetching this pointer explicitly via this function.
+    /// This is actually unnecessary through this binding. The pointer is retrieved automatically
+    /// where needed.
+    pub fn get_api<'a>(&self) -> &'a MainloopApi {
+        let ptr = self._inner.get_api_ptr();
+        assert!(!ptr.is_null());
+        unsafe { &*ptr }
+    }
+
+    /// Gets a reference to the backend.
+    ///
+    /// Panics if the backend is currently mutably borrowed. See the
+    /// [re-entrancy notes](mod@self#re-entrancy).
+    #[inline]
+    pub fn backend(&self) -> Ref<'_, B> {
+        self.internal().backend.borrow()
+    }
+
+    /// Gets a mutable reference to the backend.
+    ///
+    /// Panics if the backend is currently borrowed. See the
+    /// [re-entrancy notes](mod@self#re-entrancy).
+    #[inline]
+    pub fn backend_mut(&self) -> RefMut<'_, B> {
+        self.internal().backend.borrow_mut()
+    }
+
+    /// Dispatches an IO event source, with the events that occurred.
+    ///
+    /// Returns `false` if no such event source exists (any longer).
+    pub fn dispatch_io(&self, id: SourceId, events: IoEventFlagSet) -> bool {
+        let ml = self._inner.get_ptr();
+        let e = match self.internal().io_events.borrow().get(&id) {
+            Some(&e) => e,
+            None => return false,
+        };
+        let _guard = DispatchGuard::new(ml);
+        unsafe {
+            if let Some(cb) = (*e).cb {
+                cb(&(*ml).api, e as *mut IoEventInternal, (*e).fd, events, (*e).userdata);
+            }
+        }
+        true
+    }
+
+    /// Dispatches a timer event source.
+    ///
+    /// The timer is disarmed prior to executing its callback.
+    ///
+    /// Returns `false` if no such event source exists (any longer), or if it is not armed.
+    pub fn dispatch_timer(&self, id: SourceId) -> bool {
+        let ml = self._inner.get_ptr();
+        let e = match self.internal().time_events.borrow().get(&id) {
+            Some(&e) => e,
+            None => return false,
+        };
+        let _guard = DispatchGuard::new(ml);
+        unsafe {
+            let tv = match (*e).tv.take() {
+                Some(tv) => tv,
+                None => return false,
+            };
+            if let Some(cb) = (*e).cb {
+                cb(&(*ml).api, e as *mut TimeEventInternal, &tv, (*e).userdata);
+            }
+        }
+        true
+    }
+
+    /// Dispatches a deferred event source.
+    ///
+    /// Returns `false` if no such event source exists (any longer), or if it is disabled.
+    pub fn dispatch_defer(&self, id: SourceId) -> bool {
+        let ml = self._inner.get_ptr();
+        let e = match self.internal().defer_events.borrow().get(&id) {
+            Some(&e) => e,
+            None => return false,
+        };
+        let _guard = DispatchGuard::new(ml);
+        unsafe {
+            if !(*e).enabled {
+                return false;
+            }
+            if let Some(cb) = (*e).cb {
+                cb(&(*ml).api, e as *mut DeferEventInternal, (*e).userdata);
+            }
+        }
+        true
+    }
+
+    #[inline]
+    fn internal(&self) -> &MainloopInternal<B> {
+        unsafe { &*self._inner.get_ptr() }
+    }
+}
+
+/// Tracks dispatch nesting, destroying freed event sources once the outermost dispatch completes.
+struct DispatchGuard<B: CustomMainloopBackend>(*mut MainloopInternal<B>);
+
+impl<B: CustomMainloopBackend> DispatchGuard<B> {
+    fn new(ml: *mut MainloopInternal<B>) -> Self {
+        unsafe { (*ml).dispatching.set((*ml).dispatching.get() + 1) };
+        Self(ml)
+    }
+}
+
+impl<B: CustomMainloopBackend> Drop for DispatchGuard<B> {
+    fn drop(&mut self) {
+        unsafe {
+            (*self.0).dispatching.set((*self.0).dispatching.get() - 1);
+            MainloopInternal::cleanup(self.0);
+        }
+    }
+}
+
+impl<B: CustomMainloopBackend> MainloopInternal<B> {
+    /// Gets the main loop object from the API vtable.
+    #[inline]
+    fn from_api(a: *const MainloopApi) -> *mut Self {
+        assert!(!a.is_null());
+        unsafe { (*a).userdata as *mut Self }
+    }
+
+    /// Allocates a new event source identifier.
+    fn new_id(&self) -> SourceId {
+        let id = self.next_id.get();
+        self.next_id.set(id + 1);
+        SourceId(id)
+    }
+
+    /// Queues a freed event source for destruction, destroying it right away if not dispatching.
+    unsafe fn bury(ml: *mut Self, source: DeadSource<B>) {
+        (*ml).dead.borrow_mut().push(source);
+        Self::cleanup(ml);
+    }
+
+    /// Destroys freed event sources, unless currently dispatching.
+    unsafe fn cleanup(ml: *mut Self) {
+        if (*ml).dispatching.get() != 0 {
+            return;
+        }
+        let api = &(*ml).api as *const MainloopApi;
+        loop {
+            // Destroy callbacks could potentially free further event sources
+            let dead = (*ml).dead.borrow_mut().pop();
+            match dead {
+                Some(DeadSource::Io(e)) => {
+                    if let Some(destroy) = (*e).destroy {
+                        destroy(api, e as *mut IoEventInternal, (*e).userdata);
+                    }
+                    drop(Box::from_raw(e));
+                },
+                Some(DeadSource::Time(e)) => {
+                    if let Some(destroy) = (*e).destroy {
+                        destroy(api, e as *mut TimeEventInternal, (*e).userdata);
+                    }
+                    drop(Box::from_raw(e));
+                },
+                Some(DeadSource::Defer(e)) => {
+                    if let Some(destroy) = (*e).destroy {
+                        destroy(api, e as *mut DeferEventInternal, (*e).userdata);
+                    }
+                    drop(Box::from_raw(e));
+                },
+                None => break,
+            }
+        }
+    }
+}
+
+impl<B: CustomMainloopBackend> Drop for MainloopInternal<B> {
+    fn drop(&mut self) {
+        // Any event sources not yet freed by their owners are destroyed along with the main loop.
+        let ml = self as *mut Self;
+        let mut dead = self.dead.borrow_mut();
+        dead.extend(self.io_events.borrow_mut().drain().map(|(_, e)| DeadSource::Io(e)));
+        dead.extend(self.time_events.borrow_mut().drain().map(|(_, e)| DeadSource::Time(e)));
+        dead.extend(self.defer_events.borrow_mut().drain().map(|(_, e)| DeadSource::Defer(e)));
+        drop(dead);
+        unsafe { Self::cleanup(ml) };
+    }
+}
+
+/// Converts a C time value to a Unix timestamp, with a null pointer meaning disarmed.
+fn time_from_timeval(tv: *const timeval) -> Option<UnixTs> {
+    match tv.is_null() {
+        true => None,
+        false => Some(UnixTs(Timeval(unsafe { *tv }))),
+    }
+}
+
+extern "C"
+fn io_new<B: CustomMainloopBackend>(a: *const MainloopApi, fd: i32, events: IoEventFlagSet,
+    cb: Option<IoEventCb>, userdata: *mut c_void) -> *mut IoEventInternal
+{
+    let ml = MainloopInternal::<B>::from_api(a);
+    unsafe {
+        let id = (*ml).new_id();
+        if !(*ml).backend.borrow_mut().io_new(id, fd, events) {
+            return null_mut::<IoEventInternal>();
+        }
+        let e = Box::into_raw(Box::new(IoSource {
+            mainloop: ml, id, fd, cb, userdata, destroy: None,
+        }));
+        (*ml).io_events.borrow_mut().insert(id, e);
+        e as *mut IoEventInternal
+    }
+}
+
+extern "C"
+fn io_enable<B: CustomMainloopBackend>(e: *mut IoEventInternal, events: IoEventFlagSet) {
+    let e = e as *mut IoSource<B>;
+    unsafe { (*(*e).mainloop).backend.borrow_mut().io_enable((*e).id, events) };
+}
+
+extern "C"
+fn io_free<B: CustomMainloopBackend>(e: *mut IoEventInternal) {
+    let e = e as *mut IoSource<B>;
+    unsafe {
+        let ml = (*e).mainloop;
+        (*ml).io_events.borrow_mut().remove(&(*e).id);
+        (*ml).backend.borrow_mut().io_free((*e).id);
+        MainloopInternal::bury(ml, DeadSource::Io(e));
+    }
+}
+
+extern "C"
+fn io_set_destroy<B: CustomMainloopBackend>(e: *mut IoEventInternal, cb: Option<IoEventDestroyCb>) {
+    let e = e as *mut IoSource<B>;
+    unsafe { (*e).destroy = cb };
+}
+
+extern "C"
+fn time_new<B: CustomMainloopBackend>(a: *const MainloopApi, tv: *const timeval,
+    cb: Option<TimeEventCb>, userdata: *mut c_void) -> *mut TimeEventInternal
+{
+    let ml = MainloopInternal::<B>::from_api(a);
+    unsafe {
+        let id = (*ml).new_id();
+        let e = Box::into_raw(Box::new(TimeSource {
+            mainloop: ml,
+            id,
+            tv: match tv.is_null() { true => None, false => Some(*tv) },
+            cb,
+            userdata,
+            destroy: None,
+        }));
+        (*ml).time_events.borrow_mut().insert(id, e);
+        (*ml).backend.borrow_mut().time_new(id, time_from_timeval(tv));
+        e as *mut TimeEventInternal
+    }
+}
+
+extern "C"
+fn time_restart<B: CustomMainloopBackend>(e: *mut TimeEventInternal, tv: *const timeval) {
+    let e = e as *mut TimeSource<B>;
+    unsafe {
+        (*e).tv = match tv.is_null() { true => None, false => Some(*tv) };
+        (*(*e).mainloop).backend.borrow_mut().time_restart((*e).id, time_from_timeval(tv));
+    }
+}
+
+extern "C"
+fn time_free<B: CustomMainloopBackend>(e: *mut TimeEventInternal) {
+    let e = e as *mut TimeSource<B>;
+    unsafe {
+        let ml = (*e).mainloop;
+        (*ml).time_events.borrow_mut().remove(&(*e).id);
+        (*ml).backend.borrow_mut().time_free((*e).id);
+        MainloopInternal::bury(ml, DeadSource::Time(e));
+    }
+}
+
+extern "C"
+fn time_set_destroy<B: CustomMainloopBackend>(e: *mut TimeEventInternal,
+    cb: Option<TimeEventDestroyCb>)
+{
+    let e = e as *mut TimeSource<B>;
+    unsafe { (*e).destroy = cb };
+}
+
+extern "C"
+fn defer_new<B: CustomMainloopBackend>(a: *const MainloopApi, cb: Option<DeferEventCb>,
+    userdata: *mut c_void) -> *mut DeferEventInternal
+{
+    let ml = MainloopInternal::<B>::from_api(a);
+    unsafe {
+        let id = (*ml).new_id();
+        let e = Box::into_raw(Box::new(DeferSource {
+            mainloop: ml, id, enabled: true, cb, userdata, destroy: None,
+        }));
+        (*ml).defer_events.borrow_mut().insert(id, e);
+        (*ml).backend.borrow_mut().defer_new(id);
+        e as *mut DeferEventInternal
+    }
+}
+
+extern "C"
+fn defer_enable<B: CustomMainloopBackend>(e: *mut DeferEventInternal, b: i32) {
+    let e = e as *mut DeferSource<B>;
+    unsafe {
+        (*e).enabled = b != 0;
+        (*(*e).mainloop).backend.borrow_mut().defer_enable((*e).id, b != 0);
+    }
+}
+
+extern "C"
+fn defer_free<B: CustomMainloopBackend>(e: *mut DeferEventInternal) {
+    let e = e as *mut DeferSource<B>;
+    unsafe {
+        let ml = (*e).mainloop;
+        (*e).enabled = false;
+        (*ml).defer_events.borrow_mut().remove(&(*e).id);
+        (*ml).backend.borrow_mut().defer_free((*e).id);
+        MainloopInternal::bury(ml, DeadSource::Defer(e));
+    }
+}
+
+extern "C"
+fn defer_set_destroy<B: CustomMainloopBackend>(e: *mut DeferEventInternal,
+    cb: Option<DeferEventDestroyCb>)
+{
+    let e = e as *mut DeferSource<B>;
+    unsafe { (*e).destroy = cb };
+}
+
+extern "C"
+fn quit<B: CustomMainloopBackend>(a: *const MainloopApi, retval: def::RetvalActual) {
+    let ml = MainloopInternal::<B>::from_api(a);
+    unsafe { (*ml).backend.borrow_mut().quit(def::Retval(retval)) };
+}
+
+/// Check event sources are tracked through to the backend, and dispatched
+#[test]
+fn custom_backend_defer_dispatch() {
+    #[derive(Default)]
+    struct Backend {
+        defers: HashMap<SourceId, bool>,
+    }
+    impl CustomMainloopBackend for Backend {
+        fn io_new(&mut self, _: SourceId, _: i32, _: IoEventFlagSet) -> bool { false }
+        fn io_enable(&mut self, _: SourceId, _: IoEventFlagSet) {}
+        fn io_free(&mut self, _: SourceId) {}
+        fn time_new(&mut self, _: SourceId, _: Option<UnixTs>) {}
+        fn time_restart(&mut self, _: SourceId, _: Option<UnixTs>) {}
+        fn time_free(&mut self, _: SourceId) {}
+        fn defer_new(&mut self, id: SourceId) { self.defers.insert(id, true); }
+        fn defer_enable(&mut self, id: SourceId, enable: bool) { self.defers.insert(id, enable); }
+        fn defer_free(&mut self, id: SourceId) { self.defers.remove(&id); }
+        fn quit(&mut self, _: def::Retval) {}
+    }
+
+    let mut mainloop = Mainloop::new(Backend::default());
+    let count = Rc::new(Cell::new(0));
+    let count_ref = Rc::clone(&count);
+    let event = mainloop.new_deferred_event(Box::new(move |mut e| {
+        count_ref.set(count_ref.get() + 1);
+        e.disable();
+    })).unwrap();
+
+    let id = *mainloop.backend().defers.keys().next().unwrap();
+    assert_eq!(mainloop.backend().defers.get(&id), Some(&true));
+    assert!(mainloop.dispatch_defer(id));
+    assert_eq!(count.get(), 1);
+    assert_eq!(mainloop.backend().defers.get(&id), Some(&false));
+    assert!(!mainloop.dispatch_defer(id));
+
+    drop(event);
+    assert!(mainloop.backend().defers.is_empty());
+    assert!(!mainloop.dispatch_defer(id));
+    assert_eq!(count.get(), 1);
+}
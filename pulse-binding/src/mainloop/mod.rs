@@ -41,6 +41,14 @@
 //! * ‘Glib’: A wrapper around GLib’s main loop. This is provided in the separate
 //!   `libpulse_glib_binding` crate.
 //!
+//! Additionally this binding provides:
+//!
+//! * [Custom](mod@self::custom): A means of implementing the abstraction with an event loop of
+//!   your own choosing, through a safe trait.
+//! * Tokio (`mainloop::tokio`): An implementation done on the Rust side, driven by the tokio
+//!   runtime’s reactor and timers, allowing use from within async code. This requires the `tokio`
+//!   feature and is only available on Unix platforms.
+//!
 //! UNIX signals may be hooked to a main loop using the functionality from the
 //! [`signal`](mod@signal) mod. This relies only on the main loop abstraction and can therefore be
 //! used with any of the implementations.
@@ -62,7 +70,7 @@
 //!
 //! While execution using the [standard mainloop] is entirely synchronous, the [threaded mainloop]
 //! implementation runs the standard mainloop in a separate thread and callback execution occurs
-//! asynchronously, requiring careful use of the mainloop’s [`lock()`] method. When writing
+//! asynchronously, requiring careful use of the mainloop’s [`lock_guard()`] method. When writing
 //! callbacks with the threaded mainloop, users must beware the potential that in a few cases the
 //! callback may be executed in two different scenarios, and with different threads. Note that the
 //! threaded mainloop has an [`in_thread()`] method for determining whether or not the thread it is
@@ -80,7 +88,7 @@
 //! Normally when holding multiple references to objects across threads in Rust you would use an
 //! [`Arc`] wrapper. However, with the [threaded mainloop], you may be able to get away with using
 //! just an `Rc` wrapper. Remember that with the [threaded mainloop] you **must** use its
-//! [`lock()`] method to synchronise access to objects, and so you know that at any one moment
+//! [`lock_guard()`] method to synchronise access to objects, and so you know that at any one moment
 //! either your thread (when you take the lock) **or** the event loop thread hold the lock, never
 //! both, and thus only one thread is ever working with objects at any one time, and since Rust
 //! actually has no idea that more than one thread is involved (hidden in the C library’s
@@ -88,12 +96,17 @@
 //!
 //! [standard mainloop]: mod@standard
 //! [threaded mainloop]: mod@self::threaded
-//! [`lock()`]: self::threaded::Mainloop::lock
+//! [`lock_guard()`]: self::threaded::Mainloop::lock_guard
 //! [`in_thread()`]: self::threaded::Mainloop::in_thread
 //! [`Arc`]: std::sync::Arc
 
 pub mod api;
+pub mod custom;
 pub mod events;
 pub mod signal;
 pub mod standard;
 pub mod threaded;
+
+#[cfg(all(unix, feature = "tokio"))]
+#[cfg_attr(docsrs, doc(cfg(all(unix, feature = "tokio"))))]
+pub mod tokio;
@@ -34,8 +34,8 @@
 //!
 //! When the PulseAudio connection has been terminated, the thread must be stopped and the
 //! resources freed. Stopping the thread is done using [`Mainloop::stop()`], which must be called
-//! without the lock (see below) held. When that function returns, the thread is stopped and the
-//! [`Mainloop`] object can be destroyed.
+//! without the lock (see below) held, or with [`Mainloop::try_stop()`], which returns an error
+//! rather than deadlocking if it is held. Once stopped, the [`Mainloop`] object can be destroyed.
 //!
 //! Destruction of the [`Mainloop`] object is done automatically when the object falls out of scope.
 //! (Rust’s `Drop` trait has been implemented and takes care of it).
@@ -44,10 +44,13 @@
 //!
 //! Since the PulseAudio API doesn’t allow concurrent accesses to objects, a locking scheme must be
 //! used to guarantee safe usage. The threaded main loop API provides such a scheme through the
-//! functions [`Mainloop::lock()`] and [`Mainloop::unlock()`].
+//! [`Mainloop::lock_guard()`] function, which returns a [`LockGuard`]. The lock is held for as long
+//! as the guard exists, being released when it is dropped.
 //!
-//! The lock is recursive, so it’s safe to use it multiple times from the same thread. Just make
-//! sure you call [`Mainloop::unlock()`] the same number of times you called [`Mainloop::lock()`].
+//! The lock is recursive, so it’s safe to use it multiple times from the same thread.
+//!
+//! Taking the lock from within the event loop thread (i.e. from within a callback) would deadlock,
+//! thus an error is returned if this is attempted.
 //!
 //! The lock needs to be held whenever you call any PulseAudio function that uses an object
 //! associated with this main loop. Those objects include the mainloop, context, stream and
@@ -65,11 +68,11 @@
 //! use pulse::stream::{Stream, State};
 //!
 //! fn check_stream(m: Rc<RefCell<Mainloop>>, s: Rc<RefCell<Stream>>) {
-//!     m.borrow_mut().lock();
+//!     let guard = m.borrow().lock_guard().unwrap();
 //!
 //!     let state = s.borrow().get_state();
 //!
-//!     m.borrow_mut().unlock();
+//!     drop(guard);
 //!
 //!     match state {
 //!         State::Ready => { println!("Stream is ready!"); },
@@ -104,7 +107,7 @@
 //! use pulse::stream::Stream;
 //!
 //! fn drain_stream(m: Rc<RefCell<Mainloop>>, s: Rc<RefCell<Stream>>) {
-//!     m.borrow_mut().lock();
+//!     let mut guard = m.borrow().lock_guard().unwrap();
 //!
 //!     // Drain
 //!     let o = {
@@ -114,27 +117,26 @@
 //!         })))
 //!     };
 //!     while o.get_state() != pulse::operation::State::Done {
-//!         m.borrow_mut().wait();
+//!         guard.wait();
 //!     }
-//!
-//!     m.borrow_mut().unlock();
 //! }
 //! ```
 //!
-//! The function `drain_stream` will wait for the callback to be called using [`Mainloop::wait()`].
+//! The function `drain_stream` will wait for the callback to be called using [`LockGuard::wait()`].
 //!
 //! If your application is multi-threaded, then this waiting must be done inside a while loop. The
-//! reason for this is that multiple threads might be using [`Mainloop::wait()`] at the same time.
+//! reason for this is that multiple threads might be using [`LockGuard::wait()`] at the same time.
 //! Each thread must therefore verify that it was its callback that was invoked. Also the underlying
 //! OS synchronization primitives are usually not free of spurious wake-ups, so a
-//! [`Mainloop::wait()`] must be called within a loop even if you have only one thread waiting.
+//! [`LockGuard::wait()`] must be called within a loop even if you have only one thread waiting.
 //!
-//! The callback `my_drain_callback` indicates to the main function that it has been called using
+//! The callback indicates to the main function that it has been called using
 //! [`Mainloop::signal()`].
 //!
-//! As you can see, [`Mainloop::wait()`] may only be called with the lock held. The same thing is
-//! true for [`Mainloop::signal()`], but as the lock is held before the callback is invoked, you do
-//! not have to deal with that.
+//! As you can see, [`LockGuard::wait()`] may only be called with the lock held, the guard being the
+//! proof of that. The same thing is true for signalling, but as the lock is held before the
+//! callback is invoked, you do not have to deal with that, and may simply use
+//! [`Mainloop::signal()`] (or [`MainloopHandle::signal()`]) from callbacks.
 //!
 //! The functions will not dead lock because the wait function will release the lock before waiting
 //! and then regrab it once it has been signalled. For those of you familiar with threads, the
@@ -159,7 +161,7 @@
 //! struct DrainCbData(*mut bool);
 //!
 //! fn drain_stream(m: Rc<RefCell<Mainloop>>, s: Rc<RefCell<Stream>>) {
-//!     m.borrow_mut().lock();
+//!     let mut lock = m.borrow().lock_guard().unwrap();
 //!
 //!     // For guarding against spurious wakeups
 //!     // Possibly also needed for memory flushing and ordering control
@@ -181,21 +183,19 @@
 //!         })))
 //!     };
 //!     while guard.borrow().load(Ordering::Acquire) {
-//!         m.borrow_mut().wait();
+//!         lock.wait();
 //!     }
 //!
 //!     assert!(!data.borrow().is_none());
 //!     let success = unsafe { *(data.borrow_mut().take().unwrap().0) };
 //!
 //!     // Allow callback to continue now
-//!     m.borrow_mut().accept();
+//!     lock.accept();
 //!
 //!     match success {
 //!         false => { println!("Bitter defeat..."); },
 //!         true => { println!("Success!"); },
 //!     }
-//!
-//!     m.borrow_mut().unlock();
 //! }
 //! ```
 //!
@@ -203,14 +203,14 @@
 //! `success`, but for larger data structures this can be wasteful.
 //!
 //! The difference here compared to the basic callback is the value `true` passed to
-//! [`Mainloop::signal()`] and the call to [`Mainloop::accept()`]. What will happen is that
+//! [`Mainloop::signal()`] and the call to [`LockGuard::accept()`]. What will happen is that
 //! [`Mainloop::signal()`] will signal the main function and then wait. The main function is then
-//! free to use the data in the callback until [`Mainloop::accept()`] is called, which will allow
+//! free to use the data in the callback until [`LockGuard::accept()`] is called, which will allow
 //! the callback to continue.
 //!
-//! Note that [`Mainloop::accept()`] must be called some time between exiting the while loop and
+//! Note that [`LockGuard::accept()`] must be called some time between exiting the while loop and
 //! unlocking the main loop! Failure to do so will result in a race condition. I.e. it is not okay
-//! to release the lock and regrab it before calling [`Mainloop::accept()`].
+//! to release the lock and regrab it before calling [`LockGuard::accept()`].
 //!
 //! ## Asynchronous callbacks
 //!
@@ -224,6 +224,38 @@
 //! * State callbacks for contexts, streams, etc.
 //! * Subscription notifications.
 //!
+//! # Handles
+//!
+//! The [`Mainloop`] object itself, like the objects attached to it, cannot be sent to other
+//! threads. For other threads to be able to take the lock, a [`MainloopHandle`] can be obtained
+//! through [`Mainloop::handle()`]. Handles are cheap to clone and can be sent to other threads.
+//!
+//! Handles can also be captured by callbacks, as an alternative means of signalling waiting
+//! threads with [`MainloopHandle::signal()`], which returns an error if used outside of the event
+//! loop thread (where the lock would not be held).
+//!
+//! Handles do not keep the main loop alive; once the [`Mainloop`] object is dropped, taking the
+//! lock through a handle returns an error. Dropping the [`Mainloop`] object blocks until all guards
+//! obtained through handles on other threads have been released.
+//!
+//! ```rust,no_run
+//! extern crate libpulse_binding as pulse;
+//!
+//! use pulse::mainloop::threaded::Mainloop;
+//!
+//! let mut mainloop = Mainloop::new().expect("Failed to create mainloop");
+//! mainloop.start().expect("Failed to start mainloop");
+//!
+//! let handle = mainloop.handle();
+//! let worker = std::thread::spawn(move || {
+//!     let _guard = handle.lock().expect("Main loop gone");
+//!     // Access objects attached to the main loop...
+//! });
+//! worker.join().unwrap();
+//!
+//! mainloop.try_stop().unwrap();
+//! ```
+//!
 //! # Example
 //!
 //! An example program using the threaded mainloop:
@@ -282,7 +314,7 @@
 //!     context.borrow_mut().connect(None, ContextFlagSet::NOFLAGS, None)
 //!         .expect("Failed to connect context");
 //!
-//!     mainloop.borrow_mut().lock();
+//!     let mut guard = mainloop.borrow().lock_guard().unwrap();
 //!     mainloop.borrow_mut().start().expect("Failed to start mainloop");
 //!
 //!     // Wait for context to be ready
@@ -292,11 +324,11 @@
 //!             pulse::context::State::Failed |
 //!             pulse::context::State::Terminated => {
 //!                 eprintln!("Context state failed/terminated, quitting...");
-//!                 mainloop.borrow_mut().unlock();
-//!                 mainloop.borrow_mut().stop();
+//!                 drop(guard);
+//!                 mainloop.borrow_mut().try_stop().unwrap();
 //!                 return;
 //!             },
-//!             _ => { mainloop.borrow_mut().wait(); },
+//!             _ => { guard.wait(); },
 //!         }
 //!     }
 //!     context.borrow_mut().set_state_callback(None);
@@ -335,21 +367,21 @@
 //!             pulse::stream::State::Failed |
 //!             pulse::stream::State::Terminated => {
 //!                 eprintln!("Stream state failed/terminated, quitting...");
-//!                 mainloop.borrow_mut().unlock();
-//!                 mainloop.borrow_mut().stop();
+//!                 drop(guard);
+//!                 mainloop.borrow_mut().try_stop().unwrap();
 //!                 return;
 //!             },
-//!             _ => { mainloop.borrow_mut().wait(); },
+//!             _ => { guard.wait(); },
 //!         }
 //!     }
 //!     stream.borrow_mut().set_state_callback(None);
 //!
-//!     mainloop.borrow_mut().unlock();
+//!     drop(guard);
 //!
 //!     // Our main logic (to output a stream of audio data)
 //! #   let mut count = 0; // For automatic unit tests, we’ll spin a few times
 //!     loop {
-//!         mainloop.borrow_mut().lock();
+//!         let mut guard = mainloop.borrow().lock_guard().unwrap();
 //!
 //!         // Write some data with stream.write()
 //!
@@ -365,10 +397,10 @@
 //!             })))
 //!         };
 //!         while o.get_state() != pulse::operation::State::Done {
-//!             mainloop.borrow_mut().wait();
+//!             guard.wait();
 //!         }
 //!
-//!         mainloop.borrow_mut().unlock();
+//!         drop(guard);
 //!
 //!         // If done writing data, call `mainloop.borrow_mut().stop()` (with lock released), then
 //!         // break!
@@ -376,22 +408,24 @@
 //! #       // Hack: Stop test getting stuck in infinite loop!
 //! #       count += 1;
 //! #       if count == 3 {
-//! #           mainloop.borrow_mut().stop();
+//! #           mainloop.borrow_mut().try_stop().unwrap();
 //! #           break;
 //! #       }
 //!     }
 //!
 //!     // Clean shutdown
-//!     mainloop.borrow_mut().lock();
+//!     let guard = mainloop.borrow().lock_guard().unwrap();
 //!     stream.borrow_mut().disconnect().unwrap();
-//!     mainloop.borrow_mut().unlock();
+//!     drop(guard);
 //! }
 //! ```
 
 use std::rc::Rc;
 use std::ffi::CString;
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread::{self, ThreadId};
 use crate::def;
-use crate::error::PAErr;
+use crate::error::{Code, PAErr};
 use crate::mainloop::api::{MainloopInternalType, MainloopInner, MainloopInnerType, MainloopApi,
                            Mainloop as MainloopTrait};
 use crate::mainloop::signal::MainloopSignals;
@@ -410,8 +444,57 @@ impl MainloopInternalType for MainloopInternal {}
 pub struct Mainloop {
     /// The ref-counted inner data.
     pub _inner: Rc<MainloopInner<MainloopInternal>>,
+    /// State shared with handles and lock guards.
+    shared: Arc<Shared>,
 }
 
+/// A cloneable handle to a threaded main loop, which can be sent to other threads.
+///
+/// This allows other threads to take the main loop lock, and allows callbacks to signal waiting
+/// threads. See the [module level documentation](mod@self#handles) for details.
+#[derive(Clone)]
+pub struct MainloopHandle {
+    shared: Arc<Shared>,
+}
+
+/// A guard holding the lock of a threaded main loop, releasing it when dropped.
+///
+/// Obtained through [`Mainloop::lock_guard()`] or [`MainloopHandle::lock()`].
+#[must_use = "the main loop is unlocked again as soon as the guard is dropped"]
+pub struct LockGuard {
+    /// The main loop object, kept alive by `_inner` if obtained from the main loop itself.
+    ptr: *mut MainloopInternal,
+    /// Shared state, for tracking lock holders.
+    shared: Arc<Shared>,
+    /// The ref-counted inner data, if obtained from the main loop itself.
+    _inner: Option<Rc<MainloopInner<MainloopInternal>>>,
+}
+
+/// State shared between a main loop, its handles, and lock guards.
+struct Shared {
+    /// The main loop object.
+    ptr: MainloopPtr,
+    /// Whether the main loop is still alive, and which threads hold guards.
+    state: Mutex<SharedState>,
+    /// Notified when a guard is released.
+    released: Condvar,
+}
+
+struct SharedState {
+    /// Cleared once the main loop object is dropped, after which handles no longer work.
+    alive: bool,
+    /// The threads holding the lock (one entry per guard, or per unreleased `lock()` call).
+    holders: Vec<ThreadId>,
+}
+
+/// Main loop pointer, which may be shared with other threads.
+struct MainloopPtr(*mut MainloopInternal);
+
+// The threaded main loop C object is built for use across threads, access being synchronised by
+// its lock and by the liveness tracking done in `Shared`.
+unsafe impl Send for MainloopPtr {}
+unsafe impl Sync for MainloopPtr {}
+
 impl MainloopTrait for Mainloop {
     type MI = MainloopInner<MainloopInternal>;
 
@@ -445,7 +528,12 @@ impl Mainloop {
             MainloopInner::<MainloopInternal>::new(ptr, std::mem::transmute(api_ptr),
                 MainloopInner::<MainloopInternal>::drop_actual, true)
         };
-        Some(Self { _inner: Rc::new(ml_inner) })
+        let shared = Arc::new(Shared {
+            ptr: MainloopPtr(ptr),
+            state: Mutex::new(SharedState { alive: true, holders: Vec::new() }),
+            released: Condvar::new(),
+        });
+        Some(Self { _inner: Rc::new(ml_inner), shared })
     }
 
     /// Starts the event loop thread.
@@ -458,28 +546,60 @@ impl Mainloop {
 
     /// Terminates the event loop thread cleanly.
     ///
-    /// Make sure to unlock the mainloop object before calling this function.
+    /// Make sure to unlock the mainloop object before calling this function, i.e. drop any
+    /// [`LockGuard`] held by this thread, otherwise this will deadlock, as it will if called from
+    /// within the event loop thread. See [`try_stop()`](Self::try_stop) for a checked version.
     #[inline]
     pub fn stop(&mut self) {
         unsafe { capi::pa_threaded_mainloop_stop(self._inner.get_ptr()); }
     }
 
+    /// Terminates the event loop thread cleanly, checking that doing so will not deadlock.
+    ///
+    /// Returns the [`PAErr`] form of the [`Code::BadState`] error, without doing anything, if
+    /// called from within the event loop thread, or while this thread holds the lock, since either
+    /// would deadlock.
+    pub fn try_stop(&mut self) -> Result<(), PAErr> {
+        if self.in_thread() || self.shared.is_held_by_current_thread() {
+            return Err(PAErr::from(Code::BadState));
+        }
+        self.stop();
+        Ok(())
+    }
+
+    /// Locks the event loop object, effectively blocking the event loop thread from processing
+    /// events, until the returned guard is dropped.
+    ///
+    /// You can use this to enforce exclusive access to all objects attached to the event loop. This
+    /// lock is recursive. Events that are dispatched from the event loop thread are executed with
+    /// this lock held.
+    ///
+    /// Returns the [`PAErr`] form of the [`Code::BadState`] error if called from within the event
+    /// loop thread, since this would deadlock.
+    pub fn lock_guard(&self) -> Result<LockGuard, PAErr> {
+        LockGuard::acquire(Arc::clone(&self.shared), Some(Rc::clone(&self._inner)))
+    }
+
     /// Locks the event loop object, effectively blocking the event loop thread from processing
     /// events.
     ///
     /// You can use this to enforce exclusive access to all objects attached to the event loop. This
     /// lock is recursive. This function may not be called inside the event loop thread. Events that
     /// are dispatched from the event loop thread are executed with this lock held.
+    #[deprecated(since = "2.31.0", note = "use `lock_guard()`, which unlocks upon drop, instead")]
     #[inline]
     pub fn lock(&mut self) {
         assert!(!self.in_thread(), "lock() can not be called from within the event loop thread!");
+        self.shared.state.lock().unwrap().holders.push(thread::current().id());
         unsafe { capi::pa_threaded_mainloop_lock(self._inner.get_ptr()); }
     }
 
     /// Unlocks the event loop object, inverse of [`lock()`](Self::lock).
+    #[deprecated(since = "2.31.0", note = "use `lock_guard()`, which unlocks upon drop, instead")]
     #[inline]
     pub fn unlock(&mut self) {
         unsafe { capi::pa_threaded_mainloop_unlock(self._inner.get_ptr()); }
+        self.shared.release_current_thread();
     }
 
     /// Waits for an event to be signalled by the event loop thread.
@@ -493,36 +613,45 @@ impl Mainloop {
     ///
     /// [`lock()`]: Self::lock
     /// [`signal()`]: Self::signal
+    #[deprecated(since = "2.31.0", note = "use `LockGuard::wait()` instead")]
     #[inline]
     pub fn wait(&mut self) {
         unsafe { capi::pa_threaded_mainloop_wait(self._inner.get_ptr()); }
     }
 
-    /// Signals all threads waiting for a signalling event in [`wait()`].
-    ///
-    /// If `wait_for_accept` is `true`, do not return before the signal was accepted by an
-    /// [`accept()`] call. While waiting for that condition the event loop object is unlocked.
-    ///
-    /// [`wait()`]: Self::wait
-    /// [`accept()`]: Self::accept
-    #[inline]
-    pub fn signal(&mut self, wait_for_accept: bool) {
-        unsafe {
-            capi::pa_threaded_mainloop_signal(self._inner.get_ptr(), wait_for_accept as i32);
-        }
-    }
-
     /// Accepts a signal from the event thread issued with [`signal()`].
     ///
     /// This call should only be used in conjunction with [`signal()`] with `wait_for_accept` as
     /// `true`.
     ///
     /// [`signal()`]: Self::signal
+    #[deprecated(since = "2.31.0", note = "use `LockGuard::accept()` instead")]
     #[inline]
     pub fn accept(&mut self) {
         unsafe { capi::pa_threaded_mainloop_accept(self._inner.get_ptr()); }
     }
 
+    /// Gets a handle to this main loop, which can be sent to other threads.
+    #[inline]
+    pub fn handle(&self) -> MainloopHandle {
+        MainloopHandle { shared: Arc::clone(&self.shared) }
+    }
+
+    /// Signals all threads waiting for a signalling event in [`LockGuard::wait()`].
+    ///
+    /// This is intended for use within callbacks, which are executed by the event loop thread with
+    /// the lock held. Other threads should use [`LockGuard::signal()`] instead.
+    ///
+    /// If `wait_for_accept` is `true`, do not return before the signal was accepted by an
+    /// [`LockGuard::accept()`] call. While waiting for that condition the event loop object is
+    /// unlocked.
+    #[inline]
+    pub fn signal(&mut self, wait_for_accept: bool) {
+        unsafe {
+            capi::pa_threaded_mainloop_signal(self._inner.get_ptr(), wait_for_accept as i32);
+        }
+    }
+
     /// Gets the return value as specified with the main loop’s `quit` routine (used internally by
     /// threaded mainloop).
     #[inline]
@@ -560,3 +689,139 @@ impl Mainloop {
         unsafe { capi::pa_threaded_mainloop_set_name(self._inner.get_ptr(), c_name.as_ptr()); }
     }
 }
+
+impl Drop for Mainloop {
+    fn drop(&mut self) {
+        // Handles stop working from here on. Since guards obtained through handles on other
+        // threads do not keep the main loop alive, wait for those to be released first.
+        let current = thread::current().id();
+        let mut state = self.shared.state.lock().unwrap();
+        state.alive = false;
+        while state.holders.iter().any(|&t| t != current) {
+            state = self.shared.released.wait(state).unwrap();
+        }
+    }
+}
+
+impl MainloopHandle {
+    /// Locks the event loop object, effectively blocking the event loop thread from processing
+    /// events, until the returned guard is dropped.
+    ///
+    /// See [`Mainloop::lock_guard()`]. Returns the [`PAErr`] form of the [`Code::Killed`] error if
+    /// the main loop object has been dropped.
+    pub fn lock(&self) -> Result<LockGuard, PAErr> {
+        LockGuard::acquire(Arc::clone(&self.shared), None)
+    }
+
+    /// Signals all threads waiting for a signalling event in [`LockGuard::wait()`].
+    ///
+    /// This is intended for use within callbacks, which are executed by the event loop thread with
+    /// the lock held. Other threads should use [`LockGuard::signal()`] instead.
+    ///
+    /// If `wait_for_accept` is `true`, do not return before the signal was accepted by an
+    /// [`LockGuard::accept()`] call. While waiting for that condition the event loop object is
+    /// unlocked.
+    ///
+    /// Returns the [`PAErr`] form of the [`Code::BadState`] error if not called from within the
+    /// event loop thread, or of [`Code::Killed`] if the main loop object has been dropped.
+    pub fn signal(&self, wait_for_accept: bool) -> Result<(), PAErr> {
+        let ptr = self.shared.ptr.0;
+        {
+            let state = self.shared.state.lock().unwrap();
+            if !state.alive {
+                return Err(PAErr::from(Code::Killed));
+            }
+            if unsafe { capi::pa_threaded_mainloop_in_thread(ptr) } == 0 {
+                return Err(PAErr::from(Code::BadState));
+            }
+        }
+        // Being in the event loop thread, the main loop cannot be freed before we return
+        unsafe { capi::pa_threaded_mainloop_signal(ptr, wait_for_accept as i32); }
+        Ok(())
+    }
+
+    /// Checks whether or not we are in the event loop thread (returns `true` if so).
+    ///
+    /// Returns `false` if the main loop object has been dropped.
+    pub fn in_thread(&self) -> bool {
+        let state = self.shared.state.lock().unwrap();
+        state.alive && unsafe { capi::pa_threaded_mainloop_in_thread(self.shared.ptr.0) != 0 }
+    }
+}
+
+impl Shared {
+    /// Checks whether the current thread holds the lock.
+    fn is_held_by_current_thread(&self) -> bool {
+        let current = thread::current().id();
+        self.state.lock().unwrap().holders.contains(&current)
+    }
+
+    /// Records the release of one hold of the lock by the current thread.
+    fn release_current_thread(&self) {
+        let current = thread::current().id();
+        let mut state = self.state.lock().unwrap();
+        if let Some(i) = state.holders.iter().position(|&t| t == current) {
+            state.holders.swap_remove(i);
+        }
+        self.released.notify_all();
+    }
+}
+
+impl LockGuard {
+    fn acquire(shared: Arc<Shared>, inner: Option<Rc<MainloopInner<MainloopInternal>>>)
+        -> Result<Self, PAErr>
+    {
+        let ptr = shared.ptr.0;
+        {
+            let mut state = shared.state.lock().unwrap();
+            if !state.alive && inner.is_none() {
+                return Err(PAErr::from(Code::Killed));
+            }
+            if unsafe { capi::pa_threaded_mainloop_in_thread(ptr) } != 0 {
+                return Err(PAErr::from(Code::BadState));
+            }
+            state.holders.push(thread::current().id());
+        }
+        unsafe { capi::pa_threaded_mainloop_lock(ptr); }
+        Ok(Self { ptr, shared, _inner: inner })
+    }
+
+    /// Waits for an event to be signalled by the event loop thread.
+    ///
+    /// You can use this to pass data from the event loop thread to the main thread in a
+    /// synchronized fashion. While waiting the lock will be released. Immediately before returning
+    /// it will be acquired again. This function may spuriously wake up even without a signal being
+    /// sent. You need to make sure to handle that!
+    #[inline]
+    pub fn wait(&mut self) {
+        unsafe { capi::pa_threaded_mainloop_wait(self.ptr); }
+    }
+
+    /// Signals all threads waiting for a signalling event in [`wait()`].
+    ///
+    /// If `wait_for_accept` is `true`, do not return before the signal was accepted by an
+    /// [`accept()`] call. While waiting for that condition the event loop object is unlocked.
+    ///
+    /// [`wait()`]: Self::wait
+    /// [`accept()`]: Self::accept
+    #[inline]
+    pub fn signal(&mut self, wait_for_accept: bool) {
+        unsafe { capi::pa_threaded_mainloop_signal(self.ptr, wait_for_accept as i32); }
+    }
+
+    /// Accepts a signal from the event thread issued with `wait_for_accept` as `true`.
+    ///
+    /// This must be called some time between being woken from [`wait()`](Self::wait) by such a
+    /// signal, and releasing the lock.
+    #[inline]
+    pub fn accept(&mut self) {
+        unsafe { capi::pa_threaded_mainloop_accept(self.ptr); }
+    }
+}
+
+impl Drop for LockGuard {
+    fn drop(&mut self) {
+        unsafe { capi::pa_threaded_mainloop_unlock(self.ptr); }
+        self.shared.release_current_thread();
+    }
+}
@@ -0,0 +1,774 @@
+// Copyright 2026 Lyndon Brown
+//
+// This file is part of the PulseAudio Rust language binding.
+//
+// Licensed under the MIT license or the Apache license (version 2.0), at your option. You may not
+// copy, modify, or distribute this file except in compliance with said license. You can find copies
+// of these licenses either in the LICENSE-MIT and LICENSE-APACHE files, or alternatively at
+// <http://opensource.org/licenses/MIT> and <http://www.apache.org/licenses/LICENSE-2.0>
+// respectively.
+
+//! Tokio driven main loop implementation.
+//!
+//! # Overview
+//!
+//! This main loop implementation provides the functions defined in the main loop abstraction
+//! ([`mainloop::api`](mod@crate::mainloop::api)) entirely on the Rust side, with IO events watched
+//! by the tokio reactor and timer events driven by tokio timers. This allows a
+//! [`Context`](crate::context::Context) to be run within an existing tokio runtime, without needing
+//! a separate thread and the locking requirements that come with the
+//! [`threaded`](mod@crate::mainloop::threaded) main loop.
+//!
+//! This module is only available on Unix platforms, and only with the `tokio` feature enabled.
+//!
+//! # Usage
+//!
+//! A [`Mainloop`] is created using [`Mainloop::new()`], which must be called from within the
+//! context of a tokio runtime, with both its IO and time drivers enabled. `None` is returned
+//! otherwise.
+//!
+//! The main loop does nothing until the future returned by [`Mainloop::run()`] is polled. Each
+//! time it is polled, all pending deferred, timer and IO events are dispatched, and the future then
+//! resolves once the main loop’s `quit()` function has been called.
+//!
+//! Like everything else in this binding, the main loop and all objects attached to it are not
+//! `Send`, thus the run future must be driven on the same thread that created it, for instance by
+//! spawning it onto a [`tokio::task::LocalSet`], or by using a current-thread runtime.
+//!
+//! Since callbacks are all executed from within the polling of that future, there is no need for
+//! any locking. Operations can be awaited through their future and stream forms (see the
+//! [`operation`](mod@crate::operation) module) from any task on the same `LocalSet`.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! extern crate libpulse_binding as pulse;
+//!
+//! use pulse::context::{Context, FlagSet as ContextFlagSet};
+//! use pulse::mainloop::tokio::Mainloop;
+//!
+//! let local = tokio::task::LocalSet::new();
+//! local.run_until(async {
+//!     let mainloop = Mainloop::new().expect("not within a tokio runtime");
+//!     let mut context = Context::new(&mainloop, "FooApp").expect("Failed to create new context");
+//!     tokio::task::spawn_local(mainloop.run());
+//!
+//!     context.connect(None, ContextFlagSet::NOFLAGS, None).expect("Failed to connect context");
+//!     // Wait for the context to become ready, then use it...
+//!     let sinks = context.introspect().get_sink_info_list_stream().collect_vec().await;
+//! }).await;
+//! ```
+
+use std::cell::{Cell, RefCell};
+use std::future::Future;
+use std::os::raw::c_void;
+use std::os::unix::io::{AsRawFd, RawFd};
+use std::pin::Pin;
+use std::ptr::null_mut;
+use std::rc::Rc;
+use std::task::{Context as TaskContext, Poll, Waker};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use ::tokio::io::unix::AsyncFd;
+use ::tokio::runtime::Handle;
+use ::tokio::time::{Instant, Sleep};
+use libc::timeval;
+use crate::def;
+use crate::mainloop::api::{MainloopInternalType, MainloopInner, MainloopInnerType, MainloopApi,
+    IoEventCb, IoEventDestroyCb, TimeEventCb, TimeEventDestroyCb, DeferEventCb, DeferEventDestroyCb,
+    Mainloop as MainloopTrait};
+use crate::mainloop::events::io::{IoEventInternal, FlagSet as IoEventFlagSet};
+use crate::mainloop::events::timer::TimeEventInternal;
+use crate::mainloop::events::deferred::DeferEventInternal;
+use crate::mainloop::signal::MainloopSignals;
+use crate::time::Timeval;
+
+/// The Rust side main loop state, to which the API vtable’s `userdata` points.
+pub struct MainloopInternal {
+    /// The API vtable handed out to the C library.
+    api: MainloopApi,
+    /// Handle of the runtime the main loop was created within.
+    handle: Handle,
+    /// IO event sources.
+    io_events: RefCell<Vec<*mut IoSource>>,
+    /// Timer event sources.
+    time_events: RefCell<Vec<*mut TimeSource>>,
+    /// Deferred event sources.
+    defer_events: RefCell<Vec<*mut DeferSource>>,
+    /// Sleep used for waking up for the earliest timer event.
+    sleep: RefCell<Option<Pin<Box<Sleep>>>>,
+    /// Return value given to quit, if it has been called.
+    quit: Cell<Option<def::Retval>>,
+    /// Waker of the task polling the run future.
+    waker: RefCell<Option<Waker>>,
+}
+
+impl MainloopInternalType for MainloopInternal {}
+
+/// A duplicate of an IO event file descriptor, registered with the tokio reactor.
+///
+/// A duplicate is used such that the C library is free to create multiple event sources for the
+/// same descriptor, which the reactor would otherwise refuse.
+struct DupFd(RawFd);
+
+impl AsRawFd for DupFd {
+    fn as_raw_fd(&self) -> RawFd {
+        self.0
+    }
+}
+
+impl Drop for DupFd {
+    fn drop(&mut self) {
+        unsafe { libc::close(self.0) };
+    }
+}
+
+struct IoSource {
+    mainloop: *mut MainloopInternal,
+    fd: RawFd,
+    async_fd: Option<AsyncFd<DupFd>>,
+    events: IoEventFlagSet,
+    cb: Option<IoEventCb>,
+    userdata: *mut c_void,
+    destroy: Option<IoEventDestroyCb>,
+    dead: bool,
+}
+
+struct TimeSource {
+    mainloop: *mut MainloopInternal,
+    tv: timeval,
+    deadline: Option<Instant>,
+    cb: Option<TimeEventCb>,
+    userdata: *mut c_void,
+    destroy: Option<TimeEventDestroyCb>,
+    dead: bool,
+}
+
+struct DeferSource {
+    mainloop: *mut MainloopInternal,
+    enabled: bool,
+    cb: Option<DeferEventCb>,
+    userdata: *mut c_void,
+    destroy: Option<DeferEventDestroyCb>,
+    dead: bool,
+}
+
+/// This acts as a safe interface to the tokio driven main loop.
+///
+/// The mainloop object pointers are further enclosed here in a ref counted wrapper, allowing this
+/// outer wrapper to have clean methods for creating event objects, which can cleanly pass a copy of
+/// the inner ref counted mainloop object to them. Giving this to events serves two purposes,
+/// firstly because they need the API pointer, secondly, it ensures that event objects do not
+/// outlive the mainloop object.
+pub struct Mainloop {
+    /// The ref-counted inner data.
+    pub _inner: Rc<MainloopInner<MainloopInternal>>,
+}
+
+impl MainloopTrait for Mainloop {
+    type MI = MainloopInner<MainloopInternal>;
+
+    #[inline(always)]
+    fn inner(&self) -> Rc<MainloopInner<MainloopInternal>> {
+        Rc::clone(&self._inner)
+    }
+}
+
+impl MainloopSignals for Mainloop {}
+
+/// Drop function for MainloopInner<MainloopInternal>.
+fn drop_actual(self_: &mut MainloopInner<MainloopInternal>) {
+    drop(unsafe { Box::from_raw(self_.get_ptr()) });
+}
+
+impl Mainloop {
+    /// Creates a new main loop object, attached to the tokio runtime of the current context.
+    ///
+    /// Returns `None` if not called from within the context of a tokio runtime.
+    pub fn new() -> Option<Self> {
+        let handle = Handle::try_current().ok()?;
+        let internal = Box::new(MainloopInternal {
+            api: MainloopApi {
+                userdata: null_mut::<c_void>(),
+                io_new: Some(io_new),
+                io_enable: Some(io_enable),
+                io_free: Some(io_free),
+                io_set_destroy: Some(io_set_destroy),
+                time_new: Some(time_new),
+                time_restart: Some(time_restart),
+                time_free: Some(time_free),
+                time_set_destroy: Some(time_set_destroy),
+                defer_new: Some(defer_new),
+                defer_enable: Some(defer_enable),
+                defer_free: Some(defer_free),
+                defer_set_destroy: Some(defer_set_destroy),
+                quit: Some(quit),
+            },
+            handle,
+            io_events: RefCell::new(Vec::new()),
+            time_events: RefCell::new(Vec::new()),
+            defer_events: RefCell::new(Vec::new()),
+            sleep: RefCell::new(None),
+            quit: Cell::new(None),
+            waker: RefCell::new(None),
+        });
+        let ptr = Box::into_raw(internal);
+        let api_ptr = unsafe {
+            (*ptr).api.userdata = ptr as *mut c_void;
+            &(*ptr).api as *const MainloopApi
+        };
+        let ml_inner = unsafe {
+            MainloopInner::<MainloopInternal>::new(ptr, api_ptr, drop_actual, false)
+        };
+        Some(Self { _inner: Rc::new(ml_inner) })
+    }
+
+    /// Gets the abstract main loop abstraction layer vtable for this main loop.
+    ///
+    /// No need to free the API as it is owned by the loop and is destroyed when the loop is freed.
+    ///
+    /// Talking to PA directly with C requires fetching this pointer explicitly via this function.
+    /// This is actually unnecessary through this binding. The pointer is retrieved automatically
+    /// where needed.
+    pub fn get_api<'a>(&self) -> &'a MainloopApi {
+        let ptr = self._inner.get_api_ptr();
+        assert!(!ptr.is_null());
+        unsafe { &*ptr }
+    }
+
+    /// Returns a future which runs the main loop, resolving to the return value given to quit.
+    ///
+    /// Events are only dispatched while this future is being polled. It must be polled on the
+    /// thread that created the main loop.
+    pub fn run(&self) -> Run {
+        Run { inner: Rc::clone(&self._inner) }
+    }
+}
+
+/// Future returned by [`Mainloop::run()`].
+#[must_use = "futures do nothing unless polled"]
+pub struct Run {
+    inner: Rc<MainloopInner<MainloopInternal>>,
+}
+
+impl Future for Run {
+    type Output = def::Retval;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<Self::Output> {
+        let ml = self.inner.get_ptr();
+        unsafe { MainloopInternal::poll_dispatch(ml, cx) }
+    }
+}
+
+impl MainloopInternal {
+    /// Gets the main loop object from the API vtable.
+    #[inline]
+    fn from_api(a: *const MainloopApi) -> *mut Self {
+        assert!(!a.is_null());
+        unsafe { (*a).userdata as *mut Self }
+    }
+
+    /// Wakes the run future, to have changes to event sources take effect.
+    fn wake(&self) {
+        if let Some(waker) = self.waker.borrow().as_ref() {
+            waker.wake_by_ref();
+        }
+    }
+
+    /// Performs a single dispatch pass.
+    ///
+    /// Callbacks are free to create, modify and free event sources, thus no borrows of the event
+    /// lists are held while executing them, and freed sources are only cleaned up at the end.
+    unsafe fn poll_dispatch(ml: *mut Self, cx: &mut TaskContext<'_>) -> Poll<def::Retval> {
+        *(*ml).waker.borrow_mut() = Some(cx.waker().clone());
+
+        if let Some(retval) = (*ml).quit.take() {
+            return Poll::Ready(retval);
+        }
+
+        let mut dispatched = false;
+
+        let defers = (*ml).defer_events.borrow().clone();
+        for e in defers {
+            if (*e).dead || !(*e).enabled {
+                continue;
+            }
+            if let Some(cb) = (*e).cb {
+                cb(&(*ml).api, e as *mut DeferEventInternal, (*e).userdata);
+            }
+            dispatched = true;
+        }
+
+        let now = Instant::now();
+        let timers = (*ml).time_events.borrow().clone();
+        for e in timers {
+            if (*e).dead || !(*e).deadline.map_or(false, |d| d <= now) {
+                continue;
+            }
+            (*e).deadline = None;
+            let tv = (*e).tv;
+            if let Some(cb) = (*e).cb {
+                cb(&(*ml).api, e as *mut TimeEventInternal, &tv, (*e).userdata);
+            }
+            dispatched = true;
+        }
+
+        let ios = (*ml).io_events.borrow().clone();
+        for e in ios {
+            if (*e).dead || (*e).events.is_empty() {
+                continue;
+            }
+            let revents = IoSource::check(e, cx);
+            if revents.is_empty() || (*e).dead {
+                continue;
+            }
+            if let Some(cb) = (*e).cb {
+                cb(&(*ml).api, e as *mut IoEventInternal, (*e).fd, revents, (*e).userdata);
+            }
+            dispatched = true;
+        }
+
+        Self::cleanup(ml);
+
+        if let Some(retval) = (*ml).quit.take() {
+            return Poll::Ready(retval);
+        }
+
+        // Yield back to the runtime between passes that did some work, rather than looping here,
+        // so as to not starve other tasks.
+        let defer_pending = (*ml).defer_events.borrow().iter().any(|&e| (*e).enabled);
+        if dispatched || defer_pending {
+            cx.waker().wake_by_ref();
+            return Poll::Pending;
+        }
+
+        let next = (*ml).time_events.borrow().iter().filter_map(|&e| (*e).deadline).min();
+        if let Some(deadline) = next {
+            let mut sleep = (*ml).sleep.borrow_mut();
+            match sleep.as_mut() {
+                Some(s) => s.as_mut().reset(deadline),
+                None => {
+                    let _guard = (*ml).handle.enter();
+                    *sleep = Some(Box::pin(::tokio::time::sleep_until(deadline)));
+                },
+            }
+            if sleep.as_mut().unwrap().as_mut().poll(cx).is_ready() {
+                cx.waker().wake_by_ref();
+            }
+        }
+        Poll::Pending
+    }
+
+    /// Destroys freed event sources.
+    unsafe fn cleanup(ml: *mut Self) {
+        let api = &(*ml).api as *const MainloopApi;
+
+        let dead: Vec<*mut IoSource> = {
+            let mut list = (*ml).io_events.borrow_mut();
+            let dead = list.iter().copied().filter(|&e| (*e).dead).collect();
+            list.retain(|&e| !(*e).dead);
+            dead
+        };
+        for e in dead {
+            if let Some(destroy) = (*e).destroy {
+                destroy(api, e as *mut IoEventInternal, (*e).userdata);
+            }
+            drop(Box::from_raw(e));
+        }
+
+        let dead: Vec<*mut TimeSource> = {
+            let mut list = (*ml).time_events.borrow_mut();
+            let dead = list.iter().copied().filter(|&e| (*e).dead).collect();
+            list.retain(|&e| !(*e).dead);
+            dead
+        };
+        for e in dead {
+            if let Some(destroy) = (*e).destroy {
+                destroy(api, e as *mut TimeEventInternal, (*e).userdata);
+            }
+            drop(Box::from_raw(e));
+        }
+
+        let dead: Vec<*mut DeferSource> = {
+            let mut list = (*ml).defer_events.borrow_mut();
+            let dead = list.iter().copied().filter(|&e| (*e).dead).collect();
+            list.retain(|&e| !(*e).dead);
+            dead
+        };
+        for e in dead {
+            if let Some(destroy) = (*e).destroy {
+                destroy(api, e as *mut DeferEventInternal, (*e).userdata);
+            }
+            drop(Box::from_raw(e));
+        }
+    }
+}
+
+impl Drop for MainloopInternal {
+    fn drop(&mut self) {
+        // Any event sources not yet freed by their owners are destroyed along with the main loop.
+        let ml = self as *mut Self;
+        unsafe {
+            for &e in self.io_events.borrow().iter() {
+                (*e).dead = true;
+            }
+            for &e in self.time_events.borrow().iter() {
+                (*e).dead = true;
+            }
+            for &e in self.defer_events.borrow().iter() {
+                (*e).dead = true;
+            }
+            Self::cleanup(ml);
+        }
+    }
+}
+
+impl IoSource {
+    /// Checks the file descriptor for the events of interest.
+    ///
+    /// If none are pending, the reactor is armed to wake the current task once the descriptor
+    /// becomes ready. Since the reactor is edge-triggered, while the C library does not
+    /// necessarily consume all data in one go, readiness is always confirmed with a non-blocking
+    /// `poll()` rather than trusting the reactor’s readiness state.
+    unsafe fn check(e: *mut Self, cx: &mut TaskContext<'_>) -> IoEventFlagSet {
+        let async_fd = match (*e).async_fd.as_ref() {
+            Some(async_fd) => async_fd,
+            None => return IoEventFlagSet::NULL,
+        };
+        let events = &(*e).events;
+        loop {
+            let revents = poll_now(async_fd.as_raw_fd(), events);
+            if !revents.is_empty() {
+                return revents;
+            }
+
+            let mut rearmed = false;
+            if events.contains(IoEventFlagSet::OUTPUT) {
+                match async_fd.poll_write_ready(cx) {
+                    Poll::Ready(Ok(mut guard)) => { guard.clear_ready(); rearmed = true; },
+                    Poll::Ready(Err(_)) => return IoEventFlagSet::ERROR,
+                    Poll::Pending => {},
+                }
+            }
+            if !events.contains(IoEventFlagSet::OUTPUT) || events.contains(IoEventFlagSet::INPUT) {
+                match async_fd.poll_read_ready(cx) {
+                    Poll::Ready(Ok(mut guard)) => { guard.clear_ready(); rearmed = true; },
+                    Poll::Ready(Err(_)) => return IoEventFlagSet::ERROR,
+                    Poll::Pending => {},
+                }
+            }
+            if !rearmed {
+                return IoEventFlagSet::NULL;
+            }
+        }
+    }
+}
+
+/// Polls a file descriptor without blocking, returning the events that are pending.
+fn poll_now(fd: RawFd, events: &IoEventFlagSet) -> IoEventFlagSet {
+    let mut pfd = libc::pollfd { fd, events: 0, revents: 0 };
+    if events.contains(IoEventFlagSet::INPUT) {
+        pfd.events |= libc::POLLIN;
+    }
+    if events.contains(IoEventFlagSet::OUTPUT) {
+        pfd.events |= libc::POLLOUT;
+    }
+    if unsafe { libc::poll(&mut pfd, 1, 0) } <= 0 {
+        return IoEventFlagSet::NULL;
+    }
+    let mut revents = IoEventFlagSet::NULL;
+    if (pfd.revents & libc::POLLIN) != 0 {
+        revents |= IoEventFlagSet::INPUT;
+    }
+    if (pfd.revents & libc::POLLOUT) != 0 {
+        revents |= IoEventFlagSet::OUTPUT;
+    }
+    if (pfd.revents & libc::POLLHUP) != 0 {
+        revents |= IoEventFlagSet::HANGUP;
+    }
+    if (pfd.revents & (libc::POLLERR | libc::POLLNVAL)) != 0 {
+        revents |= IoEventFlagSet::ERROR;
+    }
+    revents
+}
+
+/// Converts a Unix wall-clock time to a tokio instant.
+///
+/// Returns `None` for a null pointer, which disables a timer.
+fn deadline_from_timeval(tv: *const timeval) -> Option<Instant> {
+    if tv.is_null() {
+        return None;
+    }
+    Some(deadline_at(unsafe { &*tv }, SystemTime::now(), Instant::now()))
+}
+
+/// Converts a Unix wall-clock time to a tokio instant, given the wall-clock time and instant of
+/// now. Times in the past give the instant of now.
+fn deadline_at(tv: &timeval, wall_now: SystemTime, now: Instant) -> Instant {
+    let target = UNIX_EPOCH + Duration::from_secs(tv.tv_sec.max(0) as u64)
+        + Duration::from_micros(tv.tv_usec.max(0) as u64);
+    now + target.duration_since(wall_now).unwrap_or(Duration::ZERO)
+}
+
+extern "C"
+fn io_new(a: *const MainloopApi, fd: i32, events: IoEventFlagSet, cb: Option<IoEventCb>,
+    userdata: *mut c_void) -> *mut IoEventInternal
+{
+    let ml = MainloopInternal::from_api(a);
+    let async_fd = unsafe {
+        let dup = libc::fcntl(fd, libc::F_DUPFD_CLOEXEC, 0);
+        if dup < 0 {
+            return null_mut::<IoEventInternal>();
+        }
+        let _guard = (*ml).handle.enter();
+        match AsyncFd::new(DupFd(dup)) {
+            Ok(async_fd) => async_fd,
+            Err(_) => return null_mut::<IoEventInternal>(),
+        }
+    };
+    let e = Box::into_raw(Box::new(IoSource {
+        mainloop: ml,
+        fd,
+        async_fd: Some(async_fd),
+        events,
+        cb,
+        userdata,
+        destroy: None,
+        dead: false,
+    }));
+    unsafe {
+        (*ml).io_events.borrow_mut().push(e);
+        (*ml).wake();
+    }
+    e as *mut IoEventInternal
+}
+
+extern "C"
+fn io_enable(e: *mut IoEventInternal, events: IoEventFlagSet) {
+    let e = e as *mut IoSource;
+    unsafe {
+        assert!(!(*e).dead);
+        (*e).events = events;
+        (*(*e).mainloop).wake();
+    }
+}
+
+extern "C"
+fn io_free(e: *mut IoEventInternal) {
+    let e = e as *mut IoSource;
+    unsafe {
+        (*e).dead = true;
+        (*e).events = IoEventFlagSet::NULL;
+        // Deregister from the reactor right away, the descriptor may be closed after this
+        (*e).async_fd = None;
+    }
+}
+
+extern "C"
+fn io_set_destroy(e: *mut IoEventInternal, cb: Option<IoEventDestroyCb>) {
+    let e = e as *mut IoSource;
+    unsafe { (*e).destroy = cb };
+}
+
+extern "C"
+fn time_new(a: *const MainloopApi, tv: *const timeval, cb: Option<TimeEventCb>,
+    userdata: *mut c_void) -> *mut TimeEventInternal
+{
+    let ml = MainloopInternal::from_api(a);
+    let e = Box::into_raw(Box::new(TimeSource {
+        mainloop: ml,
+        tv: match tv.is_null() {
+            true => Timeval::new_zero().0,
+            false => unsafe { *tv },
+        },
+        deadline: deadline_from_timeval(tv),
+        cb,
+        userdata,
+        destroy: None,
+        dead: false,
+    }));
+    unsafe {
+        (*ml).time_events.borrow_mut().push(e);
+        (*ml).wake();
+    }
+    e as *mut TimeEventInternal
+}
+
+extern "C"
+fn time_restart(e: *mut TimeEventInternal, tv: *const timeval) {
+    let e = e as *mut TimeSource;
+    unsafe {
+        assert!(!(*e).dead);
+        if !tv.is_null() {
+            (*e).tv = *tv;
+        }
+        (*e).deadline = deadline_from_timeval(tv);
+        (*(*e).mainloop).wake();
+    }
+}
+
+extern "C"
+fn time_free(e: *mut TimeEventInternal) {
+    let e = e as *mut TimeSource;
+    unsafe {
+        (*e).dead = true;
+        (*e).deadline = None;
+    }
+}
+
+extern "C"
+fn time_set_destroy(e: *mut TimeEventInternal, cb: Option<TimeEventDestroyCb>) {
+    let e = e as *mut TimeSource;
+    unsafe { (*e).destroy = cb };
+}
+
+extern "C"
+fn defer_new(a: *const MainloopApi, cb: Option<DeferEventCb>, userdata: *mut c_void)
+    -> *mut DeferEventInternal
+{
+    let ml = MainloopInternal::from_api(a);
+    let e = Box::into_raw(Box::new(DeferSource {
+        mainloop: ml,
+        enabled: true,
+        cb,
+        userdata,
+        destroy: None,
+        dead: false,
+    }));
+    unsafe {
+        (*ml).defer_events.borrow_mut().push(e);
+        (*ml).wake();
+    }
+    e as *mut DeferEventInternal
+}
+
+extern "C"
+fn defer_enable(e: *mut DeferEventInternal, b: i32) {
+    let e = e as *mut DeferSource;
+    unsafe {
+        assert!(!(*e).dead);
+        (*e).enabled = b != 0;
+        (*(*e).mainloop).wake();
+    }
+}
+
+extern "C"
+fn defer_free(e: *mut DeferEventInternal) {
+    let e = e as *mut DeferSource;
+    unsafe {
+        (*e).dead = true;
+        (*e).enabled = false;
+    }
+}
+
+extern "C"
+fn defer_set_destroy(e: *mut DeferEventInternal, cb: Option<DeferEventDestroyCb>) {
+    let e = e as *mut DeferSource;
+    unsafe { (*e).destroy = cb };
+}
+
+extern "C"
+fn quit(a: *const MainloopApi, retval: def::RetvalActual) {
+    let ml = MainloopInternal::from_api(a);
+    unsafe {
+        (*ml).quit.set(Some(def::Retval(retval)));
+        (*ml).wake();
+    }
+}
+
+/// Check conversion of Unix wall-clock times to instants
+#[test]
+fn tokio_deadline_conversion() {
+    let now = Instant::now();
+    let wall_now = UNIX_EPOCH + Duration::from_micros(1_000_000_500_000);
+    let tv = |tv_sec, tv_usec| timeval { tv_sec, tv_usec };
+
+    assert_eq!(deadline_at(&tv(1_000_000, 500_000), wall_now, now), now);
+    assert_eq!(deadline_at(&tv(1_000_002, 0), wall_now, now), now + Duration::from_millis(1500));
+    assert_eq!(deadline_at(&tv(1_000_000, 500_001), wall_now, now), now + Duration::from_micros(1));
+    // In the past
+    assert_eq!(deadline_at(&tv(999_999, 0), wall_now, now), now);
+    assert_eq!(deadline_at(&tv(0, 0), wall_now, now), now);
+    assert_eq!(deadline_from_timeval(std::ptr::null()), None);
+}
+
+/// Makes a Unix timestamp offset from now, for the tests.
+#[cfg(test)]
+fn unix_ts_from_now(offset: Duration, past: bool) -> crate::time::UnixTs {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+    let t = match past {
+        true => now - offset,
+        false => now + offset,
+    };
+    let tv = timeval { tv_sec: t.as_secs() as _, tv_usec: t.subsec_micros() as _ };
+    crate::time::UnixTs(Timeval(tv))
+}
+
+/// Check timer events are dispatched in order of their deadlines, including upon restarting, and
+/// not once freed
+#[test]
+fn tokio_time_dispatch() {
+    let rt = ::tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+    rt.block_on(async {
+        let mut mainloop = Mainloop::new().unwrap();
+        let api = mainloop.get_api() as *const MainloopApi;
+        let fired = Rc::new(RefCell::new(Vec::new()));
+
+        let fired_ref = Rc::clone(&fired);
+        let _late = mainloop.new_timer_event(&unix_ts_from_now(Duration::from_millis(200), false),
+            Box::new(move |_| {
+                fired_ref.borrow_mut().push("late");
+                unsafe { ((*api).quit.unwrap())(api, 5) };
+            })).unwrap();
+        let fired_ref = Rc::clone(&fired);
+        let _early = mainloop.new_timer_event(&unix_ts_from_now(Duration::from_secs(1), true),
+            Box::new(move |mut e| {
+                let mut fired = fired_ref.borrow_mut();
+                fired.push("early");
+                if fired.len() == 1 {
+                    e.restart(&unix_ts_from_now(Duration::from_millis(10), false));
+                }
+            })).unwrap();
+        let fired_ref = Rc::clone(&fired);
+        let freed = mainloop.new_timer_event(&unix_ts_from_now(Duration::from_millis(5), false),
+            Box::new(move |_| fired_ref.borrow_mut().push("freed"))).unwrap();
+        drop(freed);
+
+        assert_eq!(mainloop.run().await, def::Retval(5));
+        assert_eq!(*fired.borrow(), ["early", "early", "late"]);
+    });
+}
+
+/// Check deferred events are dispatched upon every pass while enabled, and not while disabled
+#[test]
+fn tokio_defer_dispatch() {
+    let rt = ::tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+    rt.block_on(async {
+        let mut mainloop = Mainloop::new().unwrap();
+        let api = mainloop.get_api() as *const MainloopApi;
+
+        let count = Rc::new(Cell::new(0));
+        let count_ref = Rc::clone(&count);
+        let _first = mainloop.new_deferred_event(Box::new(move |mut e| {
+            count_ref.set(count_ref.get() + 1);
+            if count_ref.get() == 3 {
+                e.disable();
+                unsafe { ((*api).quit.unwrap())(api, 1) };
+            }
+        })).unwrap();
+        let second_count = Rc::new(Cell::new(0));
+        let count_ref = Rc::clone(&second_count);
+        let mut second = mainloop.new_deferred_event(Box::new(move |mut e| {
+            count_ref.set(count_ref.get() + 1);
+            e.disable();
+            unsafe { ((*api).quit.unwrap())(api, 2) };
+        })).unwrap();
+        second.disable();
+
+        assert_eq!(mainloop.run().await, def::Retval(1));
+        assert_eq!((count.get(), second_count.get()), (3, 0));
+
+        second.enable();
+        assert_eq!(mainloop.run().await, def::Retval(2));
+        assert_eq!((count.get(), second_count.get()), (3, 1));
+    });
+}
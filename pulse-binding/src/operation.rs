@@ -12,10 +12,39 @@
 // fair-use basis, as discussed in the overall project readme (available in the git repository).
 
 //! Asynchronous operations.
+//!
+//! # Futures
+//!
+//! Operations that report completion through a callback can alternatively be awaited, using an
+//! [`OperationFuture`]. The future is created by handing a function that starts the operation to
+//! one of its constructors, which supplies the completion callback for you. For instance, for an
+//! operation that reports success:
+//!
+//! ```rust,ignore
+//! use libpulse_binding::operation::OperationFuture;
+//!
+//! let success = OperationFuture::success(|cb| stream.cork(Some(cb))).await?;
+//! let success = OperationFuture::success(|cb| context.set_default_sink("foo", cb)).await?;
+//! ```
+//!
+//! Operations fetching lists of items can similarly be consumed through an [`OperationStream`],
+//! yielding owned copies of the items, as provided by the various `_stream` suffixed introspection
+//! methods.
+//!
+//! Note that awaiting the future does not itself drive the mainloop; the mainloop must be running
+//! (or iterated) for the operation to make progress, and the usual rules about holding the
+//! threaded mainloop lock apply to the call starting the operation, as well as to dropping the
+//! future.
 
 use std::os::raw::c_void;
 use std::ptr::null_mut;
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
 use crate::callbacks;
+use crate::error::{Code, PAErr};
 
 use capi::pa_operation as OperationInternal;
 pub use capi::pa_operation_state_t as State;
@@ -99,6 +128,342 @@ impl<ClosureProto: ?Sized> Operation<ClosureProto> {
     }
 }
 
+/// Type-erased access to an [`Operation`], as held by futures and streams.
+trait ErasedOperation: Send {
+    /// Gets the current status of the operation.
+    fn get_state(&self) -> State;
+    /// Cancels the operation.
+    fn cancel(&mut self);
+}
+
+impl<ClosureProto: ?Sized> ErasedOperation for Operation<ClosureProto> {
+    #[inline]
+    fn get_state(&self) -> State {
+        Operation::get_state(self)
+    }
+
+    #[inline]
+    fn cancel(&mut self) {
+        Operation::cancel(self)
+    }
+}
+
+impl<ClosureProto: ?Sized + 'static> Operation<ClosureProto> {
+    /// Erases the closure type, setting up a state callback to run `on_cancel` should the
+    /// operation get cancelled, which its completion callback will never report.
+    fn into_erased<F>(mut self, mut on_cancel: F) -> Box<dyn ErasedOperation>
+        where F: FnMut() + 'static
+    {
+        // The operation may already have been cancelled, before the state callback is set.
+        if self.get_state() == State::Cancelled {
+            on_cancel();
+        }
+        else {
+            let op_ptr = self.ptr;
+            self.set_state_callback(Some(Box::new(move || {
+                if unsafe { capi::pa_operation_get_state(op_ptr) } == State::Cancelled {
+                    on_cancel();
+                }
+            })));
+        }
+        Box::new(self)
+    }
+}
+
+/// The error given for operations cancelled before completion.
+#[inline]
+fn cancelled_error() -> PAErr {
+    PAErr::from(Code::Killed)
+}
+
+/// A future tracking completion of an asynchronous [`Operation`].
+///
+/// It resolves to the value delivered to the operation’s completion callback, or to an error if
+/// the operation gets cancelled before completing (for instance if the context is disconnected).
+///
+/// If the future is dropped before completion, the operation is cancelled with
+/// [`Operation::cancel()`].
+#[must_use = "futures do nothing unless polled"]
+pub struct OperationFuture<T> {
+    /// The operation being tracked.
+    op: Box<dyn ErasedOperation>,
+    /// State shared with the operation’s callbacks.
+    shared: Arc<Mutex<FutureShared<T>>>,
+}
+
+/// State shared between an [`OperationFuture`] and the callbacks of its operation.
+struct FutureShared<T> {
+    /// The result, once delivered.
+    result: Option<Result<T, PAErr>>,
+    /// Whether or not a result has been delivered (it may have since been taken).
+    done: bool,
+    /// Waker of the task last polling the future.
+    waker: Option<Waker>,
+}
+
+/// Used by callbacks to deliver the result of an [`OperationFuture`].
+pub(crate) struct FutureCompleter<T>(Arc<Mutex<FutureShared<T>>>);
+
+impl<T> Clone for FutureCompleter<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        FutureCompleter(Arc::clone(&self.0))
+    }
+}
+
+impl<T> FutureCompleter<T> {
+    /// Delivers the result, waking the task awaiting it.
+    ///
+    /// Only the first result delivered is kept, any later one is ignored.
+    pub(crate) fn complete(&self, result: Result<T, PAErr>) {
+        let mut shared = self.0.lock().unwrap();
+        if shared.done {
+            return;
+        }
+        shared.result = Some(result);
+        shared.done = true;
+        if let Some(waker) = shared.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+impl<T: 'static> OperationFuture<T> {
+    /// Creates a new future.
+    ///
+    /// The `start` function is given a completer, which the closure callback it creates should
+    /// use to deliver the result, and must return the operation it starts.
+    pub(crate) fn new<ClosureProto, F>(start: F) -> Self
+        where ClosureProto: ?Sized + 'static,
+              F: FnOnce(FutureCompleter<T>) -> Operation<ClosureProto>
+    {
+        let shared = Arc::new(Mutex::new(FutureShared { result: None, done: false, waker: None }));
+        let completer = FutureCompleter(Arc::clone(&shared));
+        let op = start(completer.clone())
+            .into_erased(move || completer.complete(Err(cancelled_error())));
+        Self { op, shared }
+    }
+
+    /// Gets the current status of the operation.
+    #[inline]
+    pub fn get_state(&self) -> State {
+        self.op.get_state()
+    }
+}
+
+impl OperationFuture<bool> {
+    /// Creates a future for an operation reporting success.
+    ///
+    /// The `start` function is given the completion callback to use, and must return the operation
+    /// that it starts with it. The future resolves to the success value given to the callback.
+    ///
+    /// ```rust,ignore
+    /// let success = OperationFuture::success(|cb| {
+    ///     context.introspect().set_sink_volume_by_index(index, &volume, Some(cb))
+    /// }).await?;
+    /// ```
+    pub fn success<F>(start: F) -> Self
+        where F: FnOnce(Box<dyn FnMut(bool) + 'static>) -> Operation<dyn FnMut(bool)>
+    {
+        Self::new(|completer| start(Box::new(move |success| completer.complete(Ok(success)))))
+    }
+}
+
+impl<T> Future for OperationFuture<T> {
+    type Output = Result<T, PAErr>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut shared = self.shared.lock().unwrap();
+        match shared.result.take() {
+            Some(result) => Poll::Ready(result),
+            None => {
+                shared.waker = Some(cx.waker().clone());
+                Poll::Pending
+            },
+        }
+    }
+}
+
+impl<T> Drop for OperationFuture<T> {
+    fn drop(&mut self) {
+        let done = self.shared.lock().unwrap().done;
+        if !done && self.op.get_state() == State::Running {
+            self.op.cancel();
+        }
+    }
+}
+
+/// A stream of items delivered by an asynchronous list-fetching [`Operation`].
+///
+/// Each item is given as `Ok(item)`. Should the operation fail, or get cancelled before reaching
+/// the end of the list, a final `Err` is given. Use [`collect_vec()`](Self::collect_vec) to obtain
+/// all items at once.
+///
+/// With the `futures` feature enabled, this implements the `futures_core::Stream` trait.
+///
+/// If the stream is dropped before reaching the end of the list, the operation is cancelled with
+/// [`Operation::cancel()`].
+#[must_use = "streams do nothing unless polled"]
+pub struct OperationStream<T> {
+    /// The operation being tracked.
+    op: Box<dyn ErasedOperation>,
+    /// State shared with the operation’s callbacks.
+    shared: Arc<Mutex<StreamShared<T>>>,
+}
+
+/// State shared between an [`OperationStream`] and the callbacks of its operation.
+struct StreamShared<T> {
+    /// Items delivered but not yet taken.
+    items: VecDeque<Result<T, PAErr>>,
+    /// Whether or not the end has been reached (further items may remain to be taken).
+    finished: bool,
+    /// Waker of the task last polling the stream.
+    waker: Option<Waker>,
+}
+
+/// Used by callbacks to deliver the items of an [`OperationStream`].
+pub(crate) struct StreamSender<T>(Arc<Mutex<StreamShared<T>>>);
+
+impl<T> Clone for StreamSender<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        StreamSender(Arc::clone(&self.0))
+    }
+}
+
+impl<T> StreamSender<T> {
+    /// Delivers an item, waking the task awaiting it.
+    ///
+    /// Any item delivered after the end has been signalled is ignored.
+    pub(crate) fn send(&self, item: Result<T, PAErr>) {
+        let mut shared = self.0.lock().unwrap();
+        if shared.finished {
+            return;
+        }
+        shared.items.push_back(item);
+        if let Some(waker) = shared.waker.take() {
+            waker.wake();
+        }
+    }
+
+    /// Signals the end of the stream, optionally with a final error.
+    pub(crate) fn finish(&self, error: Option<PAErr>) {
+        let mut shared = self.0.lock().unwrap();
+        if shared.finished {
+            return;
+        }
+        if let Some(e) = error {
+            shared.items.push_back(Err(e));
+        }
+        shared.finished = true;
+        if let Some(waker) = shared.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+impl<T: 'static> OperationStream<T> {
+    /// Creates a new stream.
+    ///
+    /// The `start` function is given a sender, which the closure callback it creates should use to
+    /// deliver items, and must return the operation it starts.
+    pub(crate) fn new<ClosureProto, F>(start: F) -> Self
+        where ClosureProto: ?Sized + 'static,
+              F: FnOnce(StreamSender<T>) -> Operation<ClosureProto>
+    {
+        let shared = Arc::new(Mutex::new(StreamShared {
+            items: VecDeque::new(),
+            finished: false,
+            waker: None,
+        }));
+        let sender = StreamSender(Arc::clone(&shared));
+        let op = start(sender.clone())
+            .into_erased(move || sender.finish(Some(cancelled_error())));
+        Self { op, shared }
+    }
+
+    /// Gets the current status of the operation.
+    #[inline]
+    pub fn get_state(&self) -> State {
+        self.op.get_state()
+    }
+
+    /// Attempts to pull out the next item, registering the current task for wakeup if none is
+    /// available yet.
+    ///
+    /// Returns `Poll::Ready(None)` once the end has been reached.
+    pub fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>)
+        -> Poll<Option<Result<T, PAErr>>>
+    {
+        let mut shared = self.shared.lock().unwrap();
+        match shared.items.pop_front() {
+            Some(item) => Poll::Ready(Some(item)),
+            None if shared.finished => Poll::Ready(None),
+            None => {
+                shared.waker = Some(cx.waker().clone());
+                Poll::Pending
+            },
+        }
+    }
+
+    /// Converts into a future collecting all (remaining) items into a `Vec`.
+    ///
+    /// The future resolves to the first error encountered, if any.
+    #[inline]
+    pub fn collect_vec(self) -> CollectVec<T> {
+        CollectVec { stream: self, items: Vec::new() }
+    }
+}
+
+impl<T> Drop for OperationStream<T> {
+    fn drop(&mut self) {
+        let finished = self.shared.lock().unwrap().finished;
+        if !finished && self.op.get_state() == State::Running {
+            self.op.cancel();
+        }
+    }
+}
+
+#[cfg(feature = "futures")]
+#[cfg_attr(docsrs, doc(cfg(feature = "futures")))]
+impl<T: 'static> futures_core::Stream for OperationStream<T> {
+    type Item = Result<T, PAErr>;
+
+    #[inline]
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        OperationStream::poll_next(self, cx)
+    }
+}
+
+/// A future collecting all items of an [`OperationStream`] into a `Vec`.
+///
+/// See [`OperationStream::collect_vec()`].
+#[must_use = "futures do nothing unless polled"]
+pub struct CollectVec<T> {
+    /// The stream being collected.
+    stream: OperationStream<T>,
+    /// Items collected so far.
+    items: Vec<T>,
+}
+
+// Items are never pinned.
+impl<T> Unpin for CollectVec<T> {}
+
+impl<T: 'static> Future for CollectVec<T> {
+    type Output = Result<Vec<T>, PAErr>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        loop {
+            match Pin::new(&mut self.stream).poll_next(cx) {
+                Poll::Ready(Some(Ok(item))) => self.items.push(item),
+                Poll::Ready(Some(Err(e))) => return Poll::Ready(Err(e)),
+                Poll::Ready(None) => return Poll::Ready(Ok(std::mem::take(&mut self.items))),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
 impl<ClosureProto: ?Sized> Drop for Operation<ClosureProto> {
     fn drop(&mut self) {
         // Note, we deliberately do not destroy the `saved_cb` closure here. That should only be
@@ -119,3 +484,132 @@ fn notify_cb_proxy(_: *mut OperationInternal, userdata: *mut c_void) {
         (callback)();
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::task::Wake;
+
+    /// A stand-in for an operation, recording cancellation.
+    #[derive(Clone)]
+    struct FakeOperation(Arc<Mutex<State>>);
+
+    impl ErasedOperation for FakeOperation {
+        fn get_state(&self) -> State {
+            *self.0.lock().unwrap()
+        }
+
+        fn cancel(&mut self) {
+            *self.0.lock().unwrap() = State::Cancelled;
+        }
+    }
+
+    /// A waker counting its wakeups.
+    struct CountingWaker(AtomicUsize);
+
+    impl Wake for CountingWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    /// Creates a future tracking a fake operation, along with its completer.
+    fn fake_future<T>(op: &FakeOperation) -> (OperationFuture<T>, FutureCompleter<T>) {
+        let shared = Arc::new(Mutex::new(FutureShared { result: None, done: false, waker: None }));
+        let completer = FutureCompleter(Arc::clone(&shared));
+        (OperationFuture { op: Box::new(op.clone()), shared }, completer)
+    }
+
+    /// Creates a stream tracking a fake operation, along with its sender.
+    fn fake_stream<T>(op: &FakeOperation) -> (OperationStream<T>, StreamSender<T>) {
+        let shared = Arc::new(Mutex::new(StreamShared {
+            items: VecDeque::new(),
+            finished: false,
+            waker: None,
+        }));
+        let sender = StreamSender(Arc::clone(&shared));
+        (OperationStream { op: Box::new(op.clone()), shared }, sender)
+    }
+
+    /// Check that a future waits for, and resolves to, the first result delivered
+    #[test]
+    fn future_completion() {
+        let op = FakeOperation(Arc::new(Mutex::new(State::Running)));
+        let (mut future, completer) = fake_future::<u32>(&op);
+        let counter = Arc::new(CountingWaker(AtomicUsize::new(0)));
+        let waker = Waker::from(Arc::clone(&counter));
+        let mut cx = Context::from_waker(&waker);
+
+        assert!(Pin::new(&mut future).poll(&mut cx).is_pending());
+        completer.complete(Ok(1));
+        completer.complete(Ok(2));
+        assert_eq!(counter.0.load(Ordering::SeqCst), 1);
+        assert_eq!(Pin::new(&mut future).poll(&mut cx), Poll::Ready(Ok(1)));
+
+        // Completed, so dropping must not cancel
+        *op.0.lock().unwrap() = State::Done;
+        drop(future);
+        assert_eq!(op.get_state(), State::Done);
+    }
+
+    /// Check cancellation upon drop, and the error given once cancelled
+    #[test]
+    fn future_cancellation() {
+        let op = FakeOperation(Arc::new(Mutex::new(State::Running)));
+        let (future, _) = fake_future::<u32>(&op);
+        drop(future);
+        assert_eq!(op.get_state(), State::Cancelled);
+
+        let (mut future, completer) = fake_future::<u32>(&op);
+        completer.complete(Err(cancelled_error()));
+        let waker = Waker::from(Arc::new(CountingWaker(AtomicUsize::new(0))));
+        let result = Pin::new(&mut future).poll(&mut Context::from_waker(&waker));
+        assert_eq!(result, Poll::Ready(Err(PAErr::from(Code::Killed))));
+    }
+
+    /// Check items being yielded in order, ending with any final error, and nothing after the end
+    #[test]
+    fn stream_items() {
+        let op = FakeOperation(Arc::new(Mutex::new(State::Running)));
+        let (mut stream, sender) = fake_stream::<u32>(&op);
+        let counter = Arc::new(CountingWaker(AtomicUsize::new(0)));
+        let waker = Waker::from(Arc::clone(&counter));
+        let mut cx = Context::from_waker(&waker);
+
+        assert!(Pin::new(&mut stream).poll_next(&mut cx).is_pending());
+        sender.send(Ok(1));
+        sender.send(Ok(2));
+        sender.finish(Some(PAErr::from(Code::NoEntity)));
+        sender.send(Ok(3));
+        assert_eq!(counter.0.load(Ordering::SeqCst), 1);
+        assert_eq!(Pin::new(&mut stream).poll_next(&mut cx), Poll::Ready(Some(Ok(1))));
+        assert_eq!(Pin::new(&mut stream).poll_next(&mut cx), Poll::Ready(Some(Ok(2))));
+        assert_eq!(Pin::new(&mut stream).poll_next(&mut cx),
+            Poll::Ready(Some(Err(PAErr::from(Code::NoEntity)))));
+        assert_eq!(Pin::new(&mut stream).poll_next(&mut cx), Poll::Ready(None));
+
+        // Finished, so dropping must not cancel
+        drop(stream);
+        assert_eq!(op.get_state(), State::Running);
+    }
+
+    /// Check collecting, and cancellation of an unfinished stream upon drop
+    #[test]
+    fn stream_collect_and_cancel() {
+        let op = FakeOperation(Arc::new(Mutex::new(State::Running)));
+        let (stream, sender) = fake_stream::<u32>(&op);
+        let mut collect = stream.collect_vec();
+        let waker = Waker::from(Arc::new(CountingWaker(AtomicUsize::new(0))));
+        let mut cx = Context::from_waker(&waker);
+        sender.send(Ok(1));
+        assert!(Pin::new(&mut collect).poll(&mut cx).is_pending());
+        sender.send(Ok(2));
+        sender.finish(None);
+        assert_eq!(Pin::new(&mut collect).poll(&mut cx), Poll::Ready(Ok(vec![1, 2])));
+
+        let (stream, _) = fake_stream::<u32>(&op);
+        drop(stream);
+        assert_eq!(op.get_state(), State::Cancelled);
+    }
+}
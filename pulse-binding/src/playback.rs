@@ -0,0 +1,263 @@
+// Copyright 2026 Lyndon Brown
+//
+// This file is part of the PulseAudio Rust language binding.
+//
+// Licensed under the MIT license or the Apache license (version 2.0), at your option. You may not
+// copy, modify, or distribute this file except in compliance with said license. You can find copies
+// of these licenses either in the LICENSE-MIT and LICENSE-APACHE files, or alternatively at
+// <http://opensource.org/licenses/MIT> and <http://www.apache.org/licenses/LICENSE-2.0>
+// respectively.
+
+//! Pull-model playback.
+//!
+//! # Overview
+//!
+//! Feeding a playback stream involves responding to the write callback of the stream by obtaining
+//! a buffer with [`Stream::begin_write()`], filling it, and writing it with [`Stream::write()`],
+//! repeating until the amount requested has been written, along with draining the stream once the
+//! end of the data has been reached.
+//!
+//! Implementing the [`PlaybackSource`] trait instead allows a source of data to simply be asked to
+//! fill buffers of frames. A [`Playback`] attaches a source to a stream, taking care of the above.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use libpulse_binding::buffer::SampleBufMut;
+//! use libpulse_binding::playback::{Fill, Playback, PlaybackSource};
+//!
+//! /// Plays a square wave, on all channels, for a fixed number of frames.
+//! struct Square { remaining: usize, phase: usize }
+//!
+//! impl PlaybackSource for Square {
+//!     type Sample = f32;
+//!
+//!     fn fill(&mut self, buf: &mut SampleBufMut<'_, f32>) -> Fill {
+//!         let frames = std::cmp::min(buf.frame_count(), self.remaining);
+//!         for frame in buf.frames_mut().take(frames) {
+//!             self.phase = (self.phase + 1) % 100;
+//!             frame.fill(if self.phase < 50 { 0.2 } else { -0.2 });
+//!         }
+//!         self.remaining -= frames;
+//!         match self.remaining {
+//!             0 => Fill::End(frames),
+//!             _ => Fill::Frames(frames),
+//!         }
+//!     }
+//! }
+//!
+//! // With the stream connected for playback, in the `f32` native endian format
+//! let playback = Playback::new(&mut stream, Square { remaining: 44100, phase: 0 }).unwrap();
+//! ```
+
+use std::cell::{Cell, RefCell, RefMut};
+use std::rc::Rc;
+use crate::buffer::{Sample, SampleBufMut};
+use crate::error::{Code, PAErr};
+use crate::stream::{SeekMode, Stream};
+
+/// The outcome of filling a buffer.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Fill {
+    /// The given number of frames, from the start of the buffer, were filled. Filling fewer frames
+    /// than offered indicates that no more data is available for the time being.
+    Frames(usize),
+    /// The given number of frames, from the start of the buffer, were filled, and the end of the
+    /// data has been reached.
+    End(usize),
+}
+
+/// A source of data for a playback stream.
+///
+/// See the [module level documentation](mod@self) for details.
+pub trait PlaybackSource: 'static {
+    /// The type of the samples, which must be able to represent the sample format of the stream.
+    type Sample: Sample;
+
+    /// Fills the buffer, from the start, with as many frames as are available, up to the number of
+    /// frames it holds.
+    fn fill(&mut self, buf: &mut SampleBufMut<'_, Self::Sample>) -> Fill;
+
+    /// Called upon the server running out of data to play.
+    fn underflow(&mut self) {}
+
+    /// Called once the stream has been drained, after the end of the data was reached. The
+    /// parameter indicates success of the drain operation.
+    fn drained(&mut self, _success: bool) {}
+}
+
+/// A source of data attached to a playback stream.
+///
+/// See the [module level documentation](mod@self) for details.
+///
+/// Dropping this stops the source from being asked for data, though the write and underflow
+/// callbacks of the stream remain set until replaced.
+pub struct Playback<S: PlaybackSource> {
+    /// State shared with the stream callbacks.
+    shared: Rc<PlaybackShared<S>>,
+}
+
+/// State shared between a [`Playback`] and the stream callbacks.
+struct PlaybackShared<S: PlaybackSource> {
+    /// The stream, of which a reference is held.
+    stream: *mut capi::pa_stream,
+    /// The source of data.
+    source: RefCell<S>,
+    /// Whether or not the end of the data has been reached.
+    ended: Cell<bool>,
+    /// Whether or not the stream has been drained, after the end of the data was reached.
+    drained: Cell<bool>,
+    /// The number of underflows that have occurred.
+    underflows: Cell<u64>,
+    /// The last error encountered in writing data from within the write callback.
+    last_error: Cell<Option<PAErr>>,
+}
+
+impl<S: PlaybackSource> Playback<S> {
+    /// Attaches a source to a playback stream, replacing its write and underflow callbacks.
+    ///
+    /// The source will be asked for data as soon as the server requests it. If the source fills
+    /// fewer frames than offered, it will only be asked again upon the next request of the server,
+    /// or upon a call to [`pump()`](Self::pump).
+    ///
+    /// Returns the [`PAErr`] form of the [`Code::NotSupported`] error if the sample format of the
+    /// stream cannot be represented by the sample type of the source, or of the [`Code::BadState`]
+    /// error if the sample spec of the stream is not available.
+    pub fn new(stream: &mut Stream, source: S) -> Result<Self, PAErr> {
+        let spec = stream.get_sample_spec().ok_or(PAErr::from(Code::BadState))?;
+        if !S::Sample::is_compatible(spec.format) {
+            return Err(PAErr::from(Code::NotSupported));
+        }
+        let shared = Rc::new(PlaybackShared {
+            stream: unsafe { capi::pa_stream_ref(stream.ptr) },
+            source: RefCell::new(source),
+            ended: Cell::new(false),
+            drained: Cell::new(false),
+            underflows: Cell::new(0),
+            last_error: Cell::new(None),
+        });
+
+        let weak = Rc::downgrade(&shared);
+        stream.set_write_callback(Some(Box::new(move |nbytes| {
+            if let Some(shared) = weak.upgrade() {
+                if let Err(e) = shared.write(nbytes) {
+                    shared.last_error.set(Some(e));
+                }
+            }
+        })));
+        let weak = Rc::downgrade(&shared);
+        stream.set_underflow_callback(Some(Box::new(move || {
+            if let Some(shared) = weak.upgrade() {
+                shared.underflows.set(shared.underflows.get() + 1);
+                shared.source.borrow_mut().underflow();
+            }
+        })));
+
+        Ok(Self { shared })
+    }
+
+    /// Asks the source for as much data as the server can currently accept.
+    ///
+    /// Use this when more data has become available after the source filled fewer frames than
+    /// offered.
+    pub fn pump(&self) -> Result<(), PAErr> {
+        let stream = Stream::from_raw_weak(self.shared.stream);
+        let nbytes = stream.writable_size().ok_or(PAErr::from(Code::BadState))?;
+        self.shared.write(nbytes)
+    }
+
+    /// Checks whether or not the end of the data has been reached.
+    #[inline]
+    pub fn is_ended(&self) -> bool {
+        self.shared.ended.get()
+    }
+
+    /// Checks whether or not the stream has been drained, after the end of the data was reached.
+    #[inline]
+    pub fn is_drained(&self) -> bool {
+        self.shared.drained.get()
+    }
+
+    /// Gets the number of underflows that have occurred.
+    #[inline]
+    pub fn underflow_count(&self) -> u64 {
+        self.shared.underflows.get()
+    }
+
+    /// Gets the last error encountered in writing data from within the write callback, if any.
+    ///
+    /// Such an error leaves the request of the server unfulfilled, thus unless the stream has
+    /// failed, [`pump()`](Self::pump) may be used to try again.
+    #[inline]
+    pub fn last_error(&self) -> Option<PAErr> {
+        self.shared.last_error.get()
+    }
+
+    /// Gets the source.
+    ///
+    /// Panics if called from within a method of the source.
+    #[inline]
+    pub fn source(&self) -> RefMut<'_, S> {
+        self.shared.source.borrow_mut()
+    }
+}
+
+impl<S: PlaybackSource> PlaybackShared<S> {
+    /// Writes up to the specified number of bytes of data from the source.
+    fn write(self: &Rc<Self>, nbytes: usize) -> Result<(), PAErr> {
+        let mut stream = Stream::from_raw_weak(self.stream);
+        let frame_size = stream.get_sample_spec().ok_or(PAErr::from(Code::BadState))?.frame_size();
+        let mut frames = nbytes / frame_size;
+
+        // The buffer obtained may be smaller than requested, thus multiple may be needed
+        while frames > 0 && !self.ended.get() {
+            let mut buf = match stream.begin_write_samples::<S::Sample>(Some(frames))? {
+                Some(buf) => buf,
+                None => break,
+            };
+            let offered = buf.frame_count();
+            let (filled, end) = match offered {
+                0 => (0, false),
+                _ => match self.source.borrow_mut().fill(&mut buf) {
+                    Fill::Frames(n) => (std::cmp::min(n, offered), false),
+                    Fill::End(n) => (std::cmp::min(n, offered), true),
+                },
+            };
+
+            match filled {
+                0 => stream.cancel_write()?,
+                _ => {
+                    buf.truncate(filled);
+                    stream.write_samples(buf.as_slice(), 0, SeekMode::Relative)?;
+                },
+            }
+            frames = frames.saturating_sub(filled);
+
+            if end {
+                self.end(&mut stream);
+            }
+            else if filled < offered {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    /// Marks the end of the data as having been reached, and drains the stream.
+    fn end(self: &Rc<Self>, stream: &mut Stream) {
+        self.ended.set(true);
+        let weak = Rc::downgrade(self);
+        stream.drain(Some(Box::new(move |success| {
+            if let Some(shared) = weak.upgrade() {
+                shared.drained.set(true);
+                shared.source.borrow_mut().drained(success);
+            }
+        })));
+    }
+}
+
+impl<S: PlaybackSource> Drop for PlaybackShared<S> {
+    fn drop(&mut self) {
+        unsafe { capi::pa_stream_unref(self.stream) };
+    }
+}
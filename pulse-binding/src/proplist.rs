@@ -17,7 +17,7 @@ use std::os::raw::{c_char, c_void};
 use std::ffi::{CStr, CString};
 use std::ptr::{null, null_mut};
 use std::marker::PhantomData;
-use crate::error::PAErr;
+use crate::error::{Code, PAErr};
 
 pub(crate) use capi::pa_proplist as ProplistInternal;
 pub use capi::pa_update_mode_t as UpdateMode;
@@ -131,6 +131,125 @@ pub mod properties {
     pub const FORMAT_CHANNEL_MAP: &str = capi::PA_PROP_FORMAT_CHANNEL_MAP;
 }
 
+/// Defines an enum of the well-known values of a property, along with conversion to and from the
+/// string form used in property lists.
+macro_rules! property_value_enum {
+    (
+        $(#[$meta:meta])*
+        pub enum $name:ident {
+            $( $(#[$vmeta:meta])* $variant:ident => $value:literal, )+
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+        pub enum $name {
+            $( $(#[$vmeta])* $variant, )+
+        }
+
+        impl $name {
+            /// Gets the string form of the value, as stored in a property list.
+            pub const fn as_str(self) -> &'static str {
+                match self {
+                    $( $name::$variant => $value, )+
+                }
+            }
+        }
+
+        impl std::str::FromStr for $name {
+            type Err = ();
+
+            /// Parses the string form of the value.
+            ///
+            /// Returns `Err(())` if it is not one of the well-known values.
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                match s {
+                    $( $value => Ok($name::$variant), )+
+                    _ => Err(()),
+                }
+            }
+        }
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+    };
+}
+
+property_value_enum! {
+    /// Stream role, as held by the [`MEDIA_ROLE`](properties::MEDIA_ROLE) property.
+    pub enum MediaRole {
+        /// Video.
+        Video => "video",
+        /// Music.
+        Music => "music",
+        /// Game.
+        Game => "game",
+        /// Event sound, e.g. notifications.
+        Event => "event",
+        /// Phone/VoIP.
+        Phone => "phone",
+        /// Animation.
+        Animation => "animation",
+        /// Audio production.
+        Production => "production",
+        /// Accessibility, e.g. screen readers.
+        A11y => "a11y",
+        /// Testing.
+        Test => "test",
+    }
+}
+
+property_value_enum! {
+    /// Device class, as held by the [`DEVICE_CLASS`](properties::DEVICE_CLASS) property.
+    pub enum DeviceClass {
+        /// A sound device.
+        Sound => "sound",
+        /// A modem.
+        Modem => "modem",
+        /// A monitor of another device.
+        Monitor => "monitor",
+        /// A filter.
+        Filter => "filter",
+        /// A device not backed by hardware, e.g. a null sink.
+        Abstract => "abstract",
+    }
+}
+
+property_value_enum! {
+    /// Device form factor, as held by the
+    /// [`DEVICE_FORM_FACTOR`](properties::DEVICE_FORM_FACTOR) property.
+    pub enum DeviceFormFactor {
+        /// Internal.
+        Internal => "internal",
+        /// Speaker.
+        Speaker => "speaker",
+        /// Handset.
+        Handset => "handset",
+        /// TV.
+        Tv => "tv",
+        /// Webcam.
+        Webcam => "webcam",
+        /// Microphone.
+        Microphone => "microphone",
+        /// Headset.
+        Headset => "headset",
+        /// Headphone.
+        Headphone => "headphone",
+        /// Hands-free.
+        HandsFree => "hands-free",
+        /// Car.
+        Car => "car",
+        /// Hi-fi.
+        Hifi => "hifi",
+        /// Computer.
+        Computer => "computer",
+        /// Portable.
+        Portable => "portable",
+    }
+}
+
 /// A property list object. Basically a dictionary with ASCII strings as keys and arbitrary data as
 /// values.
 pub struct Proplist(pub(crate) ProplistInner);
@@ -493,6 +612,87 @@ impl Proplist {
     }
 }
 
+/// Typed accessors for well-known properties.
+///
+/// Getters return `None` if the property is not set, or if its value cannot be parsed as the
+/// expected type. Setters return the [`PAErr`] form of the [`Code::Invalid`] error on failure.
+impl Proplist {
+    /// Gets the [`APPLICATION_NAME`](properties::APPLICATION_NAME) property.
+    #[inline]
+    pub fn application_name(&self) -> Option<String> {
+        self.get_str(properties::APPLICATION_NAME)
+    }
+
+    /// Sets the [`APPLICATION_NAME`](properties::APPLICATION_NAME) property.
+    #[inline]
+    pub fn set_application_name(&mut self, name: &str) -> Result<(), PAErr> {
+        self.set_str(properties::APPLICATION_NAME, name)
+            .map_err(|_| PAErr::from(Code::Invalid))
+    }
+
+    /// Gets the [`MEDIA_ROLE`](properties::MEDIA_ROLE) property.
+    pub fn media_role(&self) -> Option<MediaRole> {
+        self.get_str(properties::MEDIA_ROLE)?.parse().ok()
+    }
+
+    /// Sets the [`MEDIA_ROLE`](properties::MEDIA_ROLE) property.
+    #[inline]
+    pub fn set_media_role(&mut self, role: MediaRole) -> Result<(), PAErr> {
+        self.set_str(properties::MEDIA_ROLE, role.as_str())
+            .map_err(|_| PAErr::from(Code::Invalid))
+    }
+
+    /// Gets the [`DEVICE_CLASS`](properties::DEVICE_CLASS) property.
+    pub fn device_class(&self) -> Option<DeviceClass> {
+        self.get_str(properties::DEVICE_CLASS)?.parse().ok()
+    }
+
+    /// Sets the [`DEVICE_CLASS`](properties::DEVICE_CLASS) property.
+    #[inline]
+    pub fn set_device_class(&mut self, class: DeviceClass) -> Result<(), PAErr> {
+        self.set_str(properties::DEVICE_CLASS, class.as_str())
+            .map_err(|_| PAErr::from(Code::Invalid))
+    }
+
+    /// Gets the [`DEVICE_FORM_FACTOR`](properties::DEVICE_FORM_FACTOR) property.
+    pub fn device_form_factor(&self) -> Option<DeviceFormFactor> {
+        self.get_str(properties::DEVICE_FORM_FACTOR)?.parse().ok()
+    }
+
+    /// Sets the [`DEVICE_FORM_FACTOR`](properties::DEVICE_FORM_FACTOR) property.
+    #[inline]
+    pub fn set_device_form_factor(&mut self, form_factor: DeviceFormFactor) -> Result<(), PAErr> {
+        self.set_str(properties::DEVICE_FORM_FACTOR, form_factor.as_str())
+            .map_err(|_| PAErr::from(Code::Invalid))
+    }
+
+    /// Gets the [`WINDOW_X`](properties::WINDOW_X) property, the X position of the window, in
+    /// pixels.
+    pub fn window_x(&self) -> Option<i32> {
+        self.get_str(properties::WINDOW_X)?.parse().ok()
+    }
+
+    /// Sets the [`WINDOW_X`](properties::WINDOW_X) property.
+    #[inline]
+    pub fn set_window_x(&mut self, x: i32) -> Result<(), PAErr> {
+        self.set_str(properties::WINDOW_X, &x.to_string())
+            .map_err(|_| PAErr::from(Code::Invalid))
+    }
+
+    /// Gets the [`WINDOW_Y`](properties::WINDOW_Y) property, the Y position of the window, in
+    /// pixels.
+    pub fn window_y(&self) -> Option<i32> {
+        self.get_str(properties::WINDOW_Y)?.parse().ok()
+    }
+
+    /// Sets the [`WINDOW_Y`](properties::WINDOW_Y) property.
+    #[inline]
+    pub fn set_window_y(&mut self, y: i32) -> Result<(), PAErr> {
+        self.set_str(properties::WINDOW_Y, &y.to_string())
+            .map_err(|_| PAErr::from(Code::Invalid))
+    }
+}
+
 impl Drop for ProplistInner {
     fn drop(&mut self) {
         if !self.weak {
@@ -512,6 +712,108 @@ impl Clone for Proplist {
     }
 }
 
+/// Serialized as a map. String values are serialized as strings, while any other (arbitrary data)
+/// values are serialized as bytes.
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl serde::Serialize for Proplist {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+
+        /// Arbitrary data value.
+        struct Bytes<'a>(&'a [u8]);
+
+        impl serde::Serialize for Bytes<'_> {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_bytes(self.0)
+            }
+        }
+
+        let mut map = serializer.serialize_map(Some(self.len() as usize))?;
+        for key in self.iter() {
+            match self.get_str(&key) {
+                Some(value) => map.serialize_entry(&key, &value)?,
+                None => map.serialize_entry(&key, &Bytes(self.get(&key).unwrap_or_default()))?,
+            }
+        }
+        map.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<'de> serde::Deserialize<'de> for Proplist {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::{Error, MapAccess, SeqAccess, Visitor};
+
+        /// A value, either a string or arbitrary data.
+        enum Value {
+            Str(String),
+            Bytes(Vec<u8>),
+        }
+
+        impl<'de> serde::Deserialize<'de> for Value {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                struct ValueVisitor;
+
+                impl<'de> Visitor<'de> for ValueVisitor {
+                    type Value = Value;
+
+                    fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                        f.write_str("a string or bytes")
+                    }
+
+                    fn visit_str<E: Error>(self, v: &str) -> Result<Value, E> {
+                        Ok(Value::Str(v.to_owned()))
+                    }
+
+                    fn visit_bytes<E: Error>(self, v: &[u8]) -> Result<Value, E> {
+                        Ok(Value::Bytes(v.to_vec()))
+                    }
+
+                    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Value, A::Error> {
+                        let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+                        while let Some(b) = seq.next_element()? {
+                            bytes.push(b);
+                        }
+                        Ok(Value::Bytes(bytes))
+                    }
+                }
+
+                deserializer.deserialize_any(ValueVisitor)
+            }
+        }
+
+        struct ProplistVisitor;
+
+        impl<'de> Visitor<'de> for ProplistVisitor {
+            type Value = Proplist;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                f.write_str("a map of property keys to values")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut access: A) -> Result<Proplist, A::Error> {
+                let mut list = Proplist::new()
+                    .ok_or_else(|| A::Error::custom("failed to create property list"))?;
+                while let Some((key, value)) = access.next_entry::<String, Value>()? {
+                    // Interior nul bytes cannot be passed through to the C API
+                    let result = match value {
+                        _ if key.contains('\0') => Err(()),
+                        Value::Str(s) if s.contains('\0') => Err(()),
+                        Value::Str(s) => list.set_str(&key, &s),
+                        Value::Bytes(b) => list.set(&key, &b),
+                    };
+                    result.map_err(|_| A::Error::custom(format!("invalid property `{}`", key)))?;
+                }
+                Ok(list)
+            }
+        }
+
+        deserializer.deserialize_map(ProplistVisitor)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -531,6 +833,37 @@ mod tests {
         }
     }
 */
+    /// Check that the well-known property values round-trip through their string form, and that
+    /// unknown values are rejected.
+    #[test]
+    fn property_values_parse() {
+        for role in [MediaRole::Music, MediaRole::Phone, MediaRole::A11y] {
+            assert_eq!(role.as_str().parse(), Ok(role));
+        }
+        assert_eq!("hands-free".parse(), Ok(DeviceFormFactor::HandsFree));
+        assert_eq!(DeviceClass::Abstract.to_string(), "abstract");
+        assert_eq!("phnoe".parse::<MediaRole>(), Err(()));
+        assert_eq!("Music".parse::<MediaRole>(), Err(()));
+    }
+
+    /// Check serialization round-trips, with string and arbitrary data values
+    #[cfg(feature = "serde")]
+    #[test]
+    fn proplist_serde() {
+        let mut list = Proplist::new().unwrap();
+        list.set_str(properties::MEDIA_NAME, "Song").unwrap();
+        list.set("x.data", &[0, 1, 255]).unwrap();
+        let value = serde_json::to_value(&list).unwrap();
+        assert_eq!(value, serde_json::json!({ "media.name": "Song", "x.data": [0, 1, 255] }));
+
+        let list = serde_json::from_value::<Proplist>(value).unwrap();
+        assert_eq!(list.len(), 2);
+        assert_eq!(list.get_str(properties::MEDIA_NAME).as_deref(), Some("Song"));
+        assert_eq!(list.get("x.data"), Some(&[0, 1, 255][..]));
+        assert!(serde_json::from_str::<Proplist>(r#"{ "a": 1 }"#).is_err());
+        assert!(serde_json::from_str::<Proplist>(r#"["a"]"#).is_err());
+    }
+
     /// Test that you can however return an iterator if you convert the `Proplist` into one
     #[test]
     fn proplist_iter_lifetime_conv() {
@@ -0,0 +1,277 @@
+// Copyright 2026 Lyndon Brown
+//
+// This file is part of the PulseAudio Rust language binding.
+//
+// Licensed under the MIT license or the Apache license (version 2.0), at your option. You may not
+// copy, modify, or distribute this file except in compliance with said license. You can find copies
+// of these licenses either in the LICENSE-MIT and LICENSE-APACHE files, or alternatively at
+// <http://opensource.org/licenses/MIT> and <http://www.apache.org/licenses/LICENSE-2.0>
+// respectively.
+
+//! A lock-free ring buffer, bridging a producer thread and a playback stream.
+//!
+//! # Overview
+//!
+//! Audio is often produced on a thread of its own, which must not block, whereas a [`Stream`] may
+//! only be used with the [threaded main loop] lock held, or from within the event loop thread.
+//!
+//! A single-producer, single-consumer ring buffer, created with [`channel()`], bridges the two. The
+//! [`Producer`] can be sent to the producing thread, and pushes samples without ever locking. The
+//! [`Consumer`] is a [`PlaybackSource`], to be attached to a playback stream, which takes samples
+//! from the buffer within the write callback of the stream. Should the producer fall behind, the
+//! stream is filled with silence, and an underrun recorded.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use libpulse_binding::ringbuffer;
+//!
+//! // Half a second of buffering
+//! let (mut producer, consumer) = ringbuffer::channel::<f32>(&spec, 22050).unwrap();
+//!
+//! // Attach the consumer with the threaded main loop lock held
+//! let guard = mainloop.lock_guard().unwrap();
+//! let playback = consumer.attach(&mut stream).unwrap();
+//! drop(guard);
+//!
+//! std::thread::spawn(move || loop {
+//!     let samples = render_next_block();
+//!     let mut pushed = 0;
+//!     while pushed < samples.len() {
+//!         pushed += producer.push(&samples[pushed..]);
+//!         // Wait a little before trying again, if the buffer is full
+//!     }
+//! });
+//! ```
+//!
+//! [threaded main loop]: mod@crate::mainloop::threaded
+//! [`Stream`]: crate::stream::Stream
+
+use std::cell::UnsafeCell;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use crate::buffer::{Sample, SampleBufMut};
+use crate::error::{Code, PAErr};
+use crate::playback::{Fill, Playback, PlaybackSource};
+use crate::sample::Spec;
+use crate::stream::Stream;
+
+/// Creates a ring buffer able to hold the specified number of frames of the specified sample spec,
+/// returning its producer and consumer sides.
+///
+/// Returns the [`PAErr`] form of the [`Code::NotSupported`] error if the format cannot be
+/// represented by the sample type, or of the [`Code::Invalid`] error if the number of frames or
+/// channels is zero.
+pub fn channel<T: Sample>(spec: &Spec, frames: usize) -> Result<(Producer<T>, Consumer<T>), PAErr> {
+    if !T::is_compatible(spec.format) {
+        return Err(PAErr::from(Code::NotSupported));
+    }
+    if frames == 0 || spec.channels == 0 {
+        return Err(PAErr::from(Code::Invalid));
+    }
+    let channels = spec.channels as usize;
+    let silence = T::silence(spec.format);
+    let shared = Arc::new(Shared {
+        buf: (0..frames * channels).map(|_| UnsafeCell::new(silence)).collect(),
+        read: AtomicUsize::new(0),
+        write: AtomicUsize::new(0),
+        underruns: AtomicU64::new(0),
+    });
+    let producer = Producer { shared: Arc::clone(&shared), channels };
+    let consumer = Consumer { shared, channels, silence };
+    Ok((producer, consumer))
+}
+
+/// State shared between the producer and consumer sides.
+///
+/// The read and write positions run over twice the capacity, allowing a full buffer to be told
+/// apart from an empty one. Each side only ever changes its own position, and only accesses the
+/// part of the buffer which the other side has released to it.
+struct Shared<T> {
+    /// The samples.
+    buf: Box<[UnsafeCell<T>]>,
+    /// The read position, changed by the consumer only.
+    read: AtomicUsize,
+    /// The write position, changed by the producer only.
+    write: AtomicUsize,
+    /// The number of underruns that have occurred.
+    underruns: AtomicU64,
+}
+
+unsafe impl<T: Send> Sync for Shared<T> {}
+
+impl<T: Copy> Shared<T> {
+    /// Gets the number of samples held, given the read and write positions.
+    #[inline]
+    fn len(&self, read: usize, write: usize) -> usize {
+        (write + 2 * self.buf.len() - read) % (2 * self.buf.len())
+    }
+
+    /// Copies samples, wrapping around the end of the buffer, from the specified position.
+    ///
+    /// Safety: The region must have been released to the calling side.
+    #[inline]
+    unsafe fn copy(&self, pos: usize, count: usize, mut f: impl FnMut(usize, &UnsafeCell<T>)) {
+        for i in 0..count {
+            f(i, &self.buf[(pos + i) % self.buf.len()]);
+        }
+    }
+
+    /// Advances a position by the specified number of samples.
+    #[inline]
+    fn advance(&self, pos: usize, count: usize) -> usize {
+        (pos + count) % (2 * self.buf.len())
+    }
+}
+
+/// The producer side of a ring buffer.
+///
+/// See the [module level documentation](mod@self) for details.
+pub struct Producer<T: Sample> {
+    /// State shared with the consumer.
+    shared: Arc<Shared<T>>,
+    /// The number of channels, thus samples per frame.
+    channels: usize,
+}
+
+unsafe impl<T: Sample + Send> Send for Producer<T> {}
+
+impl<T: Sample> Producer<T> {
+    /// Pushes as many whole frames of the interleaved samples as there is room for, returning the
+    /// number of samples pushed.
+    ///
+    /// This never blocks.
+    pub fn push(&mut self, samples: &[T]) -> usize {
+        let shared = &*self.shared;
+        let read = shared.read.load(Ordering::Acquire);
+        let write = shared.write.load(Ordering::Relaxed);
+        let free = shared.buf.len() - shared.len(read, write);
+        let count = std::cmp::min(free, samples.len());
+        let count = count - count % self.channels;
+        unsafe { shared.copy(write, count, |i, cell| *cell.get() = samples[i]) };
+        shared.write.store(shared.advance(write, count), Ordering::Release);
+        count
+    }
+
+    /// Gets the number of frames there is currently room for.
+    pub fn free_frames(&self) -> usize {
+        let shared = &*self.shared;
+        let read = shared.read.load(Ordering::Acquire);
+        let len = shared.len(read, shared.write.load(Ordering::Relaxed));
+        (shared.buf.len() - len) / self.channels
+    }
+
+    /// Gets the number of underruns that have occurred, where the consumer had to fill with
+    /// silence.
+    #[inline]
+    pub fn underrun_count(&self) -> u64 {
+        self.shared.underruns.load(Ordering::Relaxed)
+    }
+}
+
+/// The consumer side of a ring buffer.
+///
+/// See the [module level documentation](mod@self) for details.
+pub struct Consumer<T: Sample> {
+    /// State shared with the producer.
+    shared: Arc<Shared<T>>,
+    /// The number of channels, thus samples per frame.
+    channels: usize,
+    /// The value representing silence.
+    silence: T,
+}
+
+unsafe impl<T: Sample + Send> Send for Consumer<T> {}
+
+impl<T: Sample> Consumer<T> {
+    /// Attaches the consumer to a playback stream, as its source of data.
+    ///
+    /// With the threaded main loop, the lock must be held. See [`Playback::new()`] for the errors
+    /// possible.
+    #[inline]
+    pub fn attach(self, stream: &mut Stream) -> Result<Playback<Self>, PAErr> {
+        Playback::new(stream, self)
+    }
+
+    /// Pops as many whole frames as are available, up to the length of the slice, returning the
+    /// number of samples popped.
+    pub fn pop(&mut self, out: &mut [T]) -> usize {
+        let shared = &*self.shared;
+        let read = shared.read.load(Ordering::Relaxed);
+        let write = shared.write.load(Ordering::Acquire);
+        let count = std::cmp::min(shared.len(read, write), out.len());
+        let count = count - count % self.channels;
+        unsafe { shared.copy(read, count, |i, cell| out[i] = *cell.get()) };
+        shared.read.store(shared.advance(read, count), Ordering::Release);
+        count
+    }
+
+    /// Gets the number of frames currently available.
+    pub fn available_frames(&self) -> usize {
+        let shared = &*self.shared;
+        let write = shared.write.load(Ordering::Acquire);
+        let len = shared.len(shared.read.load(Ordering::Relaxed), write);
+        len / self.channels
+    }
+
+    /// Gets the number of underruns that have occurred, where the consumer had to fill with
+    /// silence.
+    #[inline]
+    pub fn underrun_count(&self) -> u64 {
+        self.shared.underruns.load(Ordering::Relaxed)
+    }
+}
+
+impl<T: Sample> PlaybackSource for Consumer<T> {
+    type Sample = T;
+
+    /// Fills the buffer entirely, with silence following whatever is available, recording an
+    /// underrun if any silence was needed.
+    fn fill(&mut self, buf: &mut SampleBufMut<'_, T>) -> Fill {
+        let out = buf.as_mut_slice();
+        let popped = self.pop(out);
+        if popped < out.len() {
+            out[popped..].fill(self.silence);
+            self.shared.underruns.fetch_add(1, Ordering::Relaxed);
+        }
+        Fill::Frames(buf.frame_count())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sample::Format;
+
+    /// Check pushing and popping whole frames, across the end of the buffer
+    #[test]
+    fn wrap_around() {
+        let spec = Spec { format: Format::S16NE, channels: 2, rate: 44100 };
+        let (mut producer, mut consumer) = channel::<i16>(&spec, 3).unwrap();
+
+        assert_eq!(producer.push(&[1, 2, 3, 4, 5]), 4);
+        assert_eq!(producer.free_frames(), 1);
+        let mut out = [0; 6];
+        assert_eq!(consumer.pop(&mut out[..2]), 2);
+        assert_eq!(producer.push(&[5, 6, 7, 8, 9, 10]), 4);
+        assert_eq!(producer.free_frames(), 0);
+        assert_eq!(consumer.available_frames(), 3);
+        assert_eq!(consumer.pop(&mut out), 6);
+        assert_eq!(out, [3, 4, 5, 6, 7, 8]);
+        assert_eq!(consumer.available_frames(), 0);
+    }
+
+    /// Check filling with silence, and recording of underruns
+    #[test]
+    fn underrun() {
+        let spec = Spec { format: Format::U8, channels: 1, rate: 8000 };
+        let (mut producer, mut consumer) = channel::<u8>(&spec, 4).unwrap();
+        producer.push(&[1, 2]);
+
+        let mut data = [0; 4];
+        let mut buf = SampleBufMut::new(&mut data, 1).unwrap();
+        assert_eq!(consumer.fill(&mut buf), Fill::Frames(4));
+        assert_eq!(data, [1, 2, 0x80, 0x80]);
+        assert_eq!(producer.underrun_count(), 1);
+    }
+}
@@ -66,6 +66,7 @@
 use std::ffi::{CStr, CString};
 use std::borrow::Cow;
 use num_derive::{FromPrimitive, ToPrimitive};
+use crate::error::PAErr;
 use crate::time::MicroSeconds;
 
 /// Sample format.
@@ -76,6 +77,7 @@ use crate::time::MicroSeconds;
 #[repr(C)]
 #[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
 #[derive(FromPrimitive, ToPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[allow(non_camel_case_types)]
 pub enum Format {
     /* NOTE: This enum’s variants and variant values **must** remain identical to the `sys` crate
@@ -188,6 +190,7 @@ mod ei_formats {
 /// A sample format and attribute specification.
 #[repr(C)]
 #[derive(Debug, Copy, Clone, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Spec {
     /* NOTE: This struct must be directly usable by the C API, thus same attributes/layout/etc */
     /// The sample format.
@@ -429,4 +432,18 @@ impl Format {
     pub fn is_re(&self) -> Option<bool> {
         self.is_ne().and_then(|b| Some(!b))
     }
+
+    /// Converts samples of this format to another format, returning the number of samples
+    /// converted.
+    ///
+    /// This is done purely in Rust, as the server does, without dither. The output must be exactly
+    /// as long as needed for the samples of the input. Returns the [`PAErr`] form of the
+    /// [`Code::Invalid`] error otherwise, or if either format is invalid, or the input is not a
+    /// whole number of samples. See [`Converter`] for details, and for conversion with dither.
+    ///
+    /// [`Code::Invalid`]: crate::error::Code::Invalid
+    /// [`Converter`]: crate::dsp::convert::Converter
+    pub fn convert(self, to: Self, input: &[u8], output: &mut [u8]) -> Result<usize, PAErr> {
+        crate::dsp::convert::convert(self, to, input, output, None)
+    }
 }
@@ -223,15 +223,18 @@ use std::os::raw::{c_char, c_void};
 use std::ffi::{CStr, CString};
 use std::ptr::{null, null_mut};
 use std::borrow::Cow;
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
 use bitflags::bitflags;
 use num_derive::{FromPrimitive, ToPrimitive};
 use capi::pa_stream as StreamInternal;
-use crate::{channelmap, format, def, proplist, sample};
+use crate::{buffer, channelmap, direction, format, def, proplist, sample};
 use crate::callbacks::{self, box_closure_get_capi_ptr, get_su_capi_params, get_su_callback};
-use crate::error::{self, PAErr};
+use crate::error::{self, Code, PAErr};
 use crate::format::InfoInternal;
 use crate::proplist::{Proplist, ProplistInternal};
 use crate::{context::Context, volume::ChannelVolumes, operation::Operation, time::MicroSeconds};
+use crate::mainloop::{standard, threaded};
 
 pub use capi::pa_seek_mode_t as SeekMode;
 pub use capi::pa_stream_direction_t as Direction;
@@ -241,7 +244,9 @@ pub use capi::pa_stream_direction_t as Direction;
 /// Note: Saves a copy of active multi-use closure callbacks, which it frees on drop.
 pub struct Stream {
     /// The actual C object.
-    ptr: *mut StreamInternal,
+    pub(crate) ptr: *mut StreamInternal,
+    /// Used to avoid destroying the internal object when used as a weak wrapper in callbacks.
+    weak: bool,
     /// Multi-use callback closure pointers.
     cb_ptrs: CallbackPointers,
 }
@@ -272,6 +277,9 @@ type RequestCb = callbacks::MultiUseCallback<dyn FnMut(usize),
 type NotifyCb = callbacks::MultiUseCallback<dyn FnMut(),
     extern "C" fn(*mut StreamInternal, *mut c_void)>;
 
+/// Callback given buffer attributes, for [`Stream::set_granted_buffer_attr_callback()`].
+type GrantedBufferAttrCb = Box<dyn FnMut(&def::BufferAttr) + 'static>;
+
 type EventCb = callbacks::MultiUseCallback<dyn FnMut(String, Proplist),
     extern "C" fn(*mut StreamInternal, name: *const c_char, pl: *mut ProplistInternal, *mut c_void)>;
 
@@ -538,6 +546,18 @@ pub enum Latency {
     Negative(MicroSeconds),
 }
 
+impl Latency {
+    /// Gets the latency as a signed number of microseconds, or `None` if there is none.
+    #[inline]
+    pub fn as_micros_signed(&self) -> Option<i64> {
+        match *self {
+            Latency::None => None,
+            Latency::Positive(t) => Some(t.0 as i64),
+            Latency::Negative(t) => Some(-(t.0 as i64)),
+        }
+    }
+}
+
 impl Stream {
     /// Creates a new, unconnected stream with the specified name and sample type.
     ///
@@ -639,7 +659,17 @@ impl Stream {
     #[inline]
     fn from_raw(ptr: *mut StreamInternal) -> Self {
         assert_eq!(false, ptr.is_null());
-        Self { ptr: ptr, cb_ptrs: Default::default() }
+        Self { ptr: ptr, weak: false, cb_ptrs: Default::default() }
+    }
+
+    /// Creates a new `Stream` from an existing [`StreamInternal`] pointer.
+    ///
+    /// This is the ‘weak’ version, which avoids disconnecting and destroying the internal object
+    /// when dropped.
+    #[inline]
+    pub(crate) fn from_raw_weak(ptr: *mut StreamInternal) -> Self {
+        assert!(!ptr.is_null());
+        Self { ptr, weak: true, cb_ptrs: Default::default() }
     }
 
     /// Gets the current state of the stream.
@@ -1000,6 +1030,78 @@ impl Stream {
         }
     }
 
+    /// Writes some data to the server (for playback streams), taking ownership of it.
+    ///
+    /// This is a safe form of [`write_ext_free()`], avoiding a copy where possible. The vector is
+    /// dropped once the server-side memory block referencing it is freed, or immediately should
+    /// writing fail.
+    ///
+    /// See [`write_ext_free()`] for a description of the other parameters.
+    ///
+    /// [`write_ext_free()`]: Self::write_ext_free
+    #[cfg(any(doc, feature = "pa_v6"))]
+    #[cfg_attr(docsrs, doc(cfg(feature = "pa_v6")))]
+    #[inline]
+    pub fn write_owned(&mut self, data: Vec<u8>, offset: i64, seek: SeekMode)
+        -> Result<(), PAErr>
+    {
+        self.write_owner(data, offset, seek)
+    }
+
+    /// Writes some shared data to the server (for playback streams), holding a reference to it.
+    ///
+    /// This is the same as [`write_owned()`](Self::write_owned), except for taking shared data.
+    #[cfg(any(doc, feature = "pa_v6"))]
+    #[cfg_attr(docsrs, doc(cfg(feature = "pa_v6")))]
+    #[inline]
+    pub fn write_arc(&mut self, data: std::sync::Arc<[u8]>, offset: i64, seek: SeekMode)
+        -> Result<(), PAErr>
+    {
+        self.write_owner(data, offset, seek)
+    }
+
+    /// Writes a `bytes::Bytes` buffer to the server (for playback streams), taking ownership of
+    /// it.
+    ///
+    /// This is the same as [`write_owned()`](Self::write_owned), except for taking a `Bytes`
+    /// buffer.
+    #[cfg(all(feature = "bytes", any(doc, feature = "pa_v6")))]
+    #[cfg_attr(docsrs, doc(cfg(all(feature = "bytes", feature = "pa_v6"))))]
+    #[inline]
+    pub fn write_bytes(&mut self, data: bytes::Bytes, offset: i64, seek: SeekMode)
+        -> Result<(), PAErr>
+    {
+        self.write_owner(data, offset, seek)
+    }
+
+    /// Writes data owned by `owner`, which is dropped once no longer needed.
+    #[cfg(any(doc, feature = "pa_v6"))]
+    fn write_owner<T>(&mut self, owner: T, offset: i64, seek: SeekMode) -> Result<(), PAErr>
+        where T: AsRef<[u8]> + Send + 'static
+    {
+        let owner = Box::new(owner);
+        let data = (*owner).as_ref();
+        if data.is_empty() {
+            return self.write(data, None, offset, seek);
+        }
+        debug_assert_eq!(0, data.len().checked_rem(self.get_sample_spec().unwrap().frame_size())
+            .unwrap());
+        let (ptr, len) = (data.as_ptr(), data.len());
+        let userdata = Box::into_raw(owner) as *mut c_void;
+        let r = unsafe {
+            capi::pa_stream_write_ext_free(self.ptr, ptr as *const c_void, len,
+                Some(free_owner_proxy::<T>), userdata, offset, seek)
+        };
+        match r {
+            0 => Ok(()),
+            e => {
+                // Not taken ownership of upon failure
+                drop(unsafe { Box::from_raw(userdata as *mut T) });
+                Err(PAErr(e))
+            },
+        }
+    }
+
     /// Reads the next fragment from the buffer (for recording streams).
     ///
     /// This function returns one of the [`PeekResult`] variants - either [`Empty`], [`Hole`] or
@@ -1046,6 +1148,75 @@ impl Stream {
         }
     }
 
+    /// Writes some samples to the server (for playback streams), copying them.
+    ///
+    /// This is a typed form of [`write_copy()`](Self::write_copy). The samples must be of a type
+    /// able to represent the stream’s sample format, otherwise the [`PAErr`] form of the
+    /// [`Code::NotSupported`] error is returned. Samples from a buffer obtained with
+    /// [`begin_write_samples()`](Self::begin_write_samples) are not actually copied.
+    pub fn write_samples<T: buffer::Sample>(&mut self, data: &[T], offset: i64, seek: SeekMode)
+        -> Result<(), PAErr>
+    {
+        let spec = self.get_sample_spec().ok_or(PAErr::from(Code::BadState))?;
+        if !T::is_compatible(spec.format) {
+            return Err(PAErr::from(Code::NotSupported));
+        }
+        self.write_copy(buffer::as_bytes(data), offset, seek)
+    }
+
+    /// Prepares writing samples to the server (for playback streams).
+    ///
+    /// This is a typed form of [`begin_write()`](Self::begin_write), with the size, if given,
+    /// being a number of frames. The buffer returned is shortened to a whole number of frames.
+    /// Pass its samples to [`write_samples()`](Self::write_samples) to execute the write, subject
+    /// to the same restrictions as with [`begin_write()`](Self::begin_write).
+    ///
+    /// The samples must be of a type able to represent the stream’s sample format, otherwise the
+    /// [`PAErr`] form of the [`Code::NotSupported`] error is returned, with the write cancelled.
+    pub fn begin_write_samples<'a, T: buffer::Sample>(&mut self, frames: Option<usize>)
+        -> Result<Option<buffer::SampleBufMut<'a, T>>, PAErr>
+    {
+        let spec = *self.get_sample_spec().ok_or(PAErr::from(Code::BadState))?;
+        if !T::is_compatible(spec.format) {
+            return Err(PAErr::from(Code::NotSupported));
+        }
+        let frame_size = spec.frame_size();
+        let data = match self.begin_write(frames.map(|f| f * frame_size))? {
+            Some(data) => data,
+            None => return Ok(None),
+        };
+        let len = data.len() - data.len() % frame_size;
+        match buffer::SampleBufMut::from_bytes(&mut data[..len], &spec) {
+            Ok(buf) => Ok(Some(buf)),
+            Err(e) => {
+                self.cancel_write()?;
+                Err(e)
+            },
+        }
+    }
+
+    /// Reads the next fragment from the buffer as samples (for recording streams).
+    ///
+    /// This is a typed form of [`peek()`](Self::peek), with the same usage. The samples must be of
+    /// a type able to represent the stream’s sample format, otherwise the [`PAErr`] form of the
+    /// [`Code::NotSupported`] error is returned. The [`PAErr`] form of the [`Code::Invalid`]
+    /// error is returned if the fragment does not hold a whole number of frames.
+    pub fn peek_samples<'a, T: buffer::Sample>(&mut self)
+        -> Result<buffer::PeekSamples<'a, T>, PAErr>
+    {
+        let spec = *self.get_sample_spec().ok_or(PAErr::from(Code::BadState))?;
+        if !T::is_compatible(spec.format) {
+            return Err(PAErr::from(Code::NotSupported));
+        }
+        match self.peek()? {
+            PeekResult::Empty => Ok(buffer::PeekSamples::Empty),
+            PeekResult::Hole(size) => Ok(buffer::PeekSamples::Hole(size)),
+            PeekResult::Data(data) => {
+                Ok(buffer::PeekSamples::Data(buffer::SampleBuf::from_bytes(data, &spec)?))
+            },
+        }
+    }
+
     /// Removes the current fragment on record streams.
     ///
     /// It is invalid to do this without first calling [`peek()`](Self::peek).
@@ -1265,6 +1436,31 @@ impl Stream {
         unsafe { capi::pa_stream_set_buffer_attr_callback(self.ptr, cb_fn, cb_data); }
     }
 
+    /// Sets the callback function that is called whenever the server changes the buffer attributes
+    /// of the stream, being given the attributes now in effect.
+    ///
+    /// This is a convenience form of [`set_buffer_attr_callback()`], which it replaces, with the
+    /// attributes obtained with [`get_buffer_attr()`]. Note that it is not called for the
+    /// attributes granted upon connection, nor upon a [`set_buffer_attr()`] request; use
+    /// [`get_buffer_attr()`] for those.
+    ///
+    /// [`set_buffer_attr_callback()`]: Self::set_buffer_attr_callback
+    /// [`get_buffer_attr()`]: Self::get_buffer_attr
+    /// [`set_buffer_attr()`]: Self::set_buffer_attr
+    pub fn set_granted_buffer_attr_callback(&mut self,
+        callback: Option<GrantedBufferAttrCb>)
+    {
+        let ptr = self.ptr;
+        self.set_buffer_attr_callback(callback.map(|mut callback| -> Box<dyn FnMut()> {
+            // The callback is owned by the stream, thus the stream outlives it
+            Box::new(move || {
+                if let Some(attr) = Stream::from_raw_weak(ptr).get_buffer_attr() {
+                    (callback)(attr);
+                }
+            })
+        }));
+    }
+
     /// Pauses playback of this stream temporarily.
     ///
     /// This simply calls [`set_corked_state()`] with a value of `true`.
@@ -1638,16 +1834,570 @@ impl Stream {
     }
 }
 
+/// How to wait for a stream to become ready, for [`StreamBuilder::wait_ready()`].
+pub enum Wait<'a> {
+    /// Iterate the standard main loop until the stream is ready, failing should it be quit.
+    Standard(&'a mut standard::Mainloop),
+    /// Wait upon the threaded main loop, with its lock held by the given guard, until the stream is
+    /// ready. The state callback of the stream is used in the process, and unset afterwards.
+    Threaded(&'a threaded::Mainloop, &'a mut threaded::LockGuard),
+}
+
+/// A builder for creating and connecting streams.
+///
+/// This gathers the many optional parameters of creating and connecting a stream, validating the
+/// sample spec and channel map given, and allowing the buffer attributes to be derived from a
+/// target latency.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use libpulse_binding::stream::{StreamBuilder, Wait};
+///
+/// let stream = StreamBuilder::new("Music", &spec)
+///     .latency(MicroSeconds::from_millis(50).unwrap())
+///     .wait_ready(Wait::Standard(&mut mainloop))
+///     .connect_playback(&mut context)?;
+/// ```
+pub struct StreamBuilder<'a> {
+    /// The name of the stream.
+    name: &'a str,
+    /// The sample spec.
+    spec: sample::Spec,
+    /// The channel map, if not the default.
+    map: Option<channelmap::Map>,
+    /// The initial properties.
+    proplist: Option<&'a mut Proplist>,
+    /// The sink or source to connect to, if not the default.
+    device: Option<&'a str>,
+    /// The buffer attributes.
+    attr: Option<def::BufferAttr>,
+    /// The target latency, from which to derive buffer attributes.
+    latency: Option<MicroSeconds>,
+    /// The buffering strategy with which to derive buffer attributes, if not the default.
+    latency_mode: Option<def::LatencyMode>,
+    /// The flags to connect with.
+    flags: FlagSet,
+    /// The initial volume.
+    volume: Option<ChannelVolumes>,
+    /// The stream to synchronize with.
+    sync_stream: Option<&'a mut Stream>,
+    /// How to wait for the stream to become ready, if at all.
+    wait: Option<Wait<'a>>,
+}
+
+impl<'a> StreamBuilder<'a> {
+    /// Creates a new builder, for a stream with the specified name and sample spec.
+    pub fn new(name: &'a str, spec: &sample::Spec) -> Self {
+        Self {
+            name,
+            spec: *spec,
+            map: None,
+            proplist: None,
+            device: None,
+            attr: None,
+            latency: None,
+            latency_mode: None,
+            flags: FlagSet::NOFLAGS,
+            volume: None,
+            sync_stream: None,
+            wait: None,
+        }
+    }
+
+    /// Sets the channel map, which must be compatible with the sample spec. If not set, the default
+    /// channel map for the number of channels is used.
+    pub fn channel_map(mut self, map: &channelmap::Map) -> Self {
+        self.map = Some(*map);
+        self
+    }
+
+    /// Sets the initial properties of the stream.
+    pub fn proplist(mut self, proplist: &'a mut Proplist) -> Self {
+        self.proplist = Some(proplist);
+        self
+    }
+
+    /// Sets the name of the sink or source to connect to. If not set, the default is used.
+    pub fn device(mut self, device: &'a str) -> Self {
+        self.device = Some(device);
+        self
+    }
+
+    /// Sets the buffer attributes, taking precedence over any [`latency()`](Self::latency).
+    pub fn buffer_attr(mut self, attr: &def::BufferAttr) -> Self {
+        self.attr = Some(*attr);
+        self
+    }
+
+    /// Sets the target latency, from which the buffer attributes are derived with
+    /// [`BufferAttr::for_latency()`], along with setting [`FlagSet::ADJUST_LATENCY`], other than
+    /// for [`LatencyMode::PowerSaving`].
+    ///
+    /// The mode used is that set with [`latency_mode()`](Self::latency_mode), or otherwise
+    /// [`LatencyMode::Balanced`] for playback streams, and [`LatencyMode::Capture`] for recording
+    /// streams.
+    ///
+    /// [`BufferAttr::for_latency()`]: def::BufferAttr::for_latency
+    /// [`LatencyMode::Balanced`]: def::LatencyMode::Balanced
+    /// [`LatencyMode::PowerSaving`]: def::LatencyMode::PowerSaving
+    /// [`LatencyMode::Capture`]: def::LatencyMode::Capture
+    pub fn latency(mut self, latency: MicroSeconds) -> Self {
+        self.latency = Some(latency);
+        self
+    }
+
+    /// Sets the buffering strategy with which buffer attributes are derived from the target
+    /// latency.
+    pub fn latency_mode(mut self, mode: def::LatencyMode) -> Self {
+        self.latency_mode = Some(mode);
+        self
+    }
+
+    /// Sets the flags to connect with, added to any implied by other settings.
+    pub fn flags(mut self, flags: FlagSet) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    /// Sets the initial volume (playback only).
+    pub fn volume(mut self, volume: &ChannelVolumes) -> Self {
+        self.volume = Some(*volume);
+        self
+    }
+
+    /// Sets a stream to synchronize with (playback only).
+    pub fn sync_stream(mut self, stream: &'a mut Stream) -> Self {
+        self.sync_stream = Some(stream);
+        self
+    }
+
+    /// Waits for the stream to become ready upon connecting, rather than returning immediately.
+    pub fn wait_ready(mut self, wait: Wait<'a>) -> Self {
+        self.wait = Some(wait);
+        self
+    }
+
+    /// Creates the stream and connects it to a sink, for playback.
+    ///
+    /// Returns the [`PAErr`] form of the [`Code::Invalid`] error if the sample spec is invalid,
+    /// or the channel map is incompatible with it, and that of [`Code::Killed`] if the standard
+    /// main loop being waited upon is quit. Otherwise should creation, connection, or waiting
+    /// fail, the error of the context is returned.
+    pub fn connect_playback(mut self, ctx: &mut Context) -> Result<Stream, PAErr> {
+        let sync_stream = self.sync_stream.take();
+        self.connect_playback_synced(ctx, sync_stream)
+    }
+
+    /// Creates the stream and connects it to a sink, for playback, synchronized with the specified
+    /// stream, rather than any set.
+    fn connect_playback_synced(mut self, ctx: &mut Context, sync_stream: Option<&mut Stream>)
+        -> Result<Stream, PAErr>
+    {
+        let mut stream = self.create(ctx)?;
+        let (attr, flags) = self.attr_and_flags(direction::FlagSet::OUTPUT)?;
+        stream.connect_playback(self.device, attr.as_ref(), flags, self.volume.as_ref(),
+            sync_stream)?;
+        self.wait(ctx, stream)
+    }
+
+    /// Creates the stream and connects it to a source, for recording.
+    ///
+    /// See [`connect_playback()`](Self::connect_playback) for the errors possible. The volume and
+    /// sync stream, if set, are ignored.
+    pub fn connect_record(mut self, ctx: &mut Context) -> Result<Stream, PAErr> {
+        let mut stream = self.create(ctx)?;
+        let (attr, flags) = self.attr_and_flags(direction::FlagSet::INPUT)?;
+        stream.connect_record(self.device, attr.as_ref(), flags)?;
+        self.wait(ctx, stream)
+    }
+
+    /// Validates the sample spec, and the channel map against it.
+    fn validate(&self) -> Result<(), PAErr> {
+        if !self.spec.is_valid() {
+            return Err(PAErr::from(Code::Invalid));
+        }
+        if let Some(map) = self.map.as_ref() {
+            if !map.is_compatible_with_sample_spec(&self.spec) {
+                return Err(PAErr::from(Code::Invalid));
+            }
+        }
+        Ok(())
+    }
+
+    /// Validates the parameters, and creates the stream.
+    fn create(&mut self, ctx: &mut Context) -> Result<Stream, PAErr> {
+        self.validate()?;
+        let stream = match self.proplist.as_mut() {
+            Some(proplist) => {
+                Stream::new_with_proplist(ctx, self.name, &self.spec, self.map.as_ref(), proplist)
+            },
+            None => Stream::new(ctx, self.name, &self.spec, self.map.as_ref()),
+        };
+        stream.ok_or_else(|| ctx.errno())
+    }
+
+    /// Gets the buffer attributes and flags to connect with in the specified direction, deriving
+    /// the attributes from the latency, if set, with the latency mode set, or otherwise the
+    /// default mode of the direction.
+    fn attr_and_flags(&self, direction: direction::FlagSet)
+        -> Result<(Option<def::BufferAttr>, FlagSet), PAErr>
+    {
+        match (self.attr, self.latency) {
+            (Some(attr), _) => Ok((Some(attr), self.flags)),
+            (None, Some(latency)) => {
+                let mode = self.latency_mode.unwrap_or(match direction {
+                    direction::FlagSet::INPUT => def::LatencyMode::Capture,
+                    _ => def::LatencyMode::Balanced,
+                });
+                let attr = def::BufferAttr::for_latency(&self.spec, latency, mode)
+                    .ok_or(PAErr::from(Code::Invalid))?;
+                let flags = match mode {
+                    def::LatencyMode::PowerSaving => self.flags,
+                    _ => self.flags | FlagSet::ADJUST_LATENCY,
+                };
+                Ok((Some(attr), flags))
+            },
+            (None, None) => Ok((None, self.flags)),
+        }
+    }
+
+    /// Waits for the stream to become ready, if asked to.
+    fn wait(self, ctx: &Context, mut stream: Stream) -> Result<Stream, PAErr> {
+        let ready = |stream: &Stream| match stream.get_state() {
+            State::Ready => Ok(true),
+            State::Failed | State::Terminated => Err(ctx.errno()),
+            _ => Ok(false),
+        };
+        match self.wait {
+            None => {},
+            Some(Wait::Standard(mainloop)) => {
+                while !ready(&stream)? {
+                    match mainloop.iterate(true) {
+                        standard::IterateResult::Success(_) => {},
+                        standard::IterateResult::Err(e) => return Err(e),
+                        standard::IterateResult::Quit(_) => return Err(PAErr::from(Code::Killed)),
+                    }
+                }
+            },
+            Some(Wait::Threaded(mainloop, guard)) => {
+                let handle = mainloop.handle();
+                stream.set_state_callback(Some(Box::new(move || {
+                    let _ = handle.signal(false);
+                })));
+                let result = loop {
+                    match ready(&stream) {
+                        Ok(false) => guard.wait(),
+                        r => break r,
+                    }
+                };
+                stream.set_state_callback(None);
+                result?;
+            },
+        }
+        Ok(stream)
+    }
+}
+
+/// Makes a builder for 16-bit stereo at 44.1 kHz, for the tests of [`StreamBuilder`].
+#[cfg(test)]
+fn test_builder() -> StreamBuilder<'static> {
+    let spec = sample::Spec { format: sample::Format::S16le, rate: 44_100, channels: 2 };
+    StreamBuilder::new("Test", &spec)
+}
+
+/// Check that explicit buffer attributes take precedence over a latency
+#[test]
+fn stream_builder_attr_precedence() {
+    let attr = def::BufferAttr { tlength: 4096, ..def::BufferAttr::SERVER_DEFAULT };
+    let builder = test_builder()
+        .buffer_attr(&attr)
+        .latency(MicroSeconds::from_millis(50).unwrap())
+        .flags(FlagSet::START_CORKED);
+    for direction in [direction::FlagSet::OUTPUT, direction::FlagSet::INPUT] {
+        assert_eq!(builder.attr_and_flags(direction), Ok((Some(attr), FlagSet::START_CORKED)));
+    }
+    assert_eq!(test_builder().attr_and_flags(direction::FlagSet::OUTPUT),
+        Ok((None, FlagSet::NOFLAGS)));
+}
+
+/// Check that a latency implies adjusting the latency, other than when power saving
+#[test]
+fn stream_builder_adjust_latency() {
+    let latency = MicroSeconds::from_millis(50).unwrap();
+    let modes = [def::LatencyMode::LowLatency, def::LatencyMode::Balanced,
+        def::LatencyMode::PowerSaving, def::LatencyMode::Capture];
+    for mode in modes {
+        let builder = test_builder()
+            .latency(latency)
+            .latency_mode(mode)
+            .flags(FlagSet::START_CORKED);
+        let (attr, flags) = builder.attr_and_flags(direction::FlagSet::OUTPUT).unwrap();
+        assert_eq!(attr, def::BufferAttr::for_latency(&builder.spec, latency, mode));
+        match mode {
+            def::LatencyMode::PowerSaving => assert_eq!(flags, FlagSet::START_CORKED),
+            _ => assert_eq!(flags, FlagSet::START_CORKED | FlagSet::ADJUST_LATENCY),
+        }
+    }
+}
+
+/// Check the default latency mode of each direction
+#[test]
+fn stream_builder_default_latency_mode() {
+    let latency = MicroSeconds::from_millis(50).unwrap();
+    let builder = test_builder().latency(latency);
+    let expected = |mode| Ok((def::BufferAttr::for_latency(&builder.spec, latency, mode),
+        FlagSet::ADJUST_LATENCY));
+    assert_eq!(builder.attr_and_flags(direction::FlagSet::OUTPUT),
+        expected(def::LatencyMode::Balanced));
+    assert_eq!(builder.attr_and_flags(direction::FlagSet::INPUT),
+        expected(def::LatencyMode::Capture));
+}
+
+/// Check that an invalid sample spec, or a channel map not matching it, is rejected
+#[test]
+fn stream_builder_invalid() {
+    let invalid = Err(PAErr::from(Code::Invalid));
+    assert_eq!(test_builder().validate(), Ok(()));
+
+    let mut builder = test_builder();
+    builder.spec.channels = 0;
+    assert_eq!(builder.validate(), invalid);
+    let latency = MicroSeconds::from_millis(50).unwrap();
+    assert_eq!(builder.latency(latency).attr_and_flags(direction::FlagSet::OUTPUT).map(|_| ()),
+        invalid);
+
+    let mut map = channelmap::Map::default();
+    map.set_len(2);
+    map.get_mut().copy_from_slice(&[channelmap::Position::FrontLeft,
+        channelmap::Position::FrontRight]);
+    assert_eq!(test_builder().channel_map(&map).validate(), Ok(()));
+    map.set_len(1);
+    map.get_mut()[0] = channelmap::Position::Mono;
+    assert_eq!(test_builder().channel_map(&map).validate(), invalid);
+}
+
+/// A group of playback streams, operated together.
+///
+/// Streams created together with [`connect_playback()`](Self::connect_playback) are synchronized
+/// with each other by the server, such that they start and stop playing in lockstep. Note that the
+/// server only allows streams connected to the same sink to be synchronized. For streams on
+/// different sinks, use [`from_streams()`](Self::from_streams) instead, for which operations are
+/// issued together, but without any guarantee of taking effect at the same time; the combined
+/// timing can be used to keep an eye on how far apart they are.
+pub struct StreamGroup {
+    /// The streams.
+    streams: Vec<Stream>,
+}
+
+/// Combined timing information of a [`StreamGroup`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GroupTiming {
+    /// The time of each stream, where known. See [`Stream::get_time()`].
+    pub times: Vec<Option<MicroSeconds>>,
+    /// The latency of each stream, as a signed number of microseconds, where known. See
+    /// [`Stream::get_latency()`].
+    pub latencies: Vec<Option<i64>>,
+}
+
+impl GroupTiming {
+    /// Gets the difference between the earliest and latest of the known stream times, or `None` if
+    /// fewer than two are known.
+    pub fn spread(&self) -> Option<MicroSeconds> {
+        let mut known = self.times.iter().flatten();
+        let first = *known.next()?;
+        let (min, max, count) = known.fold((first, first, 1), |(min, max, count), &t| {
+            (std::cmp::min(min, t), std::cmp::max(max, t), count + 1)
+        });
+        match count {
+            1 => None,
+            _ => Some(max - min),
+        }
+    }
+}
+
+/// Check the spread of stream times, ignoring unknown ones
+#[test]
+fn group_timing_spread() {
+    let timing = |times: &[Option<u64>]| GroupTiming {
+        times: times.iter().map(|t| t.map(MicroSeconds)).collect(),
+        latencies: Vec::new(),
+    };
+    assert_eq!(timing(&[]).spread(), None);
+    assert_eq!(timing(&[Some(5), None]).spread(), None);
+    assert_eq!(timing(&[Some(5), None, Some(20), Some(12)]).spread(), Some(MicroSeconds(15)));
+}
+
+impl StreamGroup {
+    /// Creates and connects a group of playback streams, synchronized with each other.
+    ///
+    /// Each stream is created and connected as per [`StreamBuilder::connect_playback()`], with all
+    /// but the first being synchronized with the first, in place of any sync stream set on the
+    /// builders. The streams must be connected to the same sink. Should any fail, the error is
+    /// returned, with those already created being disconnected.
+    pub fn connect_playback<'a, I>(ctx: &mut Context, builders: I) -> Result<Self, PAErr>
+        where I: IntoIterator<Item = StreamBuilder<'a>>
+    {
+        let mut streams: Vec<Stream> = Vec::new();
+        for builder in builders {
+            let stream = builder.connect_playback_synced(ctx, streams.first_mut())?;
+            streams.push(stream);
+        }
+        Ok(Self { streams })
+    }
+
+    /// Creates a group from existing streams, which are not synchronized with each other unless
+    /// connected that way.
+    #[inline]
+    pub fn from_streams(streams: Vec<Stream>) -> Self {
+        Self { streams }
+    }
+
+    /// Gets the streams.
+    #[inline]
+    pub fn streams(&self) -> &[Stream] {
+        &self.streams
+    }
+
+    /// Gets the streams, for modification.
+    #[inline]
+    pub fn streams_mut(&mut self) -> &mut [Stream] {
+        &mut self.streams
+    }
+
+    /// Converts into the underlying streams.
+    #[inline]
+    pub fn into_streams(self) -> Vec<Stream> {
+        self.streams
+    }
+
+    /// Checks whether or not all of the streams are ready.
+    pub fn is_ready(&self) -> bool {
+        self.streams.iter().all(|s| s.get_state() == State::Ready)
+    }
+
+    /// Pauses or resumes playback of all of the streams.
+    ///
+    /// The callback, if given, is called once all have completed, with success only if all
+    /// succeeded. See [`Stream::set_corked_state()`].
+    pub fn set_corked_state(&mut self, state: bool,
+        callback: Option<Box<dyn FnMut(bool) + 'static>>) -> Vec<Operation<dyn FnMut(bool)>>
+    {
+        self.for_each(callback, |stream, cb| stream.set_corked_state(state, Some(cb)))
+    }
+
+    /// Pauses playback of all of the streams.
+    ///
+    /// This simply calls [`set_corked_state()`](Self::set_corked_state) with a value of `true`.
+    #[inline]
+    pub fn cork(&mut self, callback: Option<Box<dyn FnMut(bool) + 'static>>)
+        -> Vec<Operation<dyn FnMut(bool)>>
+    {
+        self.set_corked_state(true, callback)
+    }
+
+    /// Resumes playback of all of the streams.
+    ///
+    /// This simply calls [`set_corked_state()`](Self::set_corked_state) with a value of `false`.
+    #[inline]
+    pub fn uncork(&mut self, callback: Option<Box<dyn FnMut(bool) + 'static>>)
+        -> Vec<Operation<dyn FnMut(bool)>>
+    {
+        self.set_corked_state(false, callback)
+    }
+
+    /// Drains all of the streams.
+    ///
+    /// The callback, if given, is called once all have been drained, with success only if all
+    /// succeeded. See [`Stream::drain()`].
+    pub fn drain(&mut self, callback: Option<Box<dyn FnMut(bool) + 'static>>)
+        -> Vec<Operation<dyn FnMut(bool)>>
+    {
+        self.for_each(callback, |stream, cb| stream.drain(Some(cb)))
+    }
+
+    /// Flushes the playback buffers of all of the streams.
+    ///
+    /// The callback, if given, is called once all have completed, with success only if all
+    /// succeeded. See [`Stream::flush()`].
+    pub fn flush(&mut self, callback: Option<Box<dyn FnMut(bool) + 'static>>)
+        -> Vec<Operation<dyn FnMut(bool)>>
+    {
+        self.for_each(callback, |stream, cb| stream.flush(Some(cb)))
+    }
+
+    /// Requests a timing info update for all of the streams.
+    ///
+    /// The callback, if given, is called once all have completed, with success only if all
+    /// succeeded, at which point [`timing()`](Self::timing) gives the updated information.
+    pub fn update_timing_info(&mut self, callback: Option<Box<dyn FnMut(bool) + 'static>>)
+        -> Vec<Operation<dyn FnMut(bool)>>
+    {
+        self.for_each(callback, |stream, cb| stream.update_timing_info(Some(cb)))
+    }
+
+    /// Gets the combined timing information of the streams.
+    pub fn timing(&self) -> GroupTiming {
+        GroupTiming {
+            times: self.streams.iter().map(|s| s.get_time().ok().flatten()).collect(),
+            latencies: self.streams.iter()
+                .map(|s| s.get_latency().ok().and_then(|l| l.as_micros_signed()))
+                .collect(),
+        }
+    }
+
+    /// Starts an operation on each stream, with a callback joining their completion.
+    fn for_each<F>(&mut self, callback: Option<Box<dyn FnMut(bool) + 'static>>, mut start: F)
+        -> Vec<Operation<dyn FnMut(bool)>>
+        where F: FnMut(&mut Stream, Box<dyn FnMut(bool) + 'static>) -> Operation<dyn FnMut(bool)>
+    {
+        // Remaining count, and success so far
+        let join = Rc::new(Cell::new((self.streams.len(), true)));
+        let callback = Rc::new(RefCell::new(callback));
+        if self.streams.is_empty() {
+            if let Some(cb) = callback.borrow_mut().as_mut() {
+                (cb)(true);
+            }
+        }
+        self.streams.iter_mut().map(|stream| {
+            let join = Rc::clone(&join);
+            let callback = Rc::clone(&callback);
+            start(stream, Box::new(move |success| {
+                let (remaining, ok) = join.get();
+                join.set((remaining - 1, ok && success));
+                if remaining == 1 {
+                    if let Some(cb) = callback.borrow_mut().as_mut() {
+                        (cb)(ok && success);
+                    }
+                }
+            }))
+        }).collect()
+    }
+}
+
 impl Drop for Stream {
     fn drop(&mut self) {
-        // Throw away the `Result` from disconnecting, it may legitimately be bad if stream failed.
-        // See https://github.com/jnqnfe/pulse-binding-rust/issues/11
-        let _ = self.disconnect();
-        unsafe { capi::pa_stream_unref(self.ptr) };
+        if !self.weak {
+            // Throw away the `Result` from disconnecting, it may legitimately be bad if stream
+            // failed. See https://github.com/jnqnfe/pulse-binding-rust/issues/11
+            let _ = self.disconnect();
+            unsafe { capi::pa_stream_unref(self.ptr) };
+        }
         self.ptr = null_mut::<StreamInternal>();
     }
 }
 
+/// Proxy for freeing data written with [`Stream::write_owned()`] and similar.
+///
+/// Warning: This is for single-use cases only! It destroys the owner of the data.
+#[cfg(any(doc, feature = "pa_v6"))]
+extern "C"
+fn free_owner_proxy<T>(userdata: *mut c_void) {
+    let _ = std::panic::catch_unwind(|| {
+        drop(unsafe { Box::from_raw(userdata as *mut T) });
+    });
+}
+
 /// Proxy for completion success callbacks.
 ///
 /// Warning: This is for single-use cases only! It destroys the actual closure callback.
@@ -22,6 +22,8 @@ use super::op_err;
 /// This is an unsigned 64-bit type, and thus represents absolute values only.
 #[repr(transparent)]
 #[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct MicroSeconds(pub u64);
 
 impl MicroSeconds {
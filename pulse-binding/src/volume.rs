@@ -67,6 +67,8 @@ use crate::channelmap::{Map, Position, PositionMask, POSITION_MASK_ALL};
 
 /// Software volume expressed as an integer.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct Volume(pub capi::pa_volume_t);
 
 impl Default for Volume {
@@ -160,6 +162,50 @@ impl PartialEq<Volume> for ChannelVolumes {
     }
 }
 
+/// Serialized as the sequence of volumes of the channels in use.
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl serde::Serialize for ChannelVolumes {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.get())
+    }
+}
+
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<'de> serde::Deserialize<'de> for ChannelVolumes {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let volumes = Vec::<Volume>::deserialize(deserializer)?;
+        if volumes.len() > Self::CHANNELS_MAX as usize {
+            return Err(serde::de::Error::invalid_length(volumes.len(),
+                &"no more than `CHANNELS_MAX` volumes"));
+        }
+        let mut cv = Self::default();
+        cv.set_len(volumes.len() as u8);
+        cv.get_mut().copy_from_slice(&volumes);
+        Ok(cv)
+    }
+}
+
+/// Check serialization round-trips, and that more than `CHANNELS_MAX` volumes are rejected
+#[cfg(feature = "serde")]
+#[test]
+fn channel_volumes_serde() {
+    let mut cv = ChannelVolumes::default();
+    cv.set_len(2);
+    cv.get_mut().copy_from_slice(&[Volume::NORMAL, Volume::MUTED]);
+    let json = serde_json::to_string(&cv).unwrap();
+    assert_eq!(json, format!("[{},0]", Volume::NORMAL.0));
+    assert_eq!(serde_json::from_str::<ChannelVolumes>(&json).unwrap().get(), cv.get());
+
+    let full = vec![Volume::NORMAL; ChannelVolumes::CHANNELS_MAX as usize];
+    let value = serde_json::to_value(&full).unwrap();
+    assert_eq!(serde_json::from_value::<ChannelVolumes>(value).unwrap().get(), &full[..]);
+    let too_many = vec![Volume::NORMAL; ChannelVolumes::CHANNELS_MAX as usize + 1];
+    let value = serde_json::to_value(too_many).unwrap();
+    assert!(serde_json::from_value::<ChannelVolumes>(value).is_err());
+}
+
 /// Converts a decibel value to a volume (amplitude, not power).
 ///
 /// This is only valid for software volumes!